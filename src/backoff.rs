@@ -0,0 +1,129 @@
+// MeshX - The Immutable Global Device Mesh
+// Re-validation backoff for repeatedly failing nodes
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{MeshXNode, PopValidator, ValidationError, ValidationOutcome};
+
+// After its nth consecutive failure a node isn't re-validated for
+// base_epochs * 2^(n-1) epochs, capped at max_epochs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackoffPolicy {
+    pub base_epochs: u64,
+    pub max_epochs: u64,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            base_epochs: 1,
+            max_epochs: 64,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    pub fn interval(&self, consecutive_failures: u32) -> u64 {
+        let doublings = consecutive_failures.saturating_sub(1).min(63);
+        self.base_epochs
+            .saturating_mul(1u64 << doublings)
+            .min(self.max_epochs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureBackoff {
+    pub consecutive_failures: u32,
+    pub retry_epoch: u64, // First epoch the node is evaluated again
+}
+
+impl PopValidator {
+    // Validate a node unless it's still backing off from earlier failures.
+    // A failure extends the backoff; a success clears it.
+    pub(crate) fn validate_with_backoff(
+        &mut self,
+        node: &MeshXNode,
+    ) -> Result<ValidationOutcome, ValidationError> {
+        let Some(policy) = self.validation_backoff.clone() else {
+            return self.validate_node(node);
+        };
+        if let Some(backoff) = self.failure_backoff.get(&node.pubkey) {
+            if self.current_epoch < backoff.retry_epoch {
+                return Err(ValidationError::RetryBackoff);
+            }
+        }
+
+        let result = self.validate_node(node);
+        if result.is_ok() {
+            self.failure_backoff.remove(&node.pubkey);
+        } else {
+            let current_epoch = self.current_epoch;
+            let backoff = self
+                .failure_backoff
+                .entry(node.pubkey)
+                .or_insert(FailureBackoff {
+                    consecutive_failures: 0,
+                    retry_epoch: current_epoch,
+                });
+            backoff.consecutive_failures += 1;
+            backoff.retry_epoch = current_epoch + policy.interval(backoff.consecutive_failures);
+        }
+        result
+    }
+
+    pub fn retry_epoch(&self, pubkey: &PublicKey) -> Option<u64> {
+        self.failure_backoff
+            .get(pubkey)
+            .map(|backoff| backoff.retry_epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dcap::tests::test_quote;
+    use crate::proof_of_presence::tests::test_network;
+
+    #[test]
+    fn test_backoff_grows_and_resets() {
+        let (mut validator, keys) = test_network(4);
+        validator.validation_backoff = Some(BackoffPolicy {
+            base_epochs: 1,
+            max_epochs: 4,
+        });
+        let mut node = validator.nodes[&keys[0]].clone();
+        node.tee_attestation.quote.clear();
+
+        let mut intervals = Vec::new();
+        for _ in 0..4 {
+            assert_eq!(
+                validator.register_node(node.clone()),
+                Err(ValidationError::InvalidQuote)
+            );
+            let retry_epoch = validator.retry_epoch(&keys[0]).unwrap();
+            intervals.push(retry_epoch - validator.current_epoch);
+
+            // Skipped without validation until the retry epoch
+            validator.current_epoch = retry_epoch - 1;
+            assert_eq!(
+                validator.register_node(node.clone()),
+                Err(ValidationError::RetryBackoff)
+            );
+            validator.current_epoch = retry_epoch;
+        }
+        assert_eq!(intervals, vec![1, 2, 4, 4]);
+
+        node.tee_attestation.quote = test_quote(0);
+        validator.register_node(node.clone()).unwrap();
+        assert_eq!(validator.retry_epoch(&keys[0]), None);
+
+        node.tee_attestation.quote.clear();
+        assert!(validator.register_node(node).is_err());
+        assert_eq!(
+            validator.retry_epoch(&keys[0]),
+            Some(validator.current_epoch + 1)
+        );
+    }
+}
@@ -0,0 +1,77 @@
+// MeshX - The Immutable Global Device Mesh
+// Node bans
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+
+use crate::proof_of_presence::{MeshXNode, PopValidator};
+
+impl PopValidator {
+    // Remove a node and refuse its registrations, permanently or until
+    // `expires_epoch`. Re-banning replaces any earlier expiry.
+    pub fn ban(&mut self, pubkey: &PublicKey, expires_epoch: Option<u64>) {
+        self.nodes.remove(pubkey);
+        self.pending_nodes.remove(pubkey);
+        self.bans.insert(*pubkey, expires_epoch);
+    }
+
+    // A time-limited ban covers epochs before its expiry
+    pub fn is_banned(&self, pubkey: &PublicKey, current_epoch: u64) -> bool {
+        match self.bans.get(pubkey) {
+            Some(None) => true,
+            Some(Some(expires_epoch)) => current_epoch < *expires_epoch,
+            None => false,
+        }
+    }
+
+    // A node registering after its ban expired is on probation: the ban is
+    // cleared and it restarts at `probation_reputation`
+    pub(crate) fn end_expired_ban(&mut self, node: &mut MeshXNode) {
+        if self.bans.remove(&node.pubkey).is_some() {
+            node.reputation_score = self.probation_reputation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::proof_of_presence::tests::test_network;
+    use crate::proof_of_presence::ValidationError;
+
+    #[test]
+    fn test_time_limited_ban_and_probation() {
+        let (mut validator, keys) = test_network(4);
+        let node = validator.nodes[&keys[0]].clone();
+        validator.current_epoch = 10;
+        validator.ban(&keys[0], Some(12));
+        validator.ban(&keys[1], None);
+        assert!(!validator.nodes.contains_key(&keys[0]));
+
+        validator.current_epoch = 11;
+        assert!(validator.is_banned(&keys[0], 11));
+        assert_eq!(
+            validator.register_node(node.clone()),
+            Err(ValidationError::Banned)
+        );
+
+        validator.current_epoch = 12;
+        assert!(!validator.is_banned(&keys[0], 12));
+        assert!(validator.is_banned(&keys[1], 1_000));
+
+        // A registration that fails late doesn't use up the probation
+        let attested = node.tee_attestation.timestamp;
+        validator
+            .attestation_timestamps
+            .insert(keys[0], (12, attested + 1));
+        assert_eq!(
+            validator.register_node(node.clone()),
+            Err(ValidationError::AttestationRegression)
+        );
+        assert!(validator.bans.contains_key(&keys[0]));
+
+        validator.attestation_timestamps.remove(&keys[0]);
+        validator.register_node(node).unwrap();
+        assert_eq!(validator.nodes[&keys[0]].reputation_score, 0.0);
+        assert!(!validator.bans.contains_key(&keys[0]));
+    }
+}
@@ -0,0 +1,106 @@
+// MeshX - The Immutable Global Device Mesh
+// Bootstrapping from trusted state snapshots
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use crate::proof_of_presence::{
+    compute_state_fingerprint, MeshXNode, PopValidator, ValidationError,
+};
+
+// The fingerprinted part of a validator's state, as written for new nodes to
+// start from instead of rebuilding the registry and latency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub epoch: u64,
+    pub nodes: Vec<MeshXNode>,
+    pub latency_matrix: Vec<(PublicKey, PublicKey, u32)>,
+}
+
+impl PopValidator {
+    pub fn write_state_snapshot(&self, writer: impl Write) -> serde_json::Result<()> {
+        let snapshot = StateSnapshot {
+            epoch: self.current_epoch,
+            nodes: self.nodes.values().cloned().collect(),
+            latency_matrix: self
+                .latency_matrix
+                .iter()
+                .map(|((from, to), latency)| (*from, *to, *latency))
+                .collect(),
+        };
+        serde_json::to_writer(writer, &snapshot)
+    }
+
+    // Adopt a snapshot's epoch, registry and latency graph, but only if its
+    // state fingerprint matches one obtained from a trusted source out of
+    // band. The validator is unchanged when the snapshot is rejected.
+    pub fn bootstrap_from_snapshot(
+        &mut self,
+        reader: impl Read,
+        expected_fingerprint: [u8; 32],
+    ) -> Result<(), ValidationError> {
+        let snapshot: StateSnapshot =
+            serde_json::from_reader(reader).map_err(|_| ValidationError::MalformedStateSnapshot)?;
+        let nodes: HashMap<PublicKey, MeshXNode> = snapshot
+            .nodes
+            .into_iter()
+            .map(|node| (node.pubkey, node))
+            .collect();
+        let latency_matrix: HashMap<(PublicKey, PublicKey), u32> = snapshot
+            .latency_matrix
+            .into_iter()
+            .map(|(from, to, latency)| ((from, to), latency))
+            .collect();
+        if compute_state_fingerprint(snapshot.epoch, &nodes, &latency_matrix)
+            != expected_fingerprint
+        {
+            return Err(ValidationError::StateFingerprintMismatch);
+        }
+
+        self.current_epoch = snapshot.epoch;
+        self.nodes = nodes;
+        self.latency_matrix = latency_matrix;
+        // Timestamps belonged to the edges just replaced
+        self.latency_timestamps.clear();
+        self.latency_conflicts.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+
+    #[test]
+    fn test_bootstrap_from_snapshot() {
+        let (mut source, _) = test_network(4);
+        source.current_epoch = 12;
+        let mut blob = Vec::new();
+        source.write_state_snapshot(&mut blob).unwrap();
+        let fingerprint = source.state_fingerprint();
+
+        let mut fresh = PopValidator::new(4);
+        let mut wrong = fingerprint;
+        wrong[0] ^= 1;
+        assert_eq!(
+            fresh.bootstrap_from_snapshot(blob.as_slice(), wrong),
+            Err(ValidationError::StateFingerprintMismatch)
+        );
+        assert!(fresh.nodes.is_empty());
+        assert_eq!(
+            fresh.bootstrap_from_snapshot(&blob[1..], fingerprint),
+            Err(ValidationError::MalformedStateSnapshot)
+        );
+
+        fresh
+            .bootstrap_from_snapshot(blob.as_slice(), fingerprint)
+            .unwrap();
+        assert_eq!(fresh.current_epoch, 12);
+        assert_eq!(fresh.nodes.len(), 4);
+        assert_eq!(fresh.state_fingerprint(), fingerprint);
+    }
+}
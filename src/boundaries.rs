@@ -0,0 +1,198 @@
+// MeshX - The Immutable Global Device Mesh
+// Custom shard boundaries
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::Deserialize;
+
+use crate::proof_of_presence::{GeoLocation, PopValidator, Shard};
+
+// One boundary polygon per shard, loaded from GeoJSON for private meshes or
+// refined borders. Points outside every polygon fall back to the built-in
+// regions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShardBoundaries {
+    polygons: Vec<(Shard, Vec<Vec<[f64; 2]>>)>, // Outer ring then holes, as [lon, lat]
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum BoundaryError {
+    #[error("Invalid boundary GeoJSON: {0}")]
+    InvalidGeoJson(String),
+    #[error("Shard {0:?} has more than one boundary polygon")]
+    DuplicateShard(Shard),
+    #[error("Shard {0:?} has no boundary polygon")]
+    MissingShard(Shard),
+    #[error("Boundary polygon for {0:?} has a ring with fewer than 4 positions")]
+    DegenerateRing(Shard),
+}
+
+// The subset of a GeoJSON FeatureCollection boundaries are read from: each
+// feature a Polygon naming its shard in `properties.shard`
+#[derive(Deserialize)]
+struct FeatureCollection {
+    features: Vec<Feature>,
+}
+
+#[derive(Deserialize)]
+struct Feature {
+    properties: FeatureProperties,
+    geometry: Geometry,
+}
+
+#[derive(Deserialize)]
+struct FeatureProperties {
+    shard: Shard,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "coordinates")]
+enum Geometry {
+    Polygon(Vec<Vec<[f64; 2]>>),
+}
+
+impl ShardBoundaries {
+    pub fn from_geojson(geojson: &str) -> Result<Self, BoundaryError> {
+        let collection: FeatureCollection = serde_json::from_str(geojson)
+            .map_err(|error| BoundaryError::InvalidGeoJson(error.to_string()))?;
+
+        let mut polygons: Vec<(Shard, Vec<Vec<[f64; 2]>>)> = Vec::new();
+        for feature in collection.features {
+            let shard = feature.properties.shard;
+            let Geometry::Polygon(rings) = feature.geometry;
+            if polygons.iter().any(|(existing, _)| *existing == shard) {
+                return Err(BoundaryError::DuplicateShard(shard));
+            }
+            if rings.is_empty() || rings.iter().any(|ring| ring.len() < 4) {
+                return Err(BoundaryError::DegenerateRing(shard));
+            }
+            polygons.push((shard, rings));
+        }
+        if let Some(missing) = Shard::all()
+            .into_iter()
+            .find(|shard| !polygons.iter().any(|(existing, _)| existing == shard))
+        {
+            return Err(BoundaryError::MissingShard(missing));
+        }
+        Ok(Self { polygons })
+    }
+
+    // Shard whose polygon contains the point, if any
+    pub fn shard_at(&self, location: &GeoLocation) -> Option<Shard> {
+        let point = [location.longitude, location.latitude];
+        self.polygons
+            .iter()
+            .find(|(_, rings)| {
+                ring_contains(&rings[0], point)
+                    && !rings[1..].iter().any(|hole| ring_contains(hole, point))
+            })
+            .map(|(shard, _)| *shard)
+    }
+}
+
+// Even-odd ray casting in the plane of longitude and latitude
+pub(crate) fn ring_contains(ring: &[[f64; 2]], [x, y]: [f64; 2]) -> bool {
+    let mut inside = false;
+    for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+        if (a[1] > y) != (b[1] > y) && x < a[0] + (y - a[1]) / (b[1] - a[1]) * (b[0] - a[0]) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+impl PopValidator {
+    // Replace the built-in continental regions with GeoJSON boundaries
+    pub fn load_shard_boundaries(&mut self, geojson: &str) -> Result<(), BoundaryError> {
+        self.shard_boundaries = Some(ShardBoundaries::from_geojson(geojson)?);
+        Ok(())
+    }
+
+    // Shard for a location under the loaded boundaries, or the built-in
+    // regions when none are loaded or no polygon contains it
+    pub fn shard_for_location(&self, location: &GeoLocation) -> Shard {
+        self.shard_boundaries
+            .as_ref()
+            .and_then(|boundaries| boundaries.shard_at(location))
+            .unwrap_or_else(|| Self::assign_shard(location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 10 by 10 degree square per shard, stacked along the equator, with
+    // Africa's square moved over London
+    fn custom_geojson(shards: &[Shard]) -> String {
+        let features: Vec<_> = shards
+            .iter()
+            .enumerate()
+            .map(|(i, shard)| {
+                let (lon, lat) = match shard {
+                    Shard::Africa => (-5.0, 45.0),
+                    _ => (-170.0 + i as f64 * 20.0, -5.0),
+                };
+                serde_json::json!({
+                    "type": "Feature",
+                    "properties": { "shard": shard },
+                    "geometry": {
+                        "type": "Polygon",
+                        "coordinates": [[
+                            [lon, lat],
+                            [lon + 10.0, lat],
+                            [lon + 10.0, lat + 10.0],
+                            [lon, lat + 10.0],
+                            [lon, lat],
+                        ]],
+                    },
+                })
+            })
+            .collect();
+        serde_json::json!({ "type": "FeatureCollection", "features": features }).to_string()
+    }
+
+    #[test]
+    fn test_custom_boundary_reassigns_point() {
+        let london = GeoLocation {
+            latitude: 51.5,
+            longitude: -0.1,
+            accuracy_meters: 50.0,
+        };
+        let tokyo = GeoLocation {
+            latitude: 35.7,
+            longitude: 139.7,
+            accuracy_meters: 50.0,
+        };
+        let mut validator = PopValidator::new(1);
+        assert_eq!(validator.shard_for_location(&london), Shard::Europe);
+
+        validator
+            .load_shard_boundaries(&custom_geojson(&Shard::all()))
+            .unwrap();
+        assert_eq!(validator.shard_for_location(&london), Shard::Africa);
+        // Outside every polygon the built-in regions still apply
+        assert_eq!(validator.shard_for_location(&tokyo), Shard::Asia);
+    }
+
+    #[test]
+    fn test_boundaries_need_one_polygon_per_shard() {
+        let shards = Shard::all();
+        assert_eq!(
+            ShardBoundaries::from_geojson(&custom_geojson(&shards[1..])),
+            Err(BoundaryError::MissingShard(shards[0]))
+        );
+
+        let mut duplicated = shards.to_vec();
+        duplicated.push(Shard::Asia);
+        assert_eq!(
+            ShardBoundaries::from_geojson(&custom_geojson(&duplicated)),
+            Err(BoundaryError::DuplicateShard(Shard::Asia))
+        );
+
+        let multi = custom_geojson(&shards).replacen("\"Polygon\"", "\"MultiPolygon\"", 1);
+        assert!(matches!(
+            ShardBoundaries::from_geojson(&multi),
+            Err(BoundaryError::InvalidGeoJson(_))
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+// MeshX - The Immutable Global Device Mesh
+// Shard capacity for workload placement
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{PopValidator, Shard, ValidationError};
+
+// Resources summed over a set of nodes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateResources {
+    pub cpu_cores: u64,
+    pub ram_gb: u64,
+    pub bandwidth_mbps: u64,
+}
+
+impl AggregateResources {
+    // Whether every resource is at least the requirement's
+    pub fn meets(&self, required: &AggregateResources) -> bool {
+        self.cpu_cores >= required.cpu_cores
+            && self.ram_gb >= required.ram_gb
+            && self.bandwidth_mbps >= required.bandwidth_mbps
+    }
+}
+
+impl PopValidator {
+    // Resources of the shard's validators selected for the epoch. Only
+    // nodes passing validation are selected, so these are verified figures.
+    pub fn shard_capacity(
+        &self,
+        shard: Shard,
+        epoch: u64,
+    ) -> Result<AggregateResources, ValidationError> {
+        let mut total = AggregateResources::default();
+        for pubkey in self.select_validators(epoch)? {
+            let Some(node) = self.nodes.get(&pubkey).filter(|node| node.shard == shard) else {
+                continue;
+            };
+            total.cpu_cores += node.resources.cpu_cores as u64;
+            total.ram_gb += node.resources.ram_gb as u64;
+            total.bandwidth_mbps += node.resources.bandwidth_mbps as u64;
+        }
+        Ok(total)
+    }
+
+    // Check that the shard's selected validators can collectively host a
+    // workload needing `required`
+    pub fn shard_capacity_check(
+        &self,
+        shard: Shard,
+        epoch: u64,
+        required: &AggregateResources,
+    ) -> Result<(), ValidationError> {
+        if self.shard_capacity(shard, epoch)?.meets(required) {
+            Ok(())
+        } else {
+            Err(ValidationError::InsufficientShardCapacity)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+
+    #[test]
+    fn test_shard_capacity_check() {
+        // Four selected nodes of 8 cores, 16 GB and 100 Mbps each
+        let (validator, _) = test_network(4);
+        let capacity = validator.shard_capacity(Shard::Europe, 1).unwrap();
+        assert_eq!(
+            capacity,
+            AggregateResources {
+                cpu_cores: 32,
+                ram_gb: 64,
+                bandwidth_mbps: 400,
+            }
+        );
+
+        let fits = AggregateResources {
+            cpu_cores: 32,
+            ram_gb: 48,
+            bandwidth_mbps: 200,
+        };
+        assert_eq!(
+            validator.shard_capacity_check(Shard::Europe, 1, &fits),
+            Ok(())
+        );
+
+        let too_much_ram = AggregateResources { ram_gb: 65, ..fits };
+        assert_eq!(
+            validator.shard_capacity_check(Shard::Europe, 1, &too_much_ram),
+            Err(ValidationError::InsufficientShardCapacity)
+        );
+
+        // No Asian node was selected
+        assert_eq!(
+            validator.shard_capacity_check(Shard::Asia, 1, &fits),
+            Err(ValidationError::InsufficientShardCapacity)
+        );
+    }
+}
@@ -0,0 +1,202 @@
+// MeshX - The Immutable Global Device Mesh
+// Attestation nonce challenges
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha3::{Digest, Sha3_256};
+
+use crate::proof_of_presence::{PopValidator, TeeAttestation, TeeType, ValidationError};
+
+// A nonce issued to a node, answered by an attestation produced after it
+// and bound to the nonce by the attestation signer
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestationChallenge {
+    pub nonce: [u8; 32],
+    pub tee_type: TeeType,
+    pub issued_at: u64,
+}
+
+impl PopValidator {
+    // Issue a fresh nonce to a node, replacing any outstanding challenge for
+    // it. Challenges past their type's window are dropped on the way.
+    pub fn issue_attestation_challenge(
+        &mut self,
+        pubkey: &PublicKey,
+        tee_type: TeeType,
+    ) -> [u8; 32] {
+        let now = self.now_secs();
+        let policy = &self.policy;
+        self.attestation_challenges.retain(|_, challenge| {
+            now.saturating_sub(challenge.issued_at)
+                <= policy.challenge_window_secs(&challenge.tee_type)
+        });
+
+        let mut nonce = [0u8; 32];
+        OsRng.fill_bytes(&mut nonce);
+        self.attestation_challenges.insert(
+            *pubkey,
+            AttestationChallenge {
+                nonce,
+                tee_type,
+                issued_at: now,
+            },
+        );
+        nonce
+    }
+
+    // Accept a node's attestation as the answer to its outstanding challenge.
+    // The attestation must be timestamped no earlier than the challenge, and
+    // `signature` must be its signer's over `challenge_response_payload`, so
+    // an attestation captured before the nonce existed can't answer it. The
+    // challenge is consumed whether or not the response is accepted, so a
+    // nonce is never answered twice.
+    pub fn respond_to_challenge(
+        &mut self,
+        pubkey: &PublicKey,
+        nonce: &[u8; 32],
+        attestation: &TeeAttestation,
+        signature: &Signature,
+    ) -> Result<(), ValidationError> {
+        let challenge = match self.attestation_challenges.remove(pubkey) {
+            Some(challenge) if challenge.nonce == *nonce => challenge,
+            Some(challenge) => {
+                // A wrong nonce doesn't burn the real one
+                self.attestation_challenges.insert(*pubkey, challenge);
+                return Err(ValidationError::UnknownChallenge);
+            }
+            None => return Err(ValidationError::UnknownChallenge),
+        };
+        if attestation.tee_type != challenge.tee_type {
+            return Err(ValidationError::UnknownChallenge);
+        }
+        let elapsed = self.now_secs().saturating_sub(challenge.issued_at);
+        if elapsed > self.policy.challenge_window_secs(&challenge.tee_type) {
+            return Err(ValidationError::ChallengeExpired);
+        }
+        if attestation.timestamp < challenge.issued_at {
+            return Err(ValidationError::StaleAttestation);
+        }
+        attestation
+            .signer_pubkey
+            .verify(&challenge_response_payload(nonce, attestation), signature)
+            .map_err(|_| ValidationError::InvalidSignature)?;
+        self.verify_tee_attestation(attestation)
+    }
+}
+
+// What the attestation signer signs to answer a challenge with `attestation`
+pub fn challenge_response_payload(nonce: &[u8; 32], attestation: &TeeAttestation) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"MESHX_CHALLENGE_RESPONSE");
+    hasher.update(nonce);
+    hasher.update(serde_json::to_vec(attestation).expect("attestation serializes"));
+    hasher.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::presence::tests::test_keypair;
+    use crate::proof_of_presence::tests::{test_node, test_pubkey};
+    use ed25519_dalek::Signer;
+    use std::sync::Arc;
+
+    // Seed `seed`'s attestation at `timestamp`, signed over `nonce`
+    fn response(
+        seed: u8,
+        tee_type: TeeType,
+        timestamp: u64,
+        nonce: &[u8; 32],
+    ) -> (TeeAttestation, Signature) {
+        let mut attestation = test_node(seed, 50.0, 10.0, 100_000).tee_attestation;
+        attestation.tee_type = tee_type;
+        attestation.timestamp = timestamp;
+        let signature = test_keypair(seed).sign(&challenge_response_payload(nonce, &attestation));
+        (attestation, signature)
+    }
+
+    #[test]
+    fn test_challenge_windows_per_tee_type() {
+        let mut validator = PopValidator::new(1);
+        let clock = Arc::new(ManualClock::new(1_700_000_000));
+        validator.set_clock(clock.clone());
+        validator.policy.challenge_windows_secs = vec![(TeeType::AmdSev, 120)];
+        validator.policy.default_challenge_window_secs = 10;
+
+        let respond = |validator: &mut PopValidator, seed: u8, tee_type: TeeType, delay: u64| {
+            let node = test_pubkey(seed);
+            let nonce = validator.issue_attestation_challenge(&node, tee_type.clone());
+            clock.advance(delay);
+            let (attestation, signature) = response(seed, tee_type, validator.now_secs(), &nonce);
+            validator.respond_to_challenge(&node, &nonce, &attestation, &signature)
+        };
+
+        // Slow-quoting SEV hardware gets two minutes, SGX the 10s default
+        assert_eq!(respond(&mut validator, 1, TeeType::AmdSev, 90), Ok(()));
+        assert_eq!(
+            respond(&mut validator, 2, TeeType::IntelSgx, 90),
+            Err(ValidationError::ChallengeExpired)
+        );
+        assert_eq!(respond(&mut validator, 3, TeeType::IntelSgx, 5), Ok(()));
+        assert_eq!(
+            respond(&mut validator, 4, TeeType::AmdSev, 121),
+            Err(ValidationError::ChallengeExpired)
+        );
+
+        // Nonces are single-use
+        let node = test_pubkey(5);
+        let nonce = validator.issue_attestation_challenge(&node, TeeType::IntelSgx);
+        let (attestation, signature) = response(5, TeeType::IntelSgx, validator.now_secs(), &nonce);
+        assert_eq!(
+            validator.respond_to_challenge(&node, &[0; 32], &attestation, &signature),
+            Err(ValidationError::UnknownChallenge)
+        );
+        assert_eq!(
+            validator.respond_to_challenge(&node, &nonce, &attestation, &signature),
+            Ok(())
+        );
+        assert_eq!(
+            validator.respond_to_challenge(&node, &nonce, &attestation, &signature),
+            Err(ValidationError::UnknownChallenge)
+        );
+    }
+
+    #[test]
+    fn test_challenge_response_bound_to_nonce() {
+        let mut validator = PopValidator::new(1);
+        let clock = Arc::new(ManualClock::new(1_700_000_000));
+        validator.set_clock(clock.clone());
+        let node = test_pubkey(1);
+
+        // An attestation produced before the challenge was issued
+        let nonce = validator.issue_attestation_challenge(&node, TeeType::IntelSgx);
+        clock.advance(2);
+        let (attestation, signature) =
+            response(1, TeeType::IntelSgx, validator.now_secs() - 5, &nonce);
+        assert_eq!(
+            validator.respond_to_challenge(&node, &nonce, &attestation, &signature),
+            Err(ValidationError::StaleAttestation)
+        );
+
+        // A fresh attestation signed over some other nonce
+        let nonce = validator.issue_attestation_challenge(&node, TeeType::IntelSgx);
+        let (attestation, signature) =
+            response(1, TeeType::IntelSgx, validator.now_secs(), &[7; 32]);
+        assert_eq!(
+            validator.respond_to_challenge(&node, &nonce, &attestation, &signature),
+            Err(ValidationError::InvalidSignature)
+        );
+
+        // Or signed by a key other than the attestation's signer
+        let nonce = validator.issue_attestation_challenge(&node, TeeType::IntelSgx);
+        let (attestation, _) = response(1, TeeType::IntelSgx, validator.now_secs(), &nonce);
+        let signature = test_keypair(2).sign(&challenge_response_payload(&nonce, &attestation));
+        assert_eq!(
+            validator.respond_to_challenge(&node, &nonce, &attestation, &signature),
+            Err(ValidationError::InvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,163 @@
+// MeshX - The Immutable Global Device Mesh
+// Time sources for attestation freshness and epoch derivation
+// Copyright (c) 2025 MeshX Foundation
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Source of wall-clock and monotonic time. The validator anchors wall time
+// once and advances it with the monotonic clock, so a wall clock stepping
+// backwards can't make fresh attestations look stale or rewind epochs.
+pub trait Clock: Send + Sync {
+    // Seconds since the Unix epoch
+    fn wall_secs(&self) -> u64;
+
+    // Time elapsed on a clock that never goes backwards
+    fn monotonic(&self) -> Duration;
+}
+
+pub struct SystemClock {
+    started: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    // A system clock set before 1970 reads as the epoch itself, so every
+    // attestation looks future-dated and is rejected rather than panicking
+    fn wall_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs())
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+// A clock stopped at one instant, for judging freshness as of a past time
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn wall_secs(&self) -> u64 {
+        self.0
+    }
+
+    fn monotonic(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+// Wall time derived from a wall-clock reading taken at a monotonic instant
+#[derive(Debug, Clone, Copy)]
+pub struct ClockAnchor {
+    wall_secs: u64,
+    monotonic: Duration,
+}
+
+impl ClockAnchor {
+    pub fn new(clock: &dyn Clock) -> Self {
+        Self {
+            wall_secs: clock.wall_secs(),
+            monotonic: clock.monotonic(),
+        }
+    }
+
+    pub fn now_secs(&self, clock: &dyn Clock) -> u64 {
+        let elapsed = clock.monotonic().saturating_sub(self.monotonic);
+        self.wall_secs + elapsed.as_secs()
+    }
+
+    // Re-anchor when the wall clock has been corrected forwards. Backwards
+    // steps are ignored; returns true if the anchor moved.
+    pub fn resync(&mut self, clock: &dyn Clock) -> bool {
+        let wall_secs = clock.wall_secs();
+        if wall_secs <= self.now_secs(clock) {
+            return false;
+        }
+        *self = Self {
+            wall_secs,
+            monotonic: clock.monotonic(),
+        };
+        true
+    }
+}
+
+// Manually driven clock for tests
+#[cfg(test)]
+pub struct ManualClock {
+    wall_secs: std::sync::atomic::AtomicU64,
+    monotonic_secs: std::sync::atomic::AtomicU64,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn new(wall_secs: u64) -> Self {
+        Self {
+            wall_secs: wall_secs.into(),
+            monotonic_secs: 0.into(),
+        }
+    }
+
+    // Let time pass on both clocks
+    pub fn advance(&self, secs: u64) {
+        use std::sync::atomic::Ordering;
+        self.wall_secs.fetch_add(secs, Ordering::SeqCst);
+        self.monotonic_secs.fetch_add(secs, Ordering::SeqCst);
+    }
+
+    // Step only the wall clock, as an NTP correction or manual change would
+    pub fn set_wall(&self, wall_secs: u64) {
+        self.wall_secs
+            .store(wall_secs, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn wall_secs(&self) -> u64 {
+        self.wall_secs.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    fn monotonic(&self) -> Duration {
+        Duration::from_secs(
+            self.monotonic_secs
+                .load(std::sync::atomic::Ordering::SeqCst),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_ignores_backwards_wall_step() {
+        let clock = ManualClock::new(1_000_000);
+        let mut anchor = ClockAnchor::new(&clock);
+
+        clock.advance(100);
+        assert_eq!(anchor.now_secs(&clock), 1_000_100);
+
+        clock.set_wall(900_000);
+        assert!(!anchor.resync(&clock));
+        assert_eq!(anchor.now_secs(&clock), 1_000_100);
+
+        // Forward corrections are adopted
+        clock.set_wall(1_005_000);
+        assert!(anchor.resync(&clock));
+        assert_eq!(anchor.now_secs(&clock), 1_005_000);
+    }
+}
@@ -0,0 +1,253 @@
+// MeshX - The Immutable Global Device Mesh
+// State compaction and integrity checks
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::proof_of_presence::{PopValidator, Shard};
+
+// Internal state that disagrees with itself. Compaction reports these rather
+// than repairing them, since the right fix depends on how they arose.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Inconsistency {
+    RegistryKeyMismatch(PublicKey), // Registry key differs from the node's own pubkey
+    ShardMismatch {
+        pubkey: PublicKey,
+        recorded: Shard,
+        located: Shard,
+    },
+    OrphanedDelegation(PublicKey), // Stake delegated to a validator no longer registered
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CompactionReport {
+    pub expired_slashes: usize, // Slash and demotion records whose cooldown has fully passed
+    pub expired_backoffs: usize, // Backoffs idle a full maximum interval past their retry
+    pub stale_latency_edges: usize, // Edges touching no registered or pending node
+    pub stale_node_records: usize, // Per-node samples and counters of departed nodes
+    pub expired_announcements: usize, // Seen-announcement hashes past their TTL
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl CompactionReport {
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+}
+
+impl PopValidator {
+    // Prune records that can no longer affect validation and check the
+    // registry's invariants. Meant to run periodically on long-lived nodes;
+    // nothing a later check depends on is removed.
+    pub fn compact(&mut self) -> CompactionReport {
+        let mut report = CompactionReport::default();
+
+        let (epoch, cooldown) = (self.current_epoch, self.slash_cooldown_epochs);
+        let before = self.slashed_at.len() + self.demoted_at.len();
+        self.slashed_at
+            .retain(|_, slash_epoch| epoch.saturating_sub(*slash_epoch) <= cooldown);
+        self.demoted_at
+            .retain(|_, demoted| epoch.saturating_sub(*demoted) <= cooldown);
+        report.expired_slashes = before - self.slashed_at.len() - self.demoted_at.len();
+
+        // Failing nodes are usually unregistered, so a backoff can only
+        // expire. Keeping it a full maximum interval past its retry epoch
+        // lets a node that comes back to fail again keep escalating.
+        let idle_epochs = self
+            .validation_backoff
+            .as_ref()
+            .map_or(0, |policy| policy.max_epochs);
+        let before = self.failure_backoff.len();
+        self.failure_backoff
+            .retain(|_, backoff| epoch < backoff.retry_epoch.saturating_add(idle_epochs));
+        report.expired_backoffs = before - self.failure_backoff.len();
+
+        let known = |validator: &Self, pubkey: &PublicKey| {
+            validator.nodes.contains_key(pubkey) || validator.pending_nodes.contains_key(pubkey)
+        };
+        let stale_edges: Vec<(PublicKey, PublicKey)> = self
+            .latency_matrix
+            .keys()
+            .filter(|(from, to)| !known(self, from) && !known(self, to))
+            .copied()
+            .collect();
+        for edge in &stale_edges {
+            self.latency_matrix.remove(edge);
+            self.latency_timestamps.remove(edge);
+            self.latency_conflicts.remove(edge);
+        }
+        report.stale_latency_edges = stale_edges.len();
+
+        // A departed node's attestation from this epoch still orders any
+        // attestation it rejoins with before the epoch ends
+        let attested_earlier = self
+            .attestation_timestamps
+            .iter()
+            .filter(|(_, (attested_epoch, _))| *attested_epoch != epoch)
+            .map(|(pubkey, _)| pubkey);
+        let departed: HashSet<PublicKey> = self
+            .clock_samples
+            .keys()
+            .chain(attested_earlier)
+            .chain(self.node_uptime.keys())
+            .chain(self.liveness_failures.keys())
+            .chain(self.registered_epochs.keys())
+            .filter(|pubkey| !known(self, pubkey))
+            .copied()
+            .collect();
+        for pubkey in &departed {
+            self.clock_samples.remove(pubkey);
+            if self
+                .attestation_timestamps
+                .get(pubkey)
+                .is_some_and(|(attested_epoch, _)| *attested_epoch != epoch)
+            {
+                self.attestation_timestamps.remove(pubkey);
+            }
+            self.node_uptime.remove(pubkey);
+            self.liveness_failures.remove(pubkey);
+            self.registered_epochs.remove(pubkey);
+        }
+        report.stale_node_records = departed.len();
+
+        let before = self.seen_announcements.len();
+        self.seen_announcements.evict_expired(self.now_secs());
+        report.expired_announcements = before - self.seen_announcements.len();
+
+        report.inconsistencies = self.check_invariants();
+        report
+    }
+
+    // Registry keys match their nodes, recorded shards match the shard each
+    // node's location falls in, and delegations point at registered nodes
+    pub fn check_invariants(&self) -> Vec<Inconsistency> {
+        let mut inconsistencies = Vec::new();
+        let mut nodes: Vec<_> = self.nodes.iter().collect();
+        nodes.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+        for (key, node) in nodes {
+            if node.pubkey != *key {
+                inconsistencies.push(Inconsistency::RegistryKeyMismatch(*key));
+            }
+            let located = self.shard_for_location(&node.geo_location);
+            if node.shard != located {
+                inconsistencies.push(Inconsistency::ShardMismatch {
+                    pubkey: *key,
+                    recorded: node.shard,
+                    located,
+                });
+            }
+        }
+
+        let mut orphaned: Vec<PublicKey> = self
+            .delegations
+            .iter()
+            .filter(|(validator, delegators)| {
+                !delegators.is_empty() && !self.nodes.contains_key(*validator)
+            })
+            .map(|(validator, _)| *validator)
+            .collect();
+        orphaned.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        inconsistencies.extend(orphaned.into_iter().map(Inconsistency::OrphanedDelegation));
+        inconsistencies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backoff::{BackoffPolicy, FailureBackoff};
+    use crate::proof_of_presence::tests::{test_network, test_pubkey};
+    use crate::proof_of_presence::ValidationError;
+    use crate::stake::StakeAmount;
+
+    #[test]
+    fn test_compaction_prunes_and_checks() {
+        let (mut validator, keys) = test_network(4);
+        validator.slash_cooldown_epochs = 3;
+        validator.current_epoch = 10;
+        validator.slash_node(&keys[0], StakeAmount::ZERO).unwrap();
+        validator.current_epoch = 20;
+        validator.slash_node(&keys[1], StakeAmount::ZERO).unwrap();
+        let departed = test_pubkey(30);
+        validator.node_uptime.insert(departed, 0.9);
+        validator
+            .latency_matrix
+            .insert((departed, test_pubkey(31)), 40);
+
+        let report = validator.compact();
+        assert_eq!(report.expired_slashes, 1);
+        assert!(!validator.slashed_at.contains_key(&keys[0]));
+        assert!(validator.in_slash_cooldown(&keys[1], 21));
+        assert_eq!(report.stale_latency_edges, 1);
+        assert_eq!(report.stale_node_records, 1);
+        assert!(report.is_consistent());
+        assert_eq!(validator.compact(), CompactionReport::default());
+
+        // A slashed node that deregisters still serves out its cooldown, and
+        // a failing node that never registered still backs off
+        validator.nodes.remove(&keys[1]);
+        let failing = test_pubkey(32);
+        validator.validation_backoff = Some(BackoffPolicy::default());
+        validator.failure_backoff.insert(
+            failing,
+            FailureBackoff {
+                consecutive_failures: 3,
+                retry_epoch: 24,
+            },
+        );
+        validator.compact();
+        assert!(validator.in_slash_cooldown(&keys[1], 22));
+        assert_eq!(validator.retry_epoch(&failing), Some(24));
+
+        validator.current_epoch = 24 + 64;
+        let report = validator.compact();
+        assert_eq!((report.expired_slashes, report.expired_backoffs), (1, 1));
+        assert_eq!(validator.retry_epoch(&failing), None);
+
+        // A node filed under the wrong shard is reported, not silently fixed
+        let recorded = validator.nodes[&keys[2]].shard;
+        let wrong = Shard::all()
+            .into_iter()
+            .find(|shard| *shard != recorded)
+            .unwrap();
+        validator.nodes.get_mut(&keys[2]).unwrap().shard = wrong;
+        assert_eq!(
+            validator.compact().inconsistencies,
+            vec![Inconsistency::ShardMismatch {
+                pubkey: keys[2],
+                recorded: wrong,
+                located: recorded,
+            }]
+        );
+        assert_eq!(validator.nodes[&keys[2]].shard, wrong);
+    }
+
+    #[test]
+    fn test_compaction_keeps_current_epoch_attestations() {
+        let (mut validator, keys) = test_network(4);
+        validator.current_epoch = 10;
+        let node = validator.nodes[&keys[0]].clone();
+        validator
+            .record_attestation_timestamp(&keys[0], &node.tee_attestation)
+            .unwrap();
+
+        // A ban that has run out by this epoch only removes the node
+        validator.ban(&keys[0], Some(10));
+        assert_eq!(validator.compact(), CompactionReport::default());
+
+        // Rejoining in the same epoch with an older quote is still a replay
+        let mut replayed = node.clone();
+        replayed.tee_attestation.timestamp -= 1;
+        assert_eq!(
+            validator.register_node(replayed),
+            Err(ValidationError::AttestationRegression)
+        );
+
+        // Once the epoch is over the record no longer orders anything
+        validator.current_epoch = 11;
+        assert_eq!(validator.compact().stale_node_records, 1);
+        assert!(!validator.attestation_timestamps.contains_key(&keys[0]));
+    }
+}
@@ -0,0 +1,175 @@
+// MeshX - The Immutable Global Device Mesh
+// Per-node contribution scoring
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{haversine_distance, MeshXNode, PopValidator};
+
+// Radius within which other nodes reduce a node's geographic value
+const NEIGHBOUR_RADIUS_M: f64 = 100_000.0;
+
+// Relative weights of the factors blended into a contribution score. Each
+// factor is normalized to 0..=1 first, so only the ratios matter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContributionWeights {
+    pub stake: f32,
+    pub reputation: f32,
+    pub resources: f32,
+    pub uptime: f32,
+    pub geography: f32,
+}
+
+impl Default for ContributionWeights {
+    fn default() -> Self {
+        Self {
+            stake: 0.3,
+            reputation: 0.2,
+            resources: 0.2,
+            uptime: 0.2,
+            geography: 0.1,
+        }
+    }
+}
+
+impl PopValidator {
+    // A node's overall contribution in 0..=1 for ranking. Stake and each
+    // resource count as x / (x + minimum), so meeting the minimum scores
+    // half and more always scores higher. Uptime is the recorded fraction of
+    // liveness checks passed (1.0 until recorded), and geographic value
+    // falls with every other node within 100 km. 0 for unknown nodes.
+    pub fn contribution_score(&self, pubkey: &PublicKey) -> f32 {
+        let Some(node) = self.nodes.get(pubkey) else {
+            return 0.0;
+        };
+        let weights = &self.contribution_weights;
+        let factors = [
+            (weights.stake, self.stake_factor(node)),
+            (weights.reputation, node.reputation_score.clamp(0.0, 1.0)),
+            (weights.resources, self.resource_factor(node)),
+            (
+                weights.uptime,
+                self.node_uptime
+                    .get(pubkey)
+                    .copied()
+                    .unwrap_or(1.0)
+                    .clamp(0.0, 1.0),
+            ),
+            (weights.geography, self.geography_factor(node)),
+        ];
+
+        let total_weight: f32 = factors.iter().map(|(weight, _)| weight.max(0.0)).sum();
+        if total_weight == 0.0 {
+            return 0.0;
+        }
+        let weighted: f32 = factors
+            .iter()
+            .map(|(weight, factor)| weight.max(0.0) * factor)
+            .sum();
+        weighted / total_weight
+    }
+
+    fn stake_factor(&self, node: &MeshXNode) -> f32 {
+        saturating_ratio(
+            self.effective_stake(&node.pubkey).base_units() as f64,
+            self.policy.minimum_stake_for(&node.shard).base_units() as f64,
+        )
+    }
+
+    fn resource_factor(&self, node: &MeshXNode) -> f32 {
+        let resources = &node.resources;
+        let policy = &self.policy;
+        let ratios = [
+            saturating_ratio(resources.cpu_cores as f64, policy.min_cpu_cores as f64),
+            saturating_ratio(resources.ram_gb as f64, policy.min_ram_gb as f64),
+            saturating_ratio(resources.storage_gb as f64, policy.min_storage_gb as f64),
+            saturating_ratio(
+                resources.bandwidth_mbps as f64,
+                policy.min_bandwidth_mbps as f64,
+            ),
+        ];
+        ratios.iter().sum::<f32>() / ratios.len() as f32
+    }
+
+    fn geography_factor(&self, node: &MeshXNode) -> f32 {
+        let neighbours = self
+            .nodes
+            .values()
+            .filter(|other| other.pubkey != node.pubkey)
+            .filter(|other| {
+                haversine_distance(&other.geo_location, &node.geo_location) <= NEIGHBOUR_RADIUS_M
+            })
+            .count();
+        1.0 / (1.0 + neighbours as f32)
+    }
+}
+
+// x / (x + scale), treating a zero scale as already saturated
+fn saturating_ratio(x: f64, scale: f64) -> f32 {
+    if scale <= 0.0 {
+        return 1.0;
+    }
+    (x / (x + scale)) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::{test_network, test_node};
+
+    #[test]
+    fn test_contribution_score_is_monotonic() {
+        let (mut validator, keys) = test_network(2);
+        let key = keys[0];
+        validator.nodes.get_mut(&key).unwrap().reputation_score = 0.5;
+        validator.node_uptime.insert(key, 0.5);
+        let mut score = validator.contribution_score(&key);
+        let mut assert_rises = |validator: &PopValidator| {
+            let next = validator.contribution_score(&key);
+            assert!(next > score, "{} <= {}", next, score);
+            score = next;
+        };
+
+        let node = validator.nodes.get_mut(&key).unwrap();
+        node.stake_amount = node.stake_amount.saturating_add(node.stake_amount);
+        assert_rises(&validator);
+        validator.nodes.get_mut(&key).unwrap().reputation_score = 0.8;
+        assert_rises(&validator);
+        validator.nodes.get_mut(&key).unwrap().resources.ram_gb *= 2;
+        assert_rises(&validator);
+        validator.node_uptime.insert(key, 0.9);
+        assert_rises(&validator);
+
+        // The other test node sits 11 km away; moving it out of range raises
+        // the geographic value
+        let other = keys[1];
+        validator
+            .nodes
+            .get_mut(&other)
+            .unwrap()
+            .geo_location
+            .latitude = 40.0;
+        assert_rises(&validator);
+
+        // Another close neighbour lowers it again
+        let before = validator.contribution_score(&key);
+        let neighbour = test_node(9, 50.1, 10.0, 100_000);
+        validator.nodes.insert(neighbour.pubkey, neighbour);
+        assert!(validator.contribution_score(&key) < before);
+    }
+
+    #[test]
+    fn test_contribution_weights_configurable() {
+        let (mut validator, keys) = test_network(2);
+        validator.contribution_weights = ContributionWeights {
+            stake: 0.0,
+            reputation: 1.0,
+            resources: 0.0,
+            uptime: 0.0,
+            geography: 0.0,
+        };
+        validator.nodes.get_mut(&keys[0]).unwrap().reputation_score = 0.4;
+        assert_eq!(validator.contribution_score(&keys[0]), 0.4);
+    }
+}
@@ -0,0 +1,580 @@
+// MeshX - The Immutable Global Device Mesh
+// Intel SGX DCAP quote parsing and verification
+// Copyright (c) 2025 MeshX Foundation
+
+use crate::proof_of_presence::ValidationError;
+
+#[cfg(feature = "sgx-dcap")]
+pub use verify::DcapVerifier;
+
+// The Intel SGX Root CA certificate, as published by Intel
+#[cfg(feature = "sgx-dcap")]
+pub const INTEL_SGX_ROOT_CA_PEM: &str = include_str!("certs/intel_sgx_root_ca.pem");
+
+const HEADER_LEN: usize = 48;
+const REPORT_BODY_LEN: usize = 384;
+const MR_ENCLAVE_OFFSET: usize = 64; // Within a report body
+#[cfg(feature = "sgx-dcap")]
+const REPORT_DATA_OFFSET: usize = 320;
+const ECDSA_P256_KEY_TYPE: u16 = 2;
+const PCK_CERT_CHAIN: u16 = 5; // Certification data holding the PEM chain
+const QE_REPORT_CERTIFICATION: u16 = 6; // Version 4 wrapper around the QE report
+
+// The parts of an ECDSA-P256 DCAP quote (version 3 or 4) that verification
+// needs, borrowed from the raw quote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SgxQuote<'a> {
+    pub version: u16,
+    pub signed: &'a [u8], // Header and ISV report body, as signed by the attestation key
+    pub report_body: &'a [u8],
+    pub isv_signature: &'a [u8],   // Raw r || s
+    pub attestation_key: &'a [u8], // Raw x || y
+    pub qe_report: &'a [u8],
+    pub qe_report_signature: &'a [u8], // Raw r || s, by the PCK leaf key
+    pub qe_auth_data: &'a [u8],
+    pub pck_cert_chain: &'a [u8], // PEM, leaf first
+}
+
+impl<'a> SgxQuote<'a> {
+    // Split a quote into its parts, rejecting anything that isn't a
+    // complete ECDSA-P256 SGX quote with nothing trailing
+    pub fn parse(quote: &'a [u8]) -> Result<Self, ValidationError> {
+        let mut reader = QuoteReader::new(quote);
+        let header = reader.take(HEADER_LEN)?;
+        let version = u16::from_le_bytes([header[0], header[1]]);
+        let key_type = u16::from_le_bytes([header[2], header[3]]);
+        let tee_type = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        if !matches!(version, 3 | 4) || key_type != ECDSA_P256_KEY_TYPE || tee_type != 0 {
+            return Err(ValidationError::InvalidQuote);
+        }
+        let report_body = reader.take(REPORT_BODY_LEN)?;
+        let signature_len = reader.u32()? as usize;
+        let signature_data = reader.take(signature_len)?;
+        reader.finish()?;
+
+        let mut reader = QuoteReader::new(signature_data);
+        let isv_signature = reader.take(64)?;
+        let attestation_key = reader.take(64)?;
+        let qe = if version == 3 {
+            let qe = QeCertification::read(&mut reader)?;
+            reader.finish()?;
+            qe
+        } else {
+            let (kind, data) = reader.certification_data()?;
+            reader.finish()?;
+            if kind != QE_REPORT_CERTIFICATION {
+                return Err(ValidationError::InvalidQuote);
+            }
+            let mut reader = QuoteReader::new(data);
+            let qe = QeCertification::read(&mut reader)?;
+            reader.finish()?;
+            qe
+        };
+
+        Ok(Self {
+            version,
+            signed: &quote[..HEADER_LEN + REPORT_BODY_LEN],
+            report_body,
+            isv_signature,
+            attestation_key,
+            qe_report: qe.report,
+            qe_report_signature: qe.report_signature,
+            qe_auth_data: qe.auth_data,
+            pck_cert_chain: qe.pck_cert_chain,
+        })
+    }
+
+    pub fn mr_enclave(&self) -> &'a [u8] {
+        &self.report_body[MR_ENCLAVE_OFFSET..MR_ENCLAVE_OFFSET + 32]
+    }
+
+    // First half of the QE report's report data, which must hash the
+    // attestation key and QE authentication data
+    #[cfg(feature = "sgx-dcap")]
+    pub fn qe_report_data_hash(&self) -> &'a [u8] {
+        &self.qe_report[REPORT_DATA_OFFSET..REPORT_DATA_OFFSET + 32]
+    }
+
+    // The enclave that produced the quote must be the MeshX release
+    pub fn check_mr_enclave(&self, expected: &[u8; 32]) -> Result<(), ValidationError> {
+        if self.mr_enclave() != expected {
+            return Err(ValidationError::InvalidEnclaveCode);
+        }
+        Ok(())
+    }
+}
+
+// The quoting enclave's report and the PCK chain that certifies it
+struct QeCertification<'a> {
+    report: &'a [u8],
+    report_signature: &'a [u8],
+    auth_data: &'a [u8],
+    pck_cert_chain: &'a [u8],
+}
+
+impl<'a> QeCertification<'a> {
+    fn read(reader: &mut QuoteReader<'a>) -> Result<Self, ValidationError> {
+        let report = reader.take(REPORT_BODY_LEN)?;
+        let report_signature = reader.take(64)?;
+        let auth_len = reader.u16()? as usize;
+        let auth_data = reader.take(auth_len)?;
+        let (kind, pck_cert_chain) = reader.certification_data()?;
+        if kind != PCK_CERT_CHAIN {
+            return Err(ValidationError::UntrustedPckChain);
+        }
+        Ok(Self {
+            report,
+            report_signature,
+            auth_data,
+            pck_cert_chain,
+        })
+    }
+}
+
+struct QuoteReader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> QuoteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, at: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ValidationError> {
+        let end = self
+            .at
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(ValidationError::InvalidQuote)?;
+        let taken = &self.bytes[self.at..end];
+        self.at = end;
+        Ok(taken)
+    }
+
+    fn u16(&mut self) -> Result<u16, ValidationError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, ValidationError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    // A certification data entry: type, length, then the data
+    fn certification_data(&mut self) -> Result<(u16, &'a [u8]), ValidationError> {
+        let kind = self.u16()?;
+        let len = self.u32()? as usize;
+        Ok((kind, self.take(len)?))
+    }
+
+    fn finish(&self) -> Result<(), ValidationError> {
+        if self.at != self.bytes.len() {
+            return Err(ValidationError::InvalidQuote);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sgx-dcap")]
+mod verify {
+    use p256::ecdsa::signature::Verifier;
+    use p256::ecdsa::{Signature, VerifyingKey};
+    use sha2::{Digest, Sha256};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use x509_cert::der::{DecodePem, Encode};
+    use x509_cert::ext::pkix::BasicConstraints;
+    use x509_cert::Certificate;
+
+    use super::SgxQuote;
+    use crate::clock::{Clock, SystemClock};
+    use crate::proof_of_presence::ValidationError;
+    use crate::tee::TeeVerifier;
+
+    // Full DCAP verification: the PCK chain must end in the pinned Intel SGX
+    // Root CA with every certificate in its validity period and every issuer
+    // a CA, the PCK leaf must sign the QE report, the QE report must commit
+    // to the attestation key, and the attestation key must sign the ISV
+    // report. TCB status and revocation lists aren't checked.
+    pub struct DcapVerifier {
+        trusted_root: Vec<u8>, // DER
+        clock: Arc<dyn Clock>, // Validity periods are checked against its wall time
+    }
+
+    impl DcapVerifier {
+        // Pin the Intel SGX Root CA certificate, as published by Intel
+        pub fn from_root_pem(pem: &str) -> Result<Self, ValidationError> {
+            let trusted_root = Certificate::from_pem(pem)
+                .and_then(|root| root.to_der())
+                .map_err(|_| ValidationError::UntrustedPckChain)?;
+            Ok(Self {
+                trusted_root,
+                clock: Arc::new(SystemClock::new()),
+            })
+        }
+
+        // Check validity periods against another time source
+        pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+            self.clock = clock;
+            self
+        }
+
+        // The PCK leaf key, once every certificate in the chain is signed by
+        // the next and the chain ends in the pinned root
+        fn verify_pck_chain(&self, pem_chain: &[u8]) -> Result<VerifyingKey, ValidationError> {
+            // Quotes NUL-terminate the PEM text
+            let end = pem_chain
+                .iter()
+                .rposition(|byte| *byte != 0)
+                .map_or(0, |last| last + 1);
+            let chain = Certificate::load_pem_chain(&pem_chain[..end])
+                .map_err(|_| ValidationError::UntrustedPckChain)?;
+            let root = chain.last().ok_or(ValidationError::UntrustedPckChain)?;
+            if root.to_der().ok().as_deref() != Some(self.trusted_root.as_slice()) {
+                return Err(ValidationError::UntrustedPckChain);
+            }
+            let now = self.clock.wall_secs();
+            let issuers = chain.iter().skip(1).chain(std::iter::once(root));
+            for (cert, issuer) in chain.iter().zip(issuers) {
+                check_validity(cert, now)?;
+                check_is_ca(issuer)?;
+                check_issued_by(cert, issuer)?;
+            }
+            certificate_key(&chain[0])
+        }
+    }
+
+    impl TeeVerifier for DcapVerifier {
+        fn verify(
+            &self,
+            quote: &[u8],
+            expected_enclave_hash: &[u8; 32],
+        ) -> Result<(), ValidationError> {
+            let quote = SgxQuote::parse(quote)?;
+            quote.check_mr_enclave(expected_enclave_hash)?;
+            let pck_key = self.verify_pck_chain(quote.pck_cert_chain)?;
+            verify_signatures(&quote, &pck_key)
+        }
+
+        // Quotes from one platform carry the same PCK chain, so each distinct
+        // chain is checked once per batch
+        fn verify_batch(
+            &self,
+            quotes: &[&[u8]],
+            expected_enclave_hash: &[u8; 32],
+        ) -> Vec<Result<(), ValidationError>> {
+            let mut pck_keys: HashMap<&[u8], Result<VerifyingKey, ValidationError>> =
+                HashMap::new();
+            quotes
+                .iter()
+                .map(|quote| {
+                    let quote = SgxQuote::parse(quote)?;
+                    quote.check_mr_enclave(expected_enclave_hash)?;
+                    let pck_key = pck_keys
+                        .entry(quote.pck_cert_chain)
+                        .or_insert_with(|| self.verify_pck_chain(quote.pck_cert_chain))
+                        .clone()?;
+                    verify_signatures(&quote, &pck_key)
+                })
+                .collect()
+        }
+    }
+
+    // The QE report's signature by the PCK key, its binding of the
+    // attestation key, and the ISV report's signature by that key
+    fn verify_signatures(quote: &SgxQuote, pck_key: &VerifyingKey) -> Result<(), ValidationError> {
+        let qe_signature = Signature::from_slice(quote.qe_report_signature)
+            .map_err(|_| ValidationError::QuoteSignatureInvalid)?;
+        pck_key
+            .verify(quote.qe_report, &qe_signature)
+            .map_err(|_| ValidationError::QuoteSignatureInvalid)?;
+
+        let binding = Sha256::new()
+            .chain_update(quote.attestation_key)
+            .chain_update(quote.qe_auth_data)
+            .finalize();
+        if quote.qe_report_data_hash() != &binding[..] {
+            return Err(ValidationError::QuoteSignatureInvalid);
+        }
+
+        let attestation_key =
+            VerifyingKey::from_sec1_bytes(&[&[0x04][..], quote.attestation_key].concat())
+                .map_err(|_| ValidationError::QuoteSignatureInvalid)?;
+        let isv_signature = Signature::from_slice(quote.isv_signature)
+            .map_err(|_| ValidationError::QuoteSignatureInvalid)?;
+        attestation_key
+            .verify(quote.signed, &isv_signature)
+            .map_err(|_| ValidationError::QuoteSignatureInvalid)
+    }
+
+    fn check_validity(cert: &Certificate, now: u64) -> Result<(), ValidationError> {
+        let validity = &cert.tbs_certificate.validity;
+        let not_before = validity.not_before.to_unix_duration().as_secs();
+        let not_after = validity.not_after.to_unix_duration().as_secs();
+        if now < not_before || now > not_after {
+            return Err(ValidationError::UntrustedPckChain);
+        }
+        Ok(())
+    }
+
+    fn check_is_ca(cert: &Certificate) -> Result<(), ValidationError> {
+        match cert.tbs_certificate.get::<BasicConstraints>() {
+            Ok(Some((_, constraints))) if constraints.ca => Ok(()),
+            _ => Err(ValidationError::UntrustedPckChain),
+        }
+    }
+
+    fn check_issued_by(cert: &Certificate, issuer: &Certificate) -> Result<(), ValidationError> {
+        if cert.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+            return Err(ValidationError::UntrustedPckChain);
+        }
+        let tbs = cert
+            .tbs_certificate
+            .to_der()
+            .map_err(|_| ValidationError::UntrustedPckChain)?;
+        let signature = Signature::from_der(cert.signature.raw_bytes())
+            .map_err(|_| ValidationError::UntrustedPckChain)?;
+        certificate_key(issuer)?
+            .verify(&tbs, &signature)
+            .map_err(|_| ValidationError::UntrustedPckChain)
+    }
+
+    fn certificate_key(cert: &Certificate) -> Result<VerifyingKey, ValidationError> {
+        VerifyingKey::from_sec1_bytes(
+            cert.tbs_certificate
+                .subject_public_key_info
+                .subject_public_key
+                .raw_bytes(),
+        )
+        .map_err(|_| ValidationError::UntrustedPckChain)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const PCK_CHAIN: &[u8] = b"-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n\0";
+
+    // Signature data shared by both quote versions after the attestation key
+    fn qe_certification(pck_chain_type: u16) -> Vec<u8> {
+        let mut data = vec![0x51; REPORT_BODY_LEN]; // QE report
+        data.extend_from_slice(&[0x52; 64]); // QE report signature
+        data.extend_from_slice(&4u16.to_le_bytes());
+        data.extend_from_slice(&[0x53; 4]); // QE auth data
+        data.extend_from_slice(&pck_chain_type.to_le_bytes());
+        data.extend_from_slice(&(PCK_CHAIN.len() as u32).to_le_bytes());
+        data.extend_from_slice(PCK_CHAIN);
+        data
+    }
+
+    // A structurally complete quote; the signatures are filler
+    pub(crate) fn sample_quote(version: u16, mr_enclave: [u8; 32], pck_chain_type: u16) -> Vec<u8> {
+        let mut quote = Vec::new();
+        quote.extend_from_slice(&version.to_le_bytes());
+        quote.extend_from_slice(&ECDSA_P256_KEY_TYPE.to_le_bytes());
+        quote.extend_from_slice(&0u32.to_le_bytes());
+        quote.resize(HEADER_LEN, 0x11);
+        let mut report_body = vec![0x22; REPORT_BODY_LEN];
+        report_body[MR_ENCLAVE_OFFSET..MR_ENCLAVE_OFFSET + 32].copy_from_slice(&mr_enclave);
+        quote.extend_from_slice(&report_body);
+
+        let mut signature_data = vec![0x33; 64]; // ISV report signature
+        signature_data.extend_from_slice(&[0x44; 64]); // Attestation key
+        let qe = qe_certification(pck_chain_type);
+        if version == 3 {
+            signature_data.extend_from_slice(&qe);
+        } else {
+            signature_data.extend_from_slice(&QE_REPORT_CERTIFICATION.to_le_bytes());
+            signature_data.extend_from_slice(&(qe.len() as u32).to_le_bytes());
+            signature_data.extend_from_slice(&qe);
+        }
+        quote.extend_from_slice(&(signature_data.len() as u32).to_le_bytes());
+        quote.extend_from_slice(&signature_data);
+        quote
+    }
+
+    // A well-formed quote for the test enclave hash; distinct variants
+    // differ only in header filler
+    pub(crate) fn test_quote(variant: u8) -> Vec<u8> {
+        let mut quote = sample_quote(3, [0x42; 32], PCK_CERT_CHAIN);
+        quote[HEADER_LEN - 1] = variant;
+        quote
+    }
+
+    #[test]
+    fn test_dcap_quote_parses_both_versions() {
+        for version in [3, 4] {
+            let raw = sample_quote(version, [0x42; 32], PCK_CERT_CHAIN);
+            let quote = SgxQuote::parse(&raw).unwrap();
+            assert_eq!(quote.version, version);
+            assert_eq!(quote.signed.len(), HEADER_LEN + REPORT_BODY_LEN);
+            assert_eq!(quote.mr_enclave(), &[0x42; 32]);
+            assert_eq!(quote.isv_signature, &[0x33; 64]);
+            assert_eq!(quote.attestation_key, &[0x44; 64]);
+            assert_eq!(quote.qe_report, &[0x51; REPORT_BODY_LEN][..]);
+            assert_eq!(quote.qe_auth_data, &[0x53; 4]);
+            assert_eq!(quote.pck_cert_chain, PCK_CHAIN);
+            assert_eq!(quote.check_mr_enclave(&[0x42; 32]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_forged_dcap_quotes_rejected() {
+        assert_eq!(SgxQuote::parse(&[1]), Err(ValidationError::InvalidQuote));
+
+        let raw = sample_quote(3, [0x42; 32], PCK_CERT_CHAIN);
+        assert_eq!(
+            SgxQuote::parse(&raw[..raw.len() - 1]),
+            Err(ValidationError::InvalidQuote)
+        );
+        let trailing = [raw.clone(), vec![0]].concat();
+        assert_eq!(
+            SgxQuote::parse(&trailing),
+            Err(ValidationError::InvalidQuote)
+        );
+
+        // ECDSA-384 keys and TDX quotes aren't SGX P-256 quotes
+        let mut p384 = raw.clone();
+        p384[2] = 3;
+        assert_eq!(SgxQuote::parse(&p384), Err(ValidationError::InvalidQuote));
+        let mut tdx = raw.clone();
+        tdx[4] = 0x81;
+        assert_eq!(SgxQuote::parse(&tdx), Err(ValidationError::InvalidQuote));
+
+        // A quote for some other enclave
+        let other = sample_quote(4, [0x43; 32], PCK_CERT_CHAIN);
+        assert_eq!(
+            SgxQuote::parse(&other)
+                .unwrap()
+                .check_mr_enclave(&[0x42; 32]),
+            Err(ValidationError::InvalidEnclaveCode)
+        );
+
+        // Certified by something other than a PCK certificate chain
+        let uncertified = sample_quote(3, [0x42; 32], 1);
+        assert_eq!(
+            SgxQuote::parse(&uncertified),
+            Err(ValidationError::UntrustedPckChain)
+        );
+    }
+    // A quote captured from real SGX hardware, from the dcap-qvl sample set
+    // (github.com/Phala-Network/dcap-qvl, MIT licensed)
+    #[cfg(feature = "sgx-dcap")]
+    static CAPTURED_QUOTE: &[u8] = include_bytes!("testdata/sgx_quote.bin");
+
+    #[cfg(feature = "sgx-dcap")]
+    const CAPTURED_MR_ENCLAVE: [u8; 32] = [
+        0x33, 0xd8, 0x73, 0x6d, 0xb7, 0x56, 0xed, 0x49, 0x97, 0xe0, 0x4b, 0xa3, 0x58, 0xd2, 0x78,
+        0x33, 0x18, 0x8f, 0x19, 0x32, 0xff, 0x7b, 0x1d, 0x15, 0x69, 0x04, 0xd3, 0xf5, 0x60, 0x45,
+        0x2f, 0xbb,
+    ];
+
+    // Inside the validity period of every certificate in the captured chain
+    #[cfg(feature = "sgx-dcap")]
+    const CAPTURED_CHAIN_VALID_AT: u64 = 1_735_689_600; // 2025-01-01
+
+    #[cfg(feature = "sgx-dcap")]
+    fn captured_verifier(now: u64) -> DcapVerifier {
+        DcapVerifier::from_root_pem(INTEL_SGX_ROOT_CA_PEM)
+            .unwrap()
+            .with_clock(std::sync::Arc::new(crate::clock::FixedClock(now)))
+    }
+
+    #[cfg(feature = "sgx-dcap")]
+    #[test]
+    fn test_captured_quote_verifies() {
+        use crate::tee::TeeVerifier;
+
+        let verifier = captured_verifier(CAPTURED_CHAIN_VALID_AT);
+        assert_eq!(
+            verifier.verify(CAPTURED_QUOTE, &CAPTURED_MR_ENCLAVE),
+            Ok(())
+        );
+        assert_eq!(
+            verifier.verify(CAPTURED_QUOTE, &[0x42; 32]),
+            Err(ValidationError::InvalidEnclaveCode)
+        );
+        assert_eq!(
+            verifier.verify_batch(&[CAPTURED_QUOTE, CAPTURED_QUOTE], &CAPTURED_MR_ENCLAVE),
+            vec![Ok(()), Ok(())]
+        );
+    }
+
+    #[cfg(feature = "sgx-dcap")]
+    #[test]
+    fn test_tampered_quote_signatures_rejected() {
+        use crate::tee::TeeVerifier;
+
+        let verifier = captured_verifier(CAPTURED_CHAIN_VALID_AT);
+        let parsed = SgxQuote::parse(CAPTURED_QUOTE).unwrap();
+        let offset_of = |field: &[u8]| field.as_ptr() as usize - CAPTURED_QUOTE.as_ptr() as usize;
+
+        // The ISV report data, the ISV signature, the QE report and the QE
+        // report signature are each covered by a signature
+        let report_data = HEADER_LEN + REPORT_BODY_LEN - 1;
+        for offset in [
+            report_data,
+            offset_of(parsed.isv_signature),
+            offset_of(parsed.qe_report),
+            offset_of(parsed.qe_report_signature),
+        ] {
+            let mut tampered = CAPTURED_QUOTE.to_vec();
+            tampered[offset] ^= 1;
+            assert_eq!(
+                verifier.verify(&tampered, &CAPTURED_MR_ENCLAVE),
+                Err(ValidationError::QuoteSignatureInvalid)
+            );
+        }
+    }
+
+    #[cfg(feature = "sgx-dcap")]
+    #[test]
+    fn test_tampered_pck_chain_rejected() {
+        use crate::tee::TeeVerifier;
+
+        let verifier = captured_verifier(CAPTURED_CHAIN_VALID_AT);
+        let parsed = SgxQuote::parse(CAPTURED_QUOTE).unwrap();
+        let chain_start =
+            parsed.pck_cert_chain.as_ptr() as usize - CAPTURED_QUOTE.as_ptr() as usize;
+
+        // One base64 character inside the PCK leaf certificate
+        let mut tampered = CAPTURED_QUOTE.to_vec();
+        let leaf_byte = chain_start + 100;
+        tampered[leaf_byte] = if tampered[leaf_byte] == b'A' {
+            b'B'
+        } else {
+            b'A'
+        };
+        assert_eq!(
+            verifier.verify(&tampered, &CAPTURED_MR_ENCLAVE),
+            Err(ValidationError::UntrustedPckChain)
+        );
+
+        // A chain that doesn't end in the pinned root
+        let chain = std::str::from_utf8(parsed.pck_cert_chain).unwrap();
+        let end_marker = "-----END CERTIFICATE-----\n";
+        let processor_ca = chain.split_inclusive(end_marker).nth(1).unwrap();
+        let other_root = DcapVerifier::from_root_pem(processor_ca).unwrap();
+        assert_eq!(
+            other_root.verify(CAPTURED_QUOTE, &CAPTURED_MR_ENCLAVE),
+            Err(ValidationError::UntrustedPckChain)
+        );
+    }
+
+    #[cfg(feature = "sgx-dcap")]
+    #[test]
+    fn test_pck_chain_outside_validity_rejected() {
+        use crate::tee::TeeVerifier;
+
+        // Before the PCK leaf was issued, and after it expired
+        for now in [1_672_531_200, 1_924_992_000] {
+            assert_eq!(
+                captured_verifier(now).verify(CAPTURED_QUOTE, &CAPTURED_MR_ENCLAVE),
+                Err(ValidationError::UntrustedPckChain)
+            );
+        }
+    }
+}
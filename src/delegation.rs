@@ -0,0 +1,331 @@
+// MeshX - The Immutable Global Device Mesh
+// Stake delegation
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::{Keypair, PublicKey, Signature, Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::HashSet;
+
+use crate::proof_of_presence::{PopValidator, ValidationError};
+use crate::stake::StakeAmount;
+
+// Limits keeping any one delegator from controlling many validators. Unset
+// caps don't apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DelegationCaps {
+    pub max_validator_share: Option<f64>, // Of a validator's effective stake from one delegator
+    pub max_delegator_total: Option<StakeAmount>, // Across all validators
+    pub allow_cycles: bool, // Permit delegation loops, which count the same stake twice
+}
+
+// A delegator's instruction to move `amount` of its balance behind a
+// validator. `nonce` counts the delegator's earlier delegations, so each
+// signed instruction applies once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Delegation {
+    pub delegator: PublicKey,
+    pub validator: PublicKey,
+    pub amount: StakeAmount,
+    pub nonce: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedDelegation {
+    pub delegation: Delegation,
+    pub signature: Signature,
+}
+
+impl SignedDelegation {
+    pub fn sign(delegation: Delegation, keypair: &Keypair) -> Self {
+        let signature = keypair.sign(&Self::signing_payload(&delegation));
+        Self {
+            delegation,
+            signature,
+        }
+    }
+
+    // Only the delegator may commit its own balance
+    pub fn verify(&self) -> Result<(), ValidationError> {
+        self.delegation
+            .delegator
+            .verify(&Self::signing_payload(&self.delegation), &self.signature)
+            .map_err(|_| ValidationError::InvalidSignature)
+    }
+
+    fn signing_payload(delegation: &Delegation) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_DELEGATION");
+        hasher.update(serde_json::to_vec(delegation).expect("delegation serializes"));
+        hasher.finalize().to_vec()
+    }
+}
+
+impl PopValidator {
+    // Apply a signed delegation to a registered validator, debiting the
+    // delegator's balance and adding to any earlier delegation from it. Only
+    // stake backed this way counts towards the validator's effective stake.
+    // Rejected without change if it would breach either delegation cap.
+    pub fn delegate(&mut self, signed: &SignedDelegation) -> Result<(), ValidationError> {
+        signed.verify()?;
+        let Delegation {
+            delegator,
+            validator,
+            amount,
+            nonce,
+        } = &signed.delegation;
+        if *nonce != self.delegation_nonces.get(delegator).copied().unwrap_or(0) {
+            return Err(ValidationError::DelegationReplayed);
+        }
+        if !self.nodes.contains_key(validator) {
+            return Err(ValidationError::UnknownNode);
+        }
+        let caps = &self.policy.delegation_caps;
+        if !caps.allow_cycles && self.delegation_reaches(validator, delegator) {
+            return Err(ValidationError::CircularDelegation);
+        }
+
+        let existing = self.delegated_amount(delegator, validator);
+        let delegated = existing
+            .checked_add(*amount)
+            .ok_or(ValidationError::DelegationCapExceeded)?;
+        if let Some(max_share) = caps.max_validator_share {
+            let effective = self.effective_stake(validator).saturating_add(*amount);
+            if delegated.base_units() as f64 > max_share * effective.base_units() as f64 {
+                return Err(ValidationError::DelegationCapExceeded);
+            }
+        }
+        if let Some(max_total) = caps.max_delegator_total {
+            if self.delegator_total(delegator).saturating_add(*amount) > max_total {
+                return Err(ValidationError::DelegationCapExceeded);
+            }
+        }
+        let balance = self
+            .reward_balances
+            .get(delegator)
+            .copied()
+            .unwrap_or_default();
+        let remaining = balance
+            .checked_sub(*amount)
+            .ok_or(ValidationError::InsufficientBalance)?;
+
+        self.reward_balances.insert(*delegator, remaining);
+        *self.delegation_nonces.entry(*delegator).or_default() += 1;
+        self.delegations
+            .entry(*validator)
+            .or_default()
+            .insert(*delegator, delegated);
+        Ok(())
+    }
+
+    pub fn delegated_amount(&self, delegator: &PublicKey, validator: &PublicKey) -> StakeAmount {
+        self.delegations
+            .get(validator)
+            .and_then(|delegators| delegators.get(delegator))
+            .copied()
+            .unwrap_or(StakeAmount::ZERO)
+    }
+
+    // Stake delegated to a validator by everyone
+    pub fn delegated_to(&self, validator: &PublicKey) -> StakeAmount {
+        self.delegations
+            .get(validator)
+            .into_iter()
+            .flat_map(|delegators| delegators.values())
+            .fold(StakeAmount::ZERO, |total, amount| {
+                total.saturating_add(*amount)
+            })
+    }
+
+    // Whether stake delegated by `from` flows, directly or through further
+    // delegations, to `to`. A node trivially reaches itself, so delegating
+    // to oneself counts as a cycle.
+    fn delegation_reaches(&self, from: &PublicKey, to: &PublicKey) -> bool {
+        let mut visited: HashSet<PublicKey> = HashSet::new();
+        let mut frontier = vec![*from];
+        while let Some(delegator) = frontier.pop() {
+            if delegator == *to {
+                return true;
+            }
+            if !visited.insert(delegator) {
+                continue;
+            }
+            frontier.extend(
+                self.delegations
+                    .iter()
+                    .filter(|(_, delegators)| delegators.contains_key(&delegator))
+                    .map(|(validator, _)| *validator),
+            );
+        }
+        false
+    }
+
+    // Stake a delegator has placed across all validators
+    pub fn delegator_total(&self, delegator: &PublicKey) -> StakeAmount {
+        self.delegations
+            .values()
+            .filter_map(|delegators| delegators.get(delegator))
+            .fold(StakeAmount::ZERO, |total, amount| {
+                total.saturating_add(*amount)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presence::tests::test_keypair;
+    use crate::proof_of_presence::tests::{test_network, test_pubkey};
+
+    // Sign and apply `seed`'s next delegation
+    fn delegate(
+        validator: &mut PopValidator,
+        seed: u8,
+        to: &PublicKey,
+        amount: StakeAmount,
+    ) -> Result<(), ValidationError> {
+        let keypair = test_keypair(seed);
+        let nonce = validator
+            .delegation_nonces
+            .get(&keypair.public)
+            .copied()
+            .unwrap_or(0);
+        let delegation = Delegation {
+            delegator: keypair.public,
+            validator: *to,
+            amount,
+            nonce,
+        };
+        validator.delegate(&SignedDelegation::sign(delegation, &keypair))
+    }
+
+    fn fund(validator: &mut PopValidator, seed: u8, amount: StakeAmount) {
+        validator.reward_balances.insert(test_pubkey(seed), amount);
+    }
+
+    #[test]
+    fn test_delegation_caps() {
+        let (mut validator, keys) = test_network(3);
+        let (whale, small) = (20, 21);
+        let meshx = StakeAmount::from_meshx;
+        fund(&mut validator, whale, meshx(2_000_000));
+        fund(&mut validator, small, meshx(10_000));
+        validator.policy.delegation_caps = DelegationCaps {
+            max_validator_share: Some(0.25),
+            max_delegator_total: Some(meshx(60_000)),
+            ..DelegationCaps::default()
+        };
+
+        // 30K of 130K effective is within the 25% share cap
+        delegate(&mut validator, whale, &keys[0], meshx(30_000)).unwrap();
+        assert_eq!(validator.effective_stake(&keys[0]), meshx(130_000));
+        // 40K of 140K isn't
+        assert_eq!(
+            delegate(&mut validator, whale, &keys[0], meshx(10_000)),
+            Err(ValidationError::DelegationCapExceeded)
+        );
+        delegate(&mut validator, small, &keys[0], meshx(10_000)).unwrap();
+
+        // The whale's 60K total cap spans validators
+        delegate(&mut validator, whale, &keys[1], meshx(30_000)).unwrap();
+        assert_eq!(
+            delegate(&mut validator, whale, &keys[2], meshx(1)),
+            Err(ValidationError::DelegationCapExceeded)
+        );
+        let whale = test_pubkey(whale);
+        assert_eq!(validator.delegator_total(&whale), meshx(60_000));
+        assert_eq!(validator.delegated_to(&keys[0]), meshx(40_000));
+
+        validator.policy.delegation_caps = DelegationCaps::default();
+        delegate(&mut validator, 20, &keys[2], meshx(1_000_000)).unwrap();
+        assert_eq!(
+            delegate(&mut validator, 20, &test_pubkey(9), meshx(1)),
+            Err(ValidationError::UnknownNode)
+        );
+    }
+
+    #[test]
+    fn test_delegation_must_be_signed_and_backed() {
+        let (mut validator, keys) = test_network(3);
+        let meshx = StakeAmount::from_meshx;
+        let delegator = test_keypair(20);
+        fund(&mut validator, 20, meshx(50_000));
+
+        // Nothing to delegate beyond the balance, and nothing counts until
+        // it's debited
+        assert_eq!(
+            delegate(&mut validator, 20, &keys[0], meshx(50_001)),
+            Err(ValidationError::InsufficientBalance)
+        );
+        assert_eq!(validator.effective_stake(&keys[0]), meshx(100_000));
+        delegate(&mut validator, 20, &keys[0], meshx(20_000)).unwrap();
+        assert_eq!(validator.reward_balances[&delegator.public], meshx(30_000));
+        assert_eq!(validator.effective_stake(&keys[0]), meshx(120_000));
+
+        // Someone else can't spend the delegator's balance, even with its
+        // key in the `delegator` field
+        let forged = SignedDelegation::sign(
+            Delegation {
+                delegator: delegator.public,
+                validator: keys[1],
+                amount: meshx(30_000),
+                nonce: 1,
+            },
+            &test_keypair(21),
+        );
+        assert_eq!(
+            validator.delegate(&forged),
+            Err(ValidationError::InvalidSignature)
+        );
+
+        // A signed delegation applies once
+        let delegation = Delegation {
+            delegator: delegator.public,
+            validator: keys[1],
+            amount: meshx(10_000),
+            nonce: 1,
+        };
+        let signed = SignedDelegation::sign(delegation, &delegator);
+        validator.delegate(&signed).unwrap();
+        assert_eq!(
+            validator.delegate(&signed),
+            Err(ValidationError::DelegationReplayed)
+        );
+        assert_eq!(validator.reward_balances[&delegator.public], meshx(20_000));
+        assert_eq!(validator.delegated_to(&keys[1]), meshx(10_000));
+    }
+
+    #[test]
+    fn test_circular_delegation_rejected() {
+        let (mut validator, keys) = test_network(4);
+        let (a, b, c) = (keys[0], keys[1], keys[2]);
+        let amount = StakeAmount::from_meshx(1_000);
+        for seed in 1..=4 {
+            fund(&mut validator, seed, StakeAmount::from_meshx(10_000));
+        }
+
+        // Two delegators backing one validator is no cycle
+        delegate(&mut validator, 1, &b, amount).unwrap();
+        delegate(&mut validator, 3, &b, amount).unwrap();
+        assert_eq!(
+            delegate(&mut validator, 2, &a, amount),
+            Err(ValidationError::CircularDelegation)
+        );
+        assert_eq!(
+            delegate(&mut validator, 1, &a, amount),
+            Err(ValidationError::CircularDelegation)
+        );
+
+        // Longer loops are caught too: a -> b -> d -> a
+        delegate(&mut validator, 2, &keys[3], amount).unwrap();
+        assert_eq!(
+            delegate(&mut validator, 4, &a, amount),
+            Err(ValidationError::CircularDelegation)
+        );
+        assert_eq!(validator.delegated_to(&a), StakeAmount::ZERO);
+
+        validator.policy.delegation_caps.allow_cycles = true;
+        delegate(&mut validator, 2, &a, amount).unwrap();
+        assert_eq!(validator.delegated_to(&c), StakeAmount::ZERO);
+    }
+}
@@ -0,0 +1,142 @@
+// MeshX - The Immutable Global Device Mesh
+// Fallback for shards short of TEE diversity
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{MeshXNode, PopValidator, Shard, ValidationError};
+
+// What selection does with a shard whose candidates span fewer TEE types
+// than `min_tee_types_per_shard`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiversityFallback {
+    #[default]
+    Relax, // Fill the shard anyway, logging a warning
+    LeaveUnfilled, // Leave empty the seats the missing types would have taken
+}
+
+// A shard whose selection fell short of the TEE diversity requirement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiversityShortfall {
+    pub shard: Shard,
+    pub tee_types: usize, // Distinct types the shard's selection reached
+    pub required: usize,
+    pub fallback: DiversityFallback,
+    pub unfilled: usize, // Seats left empty; always 0 when relaxed
+}
+
+// A selection that failed, with the shortfalls behind it
+pub type ShortfallError = (ValidationError, Vec<DiversityShortfall>);
+
+impl PopValidator {
+    // Apply `diversity_fallback` to `shard`'s part of a ranked selection that
+    // reached only `tee_types` distinct types
+    pub(crate) fn apply_diversity_fallback(
+        &self,
+        shard: Shard,
+        tee_types: usize,
+        selected: &mut Vec<&MeshXNode>,
+    ) -> DiversityShortfall {
+        let mut unfilled = 0;
+        match self.diversity_fallback {
+            DiversityFallback::Relax => {
+                tracing::warn!(
+                    ?shard,
+                    tee_types,
+                    required = self.min_tee_types_per_shard,
+                    "relaxing TEE diversity to fill shard"
+                );
+            }
+            DiversityFallback::LeaveUnfilled => {
+                // Each missing type would have displaced the worst-ranked
+                // node of a type holding more than one seat
+                let missing = self.min_tee_types_per_shard.saturating_sub(tee_types);
+                while unfilled < missing {
+                    let duplicated = |node: &&MeshXNode| {
+                        let tee_type = &node.tee_attestation.tee_type;
+                        node.shard == shard
+                            && selected
+                                .iter()
+                                .filter(|other| {
+                                    other.shard == shard
+                                        && other.tee_attestation.tee_type == *tee_type
+                                })
+                                .count()
+                                > 1
+                    };
+                    let Some(position) = selected.iter().rposition(duplicated) else {
+                        break;
+                    };
+                    selected.remove(position);
+                    unfilled += 1;
+                }
+            }
+        }
+        DiversityShortfall {
+            shard,
+            tee_types,
+            required: self.min_tee_types_per_shard,
+            fallback: self.diversity_fallback,
+            unfilled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+    use crate::proof_of_presence::TeeType;
+
+    #[test]
+    fn test_diversity_fallback_policies() {
+        let (mut validator, _) = test_network(5);
+        validator.validator_count = 4;
+        let ranked = validator.select_validators(0).unwrap();
+        validator
+            .nodes
+            .get_mut(&ranked[2])
+            .unwrap()
+            .tee_attestation
+            .tee_type = TeeType::AmdSev;
+        // Only SGX and AMD are available, short of three types
+        validator.min_tee_types_per_shard = 3;
+
+        let (relaxed, shortfalls) = validator.select_validators_with_shortfalls(0).unwrap();
+        assert_eq!(relaxed, ranked);
+        assert_eq!(
+            shortfalls,
+            vec![DiversityShortfall {
+                shard: Shard::Europe,
+                tee_types: 2,
+                required: 3,
+                fallback: DiversityFallback::Relax,
+                unfilled: 0,
+            }]
+        );
+
+        // One type is missing, so one seat stays empty: the worst-ranked SGX
+        // node's, which a third type would have taken
+        validator.diversity_fallback = DiversityFallback::LeaveUnfilled;
+        let (unfilled, shortfalls) = validator.select_validators_with_shortfalls(0).unwrap();
+        assert_eq!(unfilled, vec![ranked[0], ranked[1], ranked[2]]);
+        assert_eq!(shortfalls[0].unfilled, 1);
+        assert_eq!(shortfalls[0].fallback, DiversityFallback::LeaveUnfilled);
+        // Deterministic across repeated selections
+        assert_eq!(validator.select_validators(0).unwrap(), unfilled);
+
+        // Too few seats left is still reported with the shortfall behind it
+        validator.minimum_nodes = 4;
+        assert_eq!(
+            validator.select_validators_with_shortfalls(0),
+            Err((ValidationError::InsufficientValidators, shortfalls))
+        );
+        validator.minimum_nodes = 1;
+
+        // A shard meeting the requirement reports nothing
+        validator.min_tee_types_per_shard = 2;
+        let (selected, shortfalls) = validator.select_validators_with_shortfalls(0).unwrap();
+        assert_eq!(selected, ranked);
+        assert!(shortfalls.is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+// MeshX - The Immutable Global Device Mesh
+// Clock drift estimation from reported timestamps
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use std::collections::{HashMap, VecDeque};
+
+use crate::proof_of_presence::PopValidator;
+
+// Offsets kept per node; older samples are dropped first
+const CLOCK_SAMPLES_PER_NODE: usize = 32;
+
+impl PopValidator {
+    // Note how far ahead (positive) or behind a node's clock looked when one
+    // of its timestamps arrived
+    pub(crate) fn record_clock_sample(&mut self, pubkey: &PublicKey, reported_secs: u64) {
+        let offset_ms = (reported_secs as i64 - self.now_secs() as i64) * 1000;
+        let samples = self.clock_samples.entry(*pubkey).or_default();
+        samples.push_back(offset_ms);
+        if samples.len() > CLOCK_SAMPLES_PER_NODE {
+            samples.pop_front();
+        }
+    }
+
+    // Each node's estimated clock offset from the validator's clock in
+    // milliseconds: the median offset of the latency measurements it
+    // reported, so a single delayed report doesn't skew it. Resolution is
+    // limited by the seconds-precision timestamps.
+    pub fn clock_drift_report(&self) -> HashMap<PublicKey, i64> {
+        self.clock_samples
+            .iter()
+            .filter_map(|(pubkey, samples)| Some((*pubkey, median(samples)?)))
+            .collect()
+    }
+
+    // Nodes whose estimated offset exceeds `clock_drift_tolerance_ms` in
+    // either direction, sorted by key
+    pub fn clock_drift_outliers(&self) -> Vec<PublicKey> {
+        let mut outliers: Vec<PublicKey> = self
+            .clock_drift_report()
+            .into_iter()
+            .filter(|(_, offset_ms)| offset_ms.abs() > self.clock_drift_tolerance_ms)
+            .map(|(pubkey, _)| pubkey)
+            .collect();
+        outliers.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        outliers
+    }
+}
+
+fn median(samples: &VecDeque<i64>) -> Option<i64> {
+    let mut sorted: Vec<i64> = samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        len if len % 2 == 0 => Some((sorted[mid - 1] + sorted[mid]) / 2),
+        _ => Some(sorted[mid]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::ManualClock;
+    use crate::proof_of_presence::tests::{test_network, test_pubkey};
+    use crate::proof_of_presence::LatencyMeasurement;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_clock_drift_report() {
+        let (mut validator, keys) = test_network(3);
+        let clock = Arc::new(ManualClock::new(1_700_000_000));
+        validator.set_clock(clock.clone());
+
+        for round in 0..5 {
+            let now = validator.now_secs();
+            for (from, skew_secs) in [(keys[0], 30), (keys[1], 0)] {
+                validator.record_latency(&LatencyMeasurement {
+                    from_node: from,
+                    to_node: keys[2],
+                    latency_ms: 10,
+                    timestamp: now + skew_secs,
+                    is_rtt: false,
+                });
+            }
+            // One delayed report from the on-time node
+            if round == 2 {
+                validator.record_latency(&LatencyMeasurement {
+                    from_node: keys[1],
+                    to_node: keys[0],
+                    latency_ms: 10,
+                    timestamp: now - 20,
+                    is_rtt: false,
+                });
+            }
+            clock.advance(60);
+        }
+
+        let report = validator.clock_drift_report();
+        assert_eq!(report[&keys[0]], 30_000);
+        assert_eq!(report[&keys[1]], 0);
+        assert!(!report.contains_key(&keys[2]));
+        assert!(!report.contains_key(&test_pubkey(9)));
+        assert_eq!(validator.clock_drift_outliers(), vec![keys[0]]);
+    }
+}
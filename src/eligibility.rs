@@ -0,0 +1,252 @@
+// MeshX - The Immutable Global Device Mesh
+// Selection eligibility countdown
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::Serialize;
+
+use crate::proof_of_presence::{PopValidator, Shard, ValidationError};
+
+// Something keeping a node out of validator selection
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum EligibilityBlocker {
+    Banned,
+    SlashCooldown,
+    LivenessDemotion,
+    Warmup,
+    TeeChangeHold,
+    ProvisionalMigration,
+    AwaitingPromotion, // Pending until peers have measured its latency
+    StaleAttestation,
+    Invalid(ValidationError), // Any other failed validation check
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockingCondition {
+    pub blocker: EligibilityBlocker,
+    pub clears_epoch: Option<u64>, // First epoch it no longer applies; None until the operator acts
+    pub clears_in_secs: Option<u64>, // From `now` until the node's shard enters that epoch
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EligibilityStatus {
+    pub eligible: bool,
+    pub blocking: Vec<BlockingCondition>,
+}
+
+impl PopValidator {
+    // Everything keeping the node out of selection in the current epoch,
+    // with when each clears on its own. Attestation freshness is judged at
+    // `now`, which also anchors the countdowns.
+    pub fn eligibility_status(&self, pubkey: &PublicKey, now: u64) -> EligibilityStatus {
+        let epoch = self.current_epoch;
+        let registered = self.nodes.get(pubkey);
+        let node = registered.or_else(|| self.pending_nodes.get(pubkey));
+        let shard = node.map(|node| node.shard);
+        let condition = |blocker, clears_epoch: Option<u64>| BlockingCondition {
+            blocker,
+            clears_epoch,
+            clears_in_secs: clears_epoch
+                .map(|clears| self.epoch_start(shard, clears).saturating_sub(now)),
+        };
+
+        let mut blocking = Vec::new();
+        if self.is_banned(pubkey, epoch) {
+            let expiry = self.bans.get(pubkey).copied().flatten();
+            blocking.push(condition(EligibilityBlocker::Banned, expiry));
+        }
+        let Some(node) = node else {
+            if blocking.is_empty() {
+                blocking.push(condition(
+                    EligibilityBlocker::Invalid(ValidationError::UnknownNode),
+                    None,
+                ));
+            }
+            return EligibilityStatus {
+                eligible: false,
+                blocking,
+            };
+        };
+
+        if self.in_slash_cooldown(pubkey, epoch) {
+            let slashed = self.slashed_at[pubkey];
+            let clears = slashed + self.slash_cooldown_epochs + 1;
+            blocking.push(condition(EligibilityBlocker::SlashCooldown, Some(clears)));
+        }
+        if self.in_liveness_demotion(pubkey, epoch) {
+            let clears = self.demoted_at[pubkey] + self.slash_cooldown_epochs + 1;
+            blocking.push(condition(
+                EligibilityBlocker::LivenessDemotion,
+                Some(clears),
+            ));
+        }
+        if self.in_warmup(pubkey, epoch) {
+            let clears = self.registered_epochs[pubkey] + self.warmup_epochs;
+            blocking.push(condition(EligibilityBlocker::Warmup, Some(clears)));
+        }
+        if self.in_tee_change_hold(pubkey, epoch) {
+            let hold = &self.tee_change_holds[pubkey];
+            let clears = hold.reattested.then_some(hold.until_epoch);
+            blocking.push(condition(EligibilityBlocker::TeeChangeHold, clears));
+        }
+        if let Some(migration) = self.provisional_migrations.get(pubkey) {
+            // Cleared by a top-up, or by reverting once the window ends
+            blocking.push(condition(
+                EligibilityBlocker::ProvisionalMigration,
+                Some(migration.deadline_epoch + 1),
+            ));
+        }
+        if registered.is_none() {
+            blocking.push(condition(EligibilityBlocker::AwaitingPromotion, None));
+        }
+
+        // Freshness is judged at `now` here, so the validator clock's own
+        // verdict on it is set aside below
+        let attested = node.tee_attestation.timestamp;
+        if attested > now.saturating_add(self.policy.max_attestation_future_secs) {
+            blocking.push(condition(
+                EligibilityBlocker::Invalid(ValidationError::FutureAttestation),
+                None,
+            ));
+        } else if now.saturating_sub(attested) > self.policy.max_attestation_age_secs {
+            blocking.push(condition(EligibilityBlocker::StaleAttestation, None));
+        } else if registered.is_some() {
+            match self.validate_node(node) {
+                Ok(_)
+                | Err(ValidationError::StaleAttestation)
+                | Err(ValidationError::FutureAttestation) => {}
+                Err(error) => blocking.push(condition(EligibilityBlocker::Invalid(error), None)),
+            }
+        }
+
+        EligibilityStatus {
+            eligible: blocking.is_empty(),
+            blocking,
+        }
+    }
+
+    // Whether a newly registered node is still waiting out `warmup_epochs`
+    pub fn in_warmup(&self, pubkey: &PublicKey, epoch: u64) -> bool {
+        self.registered_epochs
+            .get(pubkey)
+            .is_some_and(|registered| epoch < registered + self.warmup_epochs)
+    }
+
+    fn epoch_start(&self, shard: Option<Shard>, epoch: u64) -> u64 {
+        match shard {
+            Some(shard) => self.shard_transition_time(shard, epoch),
+            None => self.genesis_time + epoch * self.epoch_length_secs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::{current_timestamp, test_network};
+    use crate::stake::StakeAmount;
+
+    #[test]
+    fn test_slashed_warming_node_countdown() {
+        let (mut validator, keys) = test_network(5);
+        let now = current_timestamp();
+        validator.genesis_time = now - 10 * 3600 - 600;
+        validator.current_epoch = 10;
+        validator.warmup_epochs = 3;
+        validator.slash_cooldown_epochs = 4;
+        // The countdown checks run hours ahead of the node's attestation
+        validator.policy.max_attestation_age_secs = 24 * 3600;
+
+        let node = validator.nodes.remove(&keys[0]).unwrap();
+        validator.register_node(node).unwrap();
+        validator.slash_node(&keys[0], StakeAmount::ZERO).unwrap();
+
+        let status = validator.eligibility_status(&keys[0], now);
+        assert!(!status.eligible);
+        assert_eq!(
+            status.blocking,
+            vec![
+                BlockingCondition {
+                    blocker: EligibilityBlocker::SlashCooldown,
+                    clears_epoch: Some(15),
+                    clears_in_secs: Some(5 * 3600 - 600),
+                },
+                BlockingCondition {
+                    blocker: EligibilityBlocker::Warmup,
+                    clears_epoch: Some(13),
+                    clears_in_secs: Some(3 * 3600 - 600),
+                },
+            ]
+        );
+        assert!(validator.eligibility_status(&keys[1], now).eligible);
+
+        validator.current_epoch = 13;
+        let status = validator.eligibility_status(&keys[0], now + 3 * 3600);
+        assert_eq!(status.blocking.len(), 1);
+        assert_eq!(
+            status.blocking[0].blocker,
+            EligibilityBlocker::SlashCooldown
+        );
+
+        validator.current_epoch = 15;
+        let status = validator.eligibility_status(&keys[0], now + 5 * 3600);
+        assert_eq!(
+            status,
+            EligibilityStatus {
+                eligible: true,
+                blocking: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_future_dated_attestation_blocks() {
+        let (mut validator, keys) = test_network(5);
+        let now = current_timestamp();
+        let future_secs = validator.policy.max_attestation_future_secs;
+        validator
+            .nodes
+            .get_mut(&keys[0])
+            .unwrap()
+            .tee_attestation
+            .timestamp = now + future_secs + 600;
+
+        // Dated too far past `now`, even though it'll be fresh by then
+        assert_eq!(
+            validator.eligibility_status(&keys[0], now).blocking,
+            vec![BlockingCondition {
+                blocker: EligibilityBlocker::Invalid(ValidationError::FutureAttestation),
+                clears_epoch: None,
+                clears_in_secs: None,
+            }]
+        );
+        assert!(validator.eligibility_status(&keys[0], now + 600).eligible);
+    }
+
+    #[test]
+    fn test_registration_epoch_recorded_only_on_success() {
+        let (mut validator, keys) = test_network(5);
+        validator.warmup_epochs = 2;
+        let node = validator.nodes.remove(&keys[0]).unwrap();
+        validator
+            .attestation_timestamps
+            .insert(keys[0], (0, node.tee_attestation.timestamp + 1));
+        assert_eq!(
+            validator.register_node(node),
+            Err(ValidationError::AttestationRegression)
+        );
+        assert!(!validator.in_warmup(&keys[0], 0));
+        assert!(!validator.registered_epochs.contains_key(&keys[0]));
+    }
+
+    #[test]
+    fn test_warming_node_not_selected() {
+        let (mut validator, keys) = test_network(5);
+        validator.warmup_epochs = 2;
+        let node = validator.nodes.remove(&keys[0]).unwrap();
+        validator.register_node(node).unwrap();
+
+        assert!(!validator.select_validators(1).unwrap().contains(&keys[0]));
+        assert!(validator.select_validators(2).unwrap().contains(&keys[0]));
+    }
+}
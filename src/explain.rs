@@ -0,0 +1,174 @@
+// MeshX - The Immutable Global Device Mesh
+// Operator-facing explanations of validation failures
+// Copyright (c) 2025 MeshX Foundation
+
+use crate::proof_of_presence::{MeshXNode, PopValidator, ValidationError, ValidationReport};
+
+impl PopValidator {
+    // Run every validation check and describe each failure with what the
+    // operator can do about it, one line per failure
+    pub fn explain_rejection(&self, node: &MeshXNode) -> String {
+        let report = self.validation_report(node);
+        if report.is_valid() {
+            return "Node passes every validation check".to_string();
+        }
+
+        let mut lines = Vec::new();
+        if let Some(error) = &report.attestation.error {
+            lines.push(self.explain_attestation(&report, error));
+        }
+        if let Some(error) = &report.location.error {
+            lines.push(self.explain_location(&report, error));
+        }
+        if let Some(error) = &report.stake.error {
+            lines.push(self.explain_stake(&report, error));
+        }
+        for check in &report.resources {
+            match &check.error {
+                Some(ValidationError::InvalidGpuReport) => lines
+                    .push("GPU reports no memory; fix GPU detection, or report no GPU".to_string()),
+                Some(_) => lines.push(format!(
+                    "{} is {}, below the minimum of {}; upgrade the hardware",
+                    check.resource, check.actual, check.required
+                )),
+                None => {}
+            }
+        }
+        if report.metadata.error.is_some() {
+            let limits = &self.policy.metadata_limits;
+            lines.push(format!(
+                "Metadata is {} bytes; keys may be {} bytes, values {} and all labels {} together",
+                report.metadata.total_bytes,
+                limits.max_key_len,
+                limits.max_value_len,
+                report.metadata.max_total_bytes
+            ));
+        }
+        lines
+            .iter()
+            .map(|line| format!("- {}", line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn explain_attestation(&self, report: &ValidationReport, error: &ValidationError) -> String {
+        let attestation = &report.attestation;
+        match error {
+            ValidationError::StaleAttestation => format!(
+                "Attestation is {} old; re-attest, max age is {}",
+                format_duration(attestation.age_secs.unsigned_abs()),
+                format_duration(attestation.max_age_secs)
+            ),
+            ValidationError::FutureAttestation => format!(
+                "Attestation is dated {} ahead of the validator's clock; fix the node's clock \
+                 and re-attest",
+                format_duration(attestation.age_secs.unsigned_abs())
+            ),
+            ValidationError::InvalidEnclaveCode => {
+                "Enclave code hash doesn't match the MeshX release; run the official enclave build"
+                    .to_string()
+            }
+            ValidationError::PlaceholderAttestation => {
+                "Attestation carries a placeholder enclave hash; attest from real TEE hardware"
+                    .to_string()
+            }
+            ValidationError::UnsupportedTeeType => format!(
+                "No verifier is registered for {:?} attestations; use a supported TEE",
+                attestation.tee_type
+            ),
+            ValidationError::InvalidQuote => {
+                "TEE quote failed verification; regenerate the quote and re-attest".to_string()
+            }
+            ValidationError::RevokedSigner => {
+                "Attestation signing key has been revoked; re-provision the TEE".to_string()
+            }
+            other => other.to_string(),
+        }
+    }
+
+    fn explain_location(&self, report: &ValidationReport, error: &ValidationError) -> String {
+        let location = &report.location;
+        match error {
+            ValidationError::InsufficientLatencyData => format!(
+                "Only {} peers have measured latency to the node, {} are needed; \
+                 wait for more peers to measure it",
+                location.measurement_count, self.policy.min_latency_measurements
+            ),
+            ValidationError::LocationMismatch => format!(
+                "Claimed location is {:.0} km from where latency places the node, \
+                 beyond the claimed accuracy of {:.0} km; correct the coordinates",
+                location.distance_meters.unwrap_or(0.0) / 1000.0,
+                location.claimed.accuracy_meters / 1000.0
+            ),
+            ValidationError::TriangulatedShardMismatch => format!(
+                "Latency places the node outside the {:?} shard; correct the coordinates",
+                report.shard
+            ),
+            other => other.to_string(),
+        }
+    }
+
+    fn explain_stake(&self, report: &ValidationReport, error: &ValidationError) -> String {
+        let stake = &report.stake;
+        match error {
+            ValidationError::InsufficientStake => format!(
+                "Stake {} is below the {:?} minimum of {}; add {} more",
+                stake.effective_stake,
+                report.shard,
+                stake.required_stake,
+                stake.required_stake.saturating_sub(stake.effective_stake)
+            ),
+            ValidationError::ImplausibleStake => format!(
+                "Stake {} exceeds the {}% of total supply one node may hold",
+                stake.effective_stake,
+                self.policy.max_stake_fraction * 100.0
+            ),
+            other => other.to_string(),
+        }
+    }
+}
+
+// Largest whole unit, e.g. "2h", "45m" or "30s"
+fn format_duration(secs: u64) -> String {
+    match secs {
+        s if s >= 3600 && s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s >= 3600 => format!("{:.1}h", s as f64 / 3600.0),
+        s if s >= 60 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::proof_of_presence::tests::test_network;
+    use crate::stake::StakeAmount;
+
+    #[test]
+    fn test_explain_rejection_gives_shortfall() {
+        let (mut validator, keys) = test_network(4);
+        let node = validator.nodes.get_mut(&keys[0]).unwrap();
+        node.stake_amount = StakeAmount::from_meshx(40_000);
+        node.tee_attestation.timestamp -= 7200;
+        let node = node.clone();
+
+        let explanation = validator.explain_rejection(&node);
+        assert!(explanation.contains(
+            "Stake 40000 MESHX is below the Europe minimum of 100000 MESHX; add 60000 MESHX more"
+        ));
+        assert!(explanation.contains("re-attest, max age is 1h"));
+        assert_eq!(explanation.lines().count(), 2);
+
+        let mut no_gpu_memory = validator.nodes[&keys[2]].clone();
+        no_gpu_memory.resources.gpu_memory_gb = Some(0);
+        assert_eq!(
+            validator.explain_rejection(&no_gpu_memory),
+            "- GPU reports no memory; fix GPU detection, or report no GPU"
+        );
+
+        let valid = validator.nodes[&keys[1]].clone();
+        assert_eq!(
+            validator.explain_rejection(&valid),
+            "Node passes every validation check"
+        );
+    }
+}
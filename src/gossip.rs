@@ -0,0 +1,476 @@
+// MeshX - The Immutable Global Device Mesh
+// Node announcement gossip
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::{Keypair, PublicKey, Signature, Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::{HashMap, VecDeque};
+
+use crate::proof_of_presence::{
+    LatencyMeasurement, MeshXNode, NodeSummary, PopValidator, ValidationError,
+};
+
+// How to merge two measurements of the same edge carrying the same
+// timestamp. Each is independent of the order measurements arrive in, so
+// peers gossiping the same measurements converge on the same matrix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LatencyConflictPolicy {
+    #[default]
+    Min,
+    Max,
+    Mean, // Of the distinct latencies reported, rounded to the nearest ms
+}
+
+// A node record signed by the node's own key, as gossiped between peers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedNodeAnnouncement {
+    pub node: MeshXNode,
+    pub signature: Signature,
+}
+
+impl SignedNodeAnnouncement {
+    pub fn sign(node: MeshXNode, keypair: &Keypair) -> Self {
+        let signature = keypair.sign(&Self::signing_payload(&node));
+        Self { node, signature }
+    }
+
+    pub fn verify(&self) -> Result<(), ValidationError> {
+        self.node
+            .pubkey
+            .verify(&Self::signing_payload(&self.node), &self.signature)
+            .map_err(|_| ValidationError::InvalidSignature)
+    }
+
+    // Hash of the full announcement, signature included, used to recognise
+    // repeats of the same gossip message
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_ANNOUNCEMENT_ID");
+        hasher.update(serde_json::to_vec(self).expect("announcement serializes"));
+
+        let mut output = [0u8; 32];
+        output.copy_from_slice(&hasher.finalize());
+        output
+    }
+
+    fn signing_payload(node: &MeshXNode) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_NODE_ANNOUNCEMENT");
+        hasher.update(serde_json::to_vec(node).expect("node serializes"));
+        hasher.finalize().to_vec()
+    }
+}
+
+// A node summary signed by the node's own key, gossiped in place of the
+// full record; peers fetch the full record only when the summary is news
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedNodeSummary {
+    pub summary: NodeSummary,
+    pub signature: Signature,
+}
+
+impl SignedNodeSummary {
+    pub fn sign(summary: NodeSummary, keypair: &Keypair) -> Self {
+        let signature = keypair.sign(&Self::signing_payload(&summary));
+        Self { summary, signature }
+    }
+
+    pub fn verify(&self) -> Result<(), ValidationError> {
+        self.summary
+            .pubkey
+            .verify(&Self::signing_payload(&self.summary), &self.signature)
+            .map_err(|_| ValidationError::InvalidSignature)
+    }
+
+    fn signing_payload(summary: &NodeSummary) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_NODE_SUMMARY");
+        hasher.update(serde_json::to_vec(summary).expect("summary serializes"));
+        hasher.finalize().to_vec()
+    }
+}
+
+// A latency measurement signed by the node that took it, its `from_node`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedLatencyMeasurement {
+    pub measurement: LatencyMeasurement,
+    pub signature: Signature,
+}
+
+impl SignedLatencyMeasurement {
+    pub fn sign(measurement: LatencyMeasurement, keypair: &Keypair) -> Self {
+        let signature = keypair.sign(&Self::signing_payload(&measurement));
+        Self {
+            measurement,
+            signature,
+        }
+    }
+
+    // Only the reporting node may vouch for edges originating from it
+    pub fn verify(&self) -> Result<(), ValidationError> {
+        self.measurement
+            .from_node
+            .verify(&Self::signing_payload(&self.measurement), &self.signature)
+            .map_err(|_| ValidationError::UnauthorizedMeasurement)
+    }
+
+    fn signing_payload(measurement: &LatencyMeasurement) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_LATENCY_MEASUREMENT");
+        hasher.update(serde_json::to_vec(measurement).expect("measurement serializes"));
+        hasher.finalize().to_vec()
+    }
+}
+
+// Bounded set of recently processed announcement hashes. Entries expire after
+// the TTL, and the oldest entry is evicted once capacity is reached.
+#[derive(Clone)]
+pub struct SeenAnnouncements {
+    pub capacity: usize,
+    pub ttl_secs: u64,
+    seen_at: HashMap<[u8; 32], u64>,
+    order: VecDeque<([u8; 32], u64)>,
+}
+
+impl SeenAnnouncements {
+    pub fn new(capacity: usize, ttl_secs: u64) -> Self {
+        Self {
+            capacity,
+            ttl_secs,
+            seen_at: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    // Record a hash, returning false if it was already seen within the TTL
+    pub fn insert(&mut self, hash: [u8; 32], now: u64) -> bool {
+        self.evict_expired(now);
+        if self.seen_at.contains_key(&hash) {
+            return false;
+        }
+
+        while self.seen_at.len() >= self.capacity.max(1) {
+            match self.order.pop_front() {
+                Some((oldest, _)) => {
+                    self.seen_at.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.seen_at.insert(hash, now);
+        self.order.push_back((hash, now));
+        true
+    }
+
+    // Whether a hash was recorded within the TTL
+    pub fn contains(&self, hash: &[u8; 32], now: u64) -> bool {
+        self.seen_at
+            .get(hash)
+            .is_some_and(|seen_at| now.saturating_sub(*seen_at) < self.ttl_secs)
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen_at.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen_at.is_empty()
+    }
+
+    pub(crate) fn evict_expired(&mut self, now: u64) {
+        while let Some(&(hash, seen_at)) = self.order.front() {
+            if now.saturating_sub(seen_at) < self.ttl_secs {
+                break;
+            }
+            self.order.pop_front();
+            self.seen_at.remove(&hash);
+        }
+    }
+}
+
+impl PopValidator {
+    // Process a gossiped announcement. Repeats of an announcement already
+    // processed within the TTL are skipped before any signature or presence
+    // verification, returning Ok(false); the first copy is verified and
+    // registered, returning Ok(true). An announcement is marked seen once it
+    // registers or its signature fails, which no re-gossiped copy can fix.
+    // A copy rejected for a transient reason, such as missing latency data,
+    // is processed again when it's re-gossiped.
+    pub fn process_announcement(
+        &mut self,
+        announcement: &SignedNodeAnnouncement,
+    ) -> Result<bool, ValidationError> {
+        let now = self.now_secs();
+        let hash = announcement.content_hash();
+        if self.seen_announcements.contains(&hash, now) {
+            return Ok(false);
+        }
+
+        self.announcement_verifications += 1;
+        // The hash covers the signature, so a genuine copy of the same record
+        // is still processed
+        if let Err(error) = announcement.verify() {
+            self.seen_announcements.insert(hash, now);
+            return Err(error);
+        }
+        self.register_node(announcement.node.clone())?;
+        self.seen_announcements.insert(hash, now);
+        Ok(true)
+    }
+
+    // Check a gossiped summary, returning whether the node's full record
+    // needs fetching: it's unknown here, or the summary differs from the
+    // registered record. The fetched record goes through
+    // `process_announcement` like any other.
+    pub fn process_summary(&self, signed: &SignedNodeSummary) -> Result<bool, ValidationError> {
+        signed.verify()?;
+        Ok(self
+            .nodes
+            .get(&signed.summary.pubkey)
+            .is_none_or(|node| node.summary() != signed.summary))
+    }
+
+    // Authenticate a gossiped measurement and upsert it into the latency
+    // matrix. Returns Ok(false) for duplicates and superseded measurements.
+    pub fn record_signed_latency(
+        &mut self,
+        signed: &SignedLatencyMeasurement,
+    ) -> Result<bool, ValidationError> {
+        signed.verify()?;
+        Ok(self.record_latency(&signed.measurement))
+    }
+
+    // Merge a latency reported for an edge at its applied timestamp.
+    // Returns whether the edge's latency changed.
+    pub(crate) fn resolve_latency_conflict(
+        &mut self,
+        edge: (PublicKey, PublicKey),
+        latency_ms: u32,
+    ) -> bool {
+        let Some(&applied) = self.latency_matrix.get(&edge) else {
+            return false;
+        };
+        let merged = match self.latency_conflict_policy {
+            LatencyConflictPolicy::Min => applied.min(latency_ms),
+            LatencyConflictPolicy::Max => applied.max(latency_ms),
+            LatencyConflictPolicy::Mean => {
+                let reported = self
+                    .latency_conflicts
+                    .entry(edge)
+                    .or_insert_with(|| vec![applied]);
+                if !reported.contains(&latency_ms) {
+                    reported.push(latency_ms);
+                }
+                let sum: u64 = reported.iter().map(|latency| *latency as u64).sum();
+                let count = reported.len() as u64;
+                ((sum + count / 2) / count) as u32
+            }
+        };
+        if merged == applied {
+            return false;
+        }
+        self.latency_matrix.insert(edge, merged);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presence::tests::test_keypair;
+    use crate::proof_of_presence::tests::test_node;
+    use crate::stake::StakeAmount;
+
+    // Validator with three anchors measuring the announced node
+    fn anchored_validator(announced: &Keypair) -> PopValidator {
+        let mut validator = PopValidator::new(1);
+        for seed in 10..13 {
+            let anchor = test_node(seed, 50.0, 10.0, 100_000);
+            validator
+                .latency_matrix
+                .insert((anchor.pubkey, announced.public), 10);
+            validator.nodes.insert(anchor.pubkey, anchor);
+        }
+        validator
+    }
+
+    #[test]
+    fn test_duplicate_announcement_short_circuits() {
+        let keypair = test_keypair(1);
+        let mut validator = anchored_validator(&keypair);
+        let announcement =
+            SignedNodeAnnouncement::sign(test_node(1, 50.0, 10.0, 100_000), &keypair);
+
+        assert_eq!(validator.process_announcement(&announcement), Ok(true));
+        assert_eq!(validator.announcement_verifications, 1);
+
+        assert_eq!(
+            validator.process_announcement(&announcement.clone()),
+            Ok(false)
+        );
+        assert_eq!(validator.announcement_verifications, 1);
+
+        // An updated record is a new announcement and is processed
+        let updated = SignedNodeAnnouncement::sign(test_node(1, 51.0, 10.0, 100_000), &keypair);
+        assert_eq!(validator.process_announcement(&updated), Ok(true));
+        assert_eq!(validator.announcement_verifications, 2);
+        assert_eq!(validator.nodes[&keypair.public].geo_location.latitude, 51.0);
+    }
+
+    #[test]
+    fn test_forged_announcement_rejected() {
+        let keypair = test_keypair(1);
+        let mut validator = anchored_validator(&keypair);
+        let genuine = SignedNodeAnnouncement::sign(test_node(1, 50.0, 10.0, 100_000), &keypair);
+        let mut announcement = genuine.clone();
+        announcement.node.stake_amount = StakeAmount::from_meshx(1_000_000);
+
+        assert_eq!(
+            validator.process_announcement(&announcement),
+            Err(ValidationError::InvalidSignature)
+        );
+        assert!(!validator.nodes.contains_key(&keypair.public));
+
+        // Re-gossiped forgeries aren't verified again
+        assert_eq!(validator.process_announcement(&announcement), Ok(false));
+        assert_eq!(validator.announcement_verifications, 1);
+        assert_eq!(validator.process_announcement(&genuine), Ok(true));
+    }
+
+    #[test]
+    fn test_rejected_announcement_not_marked_seen() {
+        let keypair = test_keypair(1);
+        let mut validator = anchored_validator(&keypair);
+        let announcement =
+            SignedNodeAnnouncement::sign(test_node(1, 50.0, 10.0, 100_000), &keypair);
+
+        // The anchors' measurements of the node haven't arrived yet
+        let measured = std::mem::take(&mut validator.latency_matrix);
+        assert!(validator.process_announcement(&announcement).is_err());
+        assert!(validator.seen_announcements.is_empty());
+
+        // Re-gossiped once they have, the same announcement registers
+        validator.latency_matrix = measured;
+        assert_eq!(validator.process_announcement(&announcement), Ok(true));
+        assert_eq!(validator.announcement_verifications, 2);
+        assert_eq!(validator.process_announcement(&announcement), Ok(false));
+    }
+
+    #[test]
+    fn test_summary_gossip_fetches_only_news() {
+        let keypair = test_keypair(1);
+        let mut validator = anchored_validator(&keypair);
+        let node = test_node(1, 50.0, 10.0, 100_000);
+        let signed = SignedNodeSummary::sign(node.summary(), &keypair);
+        assert_eq!(validator.process_summary(&signed), Ok(true));
+
+        let announcement = SignedNodeAnnouncement::sign(node.clone(), &keypair);
+        validator.process_announcement(&announcement).unwrap();
+        assert_eq!(validator.process_summary(&signed), Ok(false));
+
+        // A changed record is worth fetching; a forged summary is rejected
+        let moved = SignedNodeSummary::sign(test_node(1, 51.0, 10.0, 100_000).summary(), &keypair);
+        assert_eq!(validator.process_summary(&moved), Ok(true));
+        let mut forged = signed.clone();
+        forged.summary.stake_amount = StakeAmount::from_meshx(1_000_000);
+        assert_eq!(
+            validator.process_summary(&forged),
+            Err(ValidationError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_signed_latency_upsert() {
+        let reporter = test_keypair(1);
+        let target = test_keypair(2).public;
+        let measurement = |latency_ms, timestamp| LatencyMeasurement {
+            from_node: reporter.public,
+            to_node: target,
+            latency_ms,
+            timestamp,
+            is_rtt: false,
+        };
+        let mut validator = PopValidator::new(1);
+        let edge = (reporter.public, target);
+
+        let first = SignedLatencyMeasurement::sign(measurement(10, 100), &reporter);
+        assert_eq!(validator.record_signed_latency(&first), Ok(true));
+        assert_eq!(validator.record_signed_latency(&first.clone()), Ok(false));
+
+        // Older measurements don't overwrite newer ones
+        let stale = SignedLatencyMeasurement::sign(measurement(50, 90), &reporter);
+        assert_eq!(validator.record_signed_latency(&stale), Ok(false));
+        assert_eq!(validator.latency_matrix[&edge], 10);
+
+        let newer = SignedLatencyMeasurement::sign(measurement(12, 110), &reporter);
+        assert_eq!(validator.record_signed_latency(&newer), Ok(true));
+        assert_eq!(validator.latency_matrix[&edge], 12);
+
+        // Signed by the target rather than the reporting node
+        let forged = SignedLatencyMeasurement::sign(measurement(1, 120), &test_keypair(2));
+        assert_eq!(
+            validator.record_signed_latency(&forged),
+            Err(ValidationError::UnauthorizedMeasurement)
+        );
+        assert_eq!(validator.latency_matrix[&edge], 12);
+    }
+
+    #[test]
+    fn test_same_timestamp_conflict_is_order_independent() {
+        let (from, to) = (test_keypair(1).public, test_keypair(2).public);
+        let measurement = |latency_ms| LatencyMeasurement {
+            from_node: from,
+            to_node: to,
+            latency_ms,
+            timestamp: 100,
+            is_rtt: false,
+        };
+        let merged = |policy, order: &[u32]| {
+            let mut validator = PopValidator::new(1);
+            validator.latency_conflict_policy = policy;
+            for latency in order {
+                validator.record_latency(&measurement(*latency));
+            }
+            validator.latency_matrix[&(from, to)]
+        };
+
+        for (policy, expected) in [
+            (LatencyConflictPolicy::Min, 10),
+            (LatencyConflictPolicy::Max, 15),
+            (LatencyConflictPolicy::Mean, 13),
+        ] {
+            assert_eq!(merged(policy, &[10, 15]), expected, "{:?}", policy);
+            assert_eq!(merged(policy, &[15, 10]), expected, "{:?}", policy);
+            // Repeats of either side don't tip the result
+            assert_eq!(merged(policy, &[15, 10, 10, 15]), expected, "{:?}", policy);
+        }
+
+        // A newer measurement replaces the merged value outright
+        let mut validator = PopValidator::new(1);
+        validator.latency_conflict_policy = LatencyConflictPolicy::Mean;
+        validator.record_latency(&measurement(10));
+        validator.record_latency(&measurement(15));
+        assert!(validator.record_latency(&LatencyMeasurement {
+            timestamp: 101,
+            ..measurement(40)
+        }));
+        assert_eq!(validator.latency_matrix[&(from, to)], 40);
+        assert!(validator.latency_conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_seen_announcements_bounds() {
+        let mut seen = SeenAnnouncements::new(2, 60);
+        assert!(seen.insert([1; 32], 0));
+        assert!(!seen.insert([1; 32], 10));
+        assert!(seen.insert([2; 32], 10));
+        assert!(seen.insert([3; 32], 10)); // Evicts [1; 32]
+        assert_eq!(seen.len(), 2);
+        assert!(seen.insert([1; 32], 20));
+
+        // Everything has expired a TTL later
+        assert!(seen.insert([3; 32], 100));
+        assert_eq!(seen.len(), 1);
+    }
+}
@@ -0,0 +1,122 @@
+// MeshX - The Immutable Global Device Mesh
+// Previewing policy changes
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::Serialize;
+
+use crate::proof_of_presence::{PopValidator, Shard, ValidationError, ValidationPolicy};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ShardImpact {
+    pub shard: Shard,
+    pub valid_before: usize,
+    pub valid_after: usize,
+    pub pushed_below_minimum: bool, // Met `minimum_nodes` before the change but not after
+}
+
+// What a policy change would do to the registered nodes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigImpact {
+    pub newly_invalid: Vec<(PublicKey, ValidationError)>, // With the first hard failure under the new policy
+    pub newly_valid: Vec<PublicKey>,
+    pub shards: Vec<ShardImpact>,
+}
+
+impl ConfigImpact {
+    // Shards the change would leave unable to field `minimum_nodes` validators
+    pub fn shards_pushed_below_minimum(&self) -> Vec<Shard> {
+        self.shards
+            .iter()
+            .filter(|impact| impact.pushed_below_minimum)
+            .map(|impact| impact.shard)
+            .collect()
+    }
+}
+
+impl PopValidator {
+    // Validate every registered node under both the current and the proposed
+    // policy, without applying the proposal
+    pub fn preview_config_change(&self, new_policy: &ValidationPolicy) -> ConfigImpact {
+        let proposed = self.with_policy_view(new_policy.clone());
+        let mut nodes: Vec<_> = self.nodes.values().collect();
+        nodes.sort_by(|a, b| a.pubkey.as_bytes().cmp(b.pubkey.as_bytes()));
+
+        let mut newly_invalid = Vec::new();
+        let mut newly_valid = Vec::new();
+        let mut shards: Vec<ShardImpact> = Shard::all()
+            .into_iter()
+            .map(|shard| ShardImpact {
+                shard,
+                valid_before: 0,
+                valid_after: 0,
+                pushed_below_minimum: false,
+            })
+            .collect();
+        for node in nodes {
+            let before = self.validate_node(node).is_ok();
+            let after = proposed.validate_node(node);
+            let impact = shards
+                .iter_mut()
+                .find(|impact| impact.shard == node.shard)
+                .expect("every shard is listed");
+            impact.valid_before += before as usize;
+            impact.valid_after += after.is_ok() as usize;
+            match after {
+                Err(error) if before => newly_invalid.push((node.pubkey, error)),
+                Ok(_) if !before => newly_valid.push(node.pubkey),
+                _ => {}
+            }
+        }
+        for impact in &mut shards {
+            impact.pushed_below_minimum = impact.valid_before >= self.minimum_nodes
+                && impact.valid_after < self.minimum_nodes;
+        }
+
+        ConfigImpact {
+            newly_invalid,
+            newly_valid,
+            shards,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+    use crate::stake::StakeAmount;
+
+    #[test]
+    fn test_preview_raised_minimum_stake() {
+        let (mut validator, keys) = test_network(5);
+        validator.minimum_nodes = 3;
+        let shard = validator.nodes[&keys[0]].shard;
+        for (i, key) in keys.iter().enumerate() {
+            validator.nodes.get_mut(key).unwrap().stake_amount =
+                StakeAmount::from_meshx(100_000 + i as u64 * 50_000);
+        }
+        let before = validator.preview_config_change(&validator.policy.clone());
+        assert!(before.newly_invalid.is_empty());
+        assert!(before.shards_pushed_below_minimum().is_empty());
+
+        // Only the two best-staked nodes clear 240K
+        let mut raised = validator.policy.clone();
+        raised
+            .minimum_stake
+            .insert(shard, StakeAmount::from_meshx(240_000));
+        let impact = validator.preview_config_change(&raised);
+        let invalid: Vec<PublicKey> = impact.newly_invalid.iter().map(|(key, _)| *key).collect();
+        assert_eq!(invalid.len(), 3);
+        assert!(keys[..3].iter().all(|key| invalid.contains(key)));
+        assert!(impact
+            .newly_invalid
+            .iter()
+            .all(|(_, error)| *error == ValidationError::InsufficientStake));
+        assert_eq!(impact.shards_pushed_below_minimum(), vec![shard]);
+
+        // Nothing was applied
+        assert_ne!(validator.policy, raised);
+        assert!(validator.validate_node(&validator.nodes[&keys[0]]).is_ok());
+    }
+}
@@ -0,0 +1,73 @@
+// MeshX - The Immutable Global Device Mesh
+// Habitable-region checks on claimed locations
+// Copyright (c) 2025 MeshX Foundation
+
+use crate::boundaries::ring_contains;
+use crate::proof_of_presence::{MeshXNode, PopValidator, ValidationError};
+
+impl PopValidator {
+    // With `habitable_regions` configured, a claimed location must fall in
+    // one of them unless the node is allowlisted as offshore (platforms,
+    // ships)
+    pub(crate) fn check_habitable(&self, node: &MeshXNode) -> Result<(), ValidationError> {
+        let Some(regions) = &self.policy.habitable_regions else {
+            return Ok(());
+        };
+        if self.policy.offshore_allowlist.contains(&node.pubkey) {
+            return Ok(());
+        }
+        let point = [node.geo_location.longitude, node.geo_location.latitude];
+        if regions.iter().any(|ring| ring_contains(ring, point)) {
+            Ok(())
+        } else {
+            Err(ValidationError::UninhabitableLocation)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_node;
+
+    fn ring(points: &[[f64; 2]]) -> Vec<[f64; 2]> {
+        [points, &points[..1]].concat()
+    }
+
+    #[test]
+    fn test_open_ocean_claims_rejected() {
+        let mut validator = PopValidator::new(1);
+        let sydney = test_node(1, -33.9, 151.2, 100_000);
+        let mid_pacific = test_node(2, -10.0, -150.0, 100_000);
+        let north_pacific = test_node(3, 30.0, 170.0, 100_000);
+        let tahiti = test_node(4, -17.65, -149.43, 100_000);
+        assert_eq!(validator.check_habitable(&mid_pacific), Ok(()));
+
+        // Operator-supplied outlines: eastern Australia and Tahiti
+        validator.policy.habitable_regions = Some(vec![
+            ring(&[
+                [140.0, -39.0],
+                [154.0, -39.0],
+                [154.0, -10.0],
+                [140.0, -10.0],
+            ]),
+            ring(&[
+                [-149.7, -17.9],
+                [-149.1, -17.9],
+                [-149.1, -17.4],
+                [-149.7, -17.4],
+            ]),
+        ]);
+        assert_eq!(validator.check_habitable(&sydney), Ok(()));
+        assert_eq!(validator.check_habitable(&tahiti), Ok(()));
+        for ocean in [&mid_pacific, &north_pacific] {
+            assert_eq!(
+                validator.check_habitable(ocean),
+                Err(ValidationError::UninhabitableLocation)
+            );
+        }
+
+        validator.policy.offshore_allowlist.push(mid_pacific.pubkey);
+        assert_eq!(validator.check_habitable(&mid_pacific), Ok(()));
+    }
+}
@@ -0,0 +1,140 @@
+// MeshX - The Immutable Global Device Mesh
+// Liveness failure demotion
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+
+use crate::proof_of_presence::{hex_encode, PopValidator, ValidationError};
+
+impl PopValidator {
+    // Record the outcome of a liveness challenge. A pass resets the node's
+    // run of consecutive failures. Reaching `liveness_demotion_threshold`
+    // failures in a row demotes the node: it is frozen for the slash
+    // cooldown without losing stake, and its seat in the current validator
+    // set is refilled from the ranking of the epoch that set was selected
+    // for, or dropped when replacement is off or no candidate remains.
+    // Demotions are recorded apart from slashes. Returns whether the node
+    // was demoted.
+    pub fn record_liveness(
+        &mut self,
+        pubkey: &PublicKey,
+        passed: bool,
+    ) -> Result<bool, ValidationError> {
+        let shard = self
+            .nodes
+            .get(pubkey)
+            .ok_or(ValidationError::UnknownNode)?
+            .shard;
+        if passed {
+            self.liveness_failures.remove(pubkey);
+            return Ok(false);
+        }
+        let failures = self.liveness_failures.entry(*pubkey).or_default();
+        *failures += 1;
+        match self.liveness_demotion_threshold {
+            Some(threshold) if *failures >= threshold => {}
+            _ => return Ok(false),
+        }
+
+        self.liveness_failures.remove(pubkey);
+        self.demoted_at.insert(*pubkey, self.current_epoch);
+        if self
+            .replace_validator(shard, self.validator_set_epoch, pubkey)
+            .is_none()
+        {
+            self.current_validator_set.retain(|member| member != pubkey);
+        }
+        tracing::warn!(
+            node = %hex_encode(pubkey.as_bytes()),
+            "validator demoted after consecutive liveness failures"
+        );
+        Ok(true)
+    }
+
+    // A node demoted in epoch N sits out epochs N..=N+cooldown, like a
+    // slashed node
+    pub fn in_liveness_demotion(&self, pubkey: &PublicKey, epoch: u64) -> bool {
+        self.demoted_at.get(pubkey).is_some_and(|&demoted| {
+            epoch >= demoted && epoch - demoted <= self.slash_cooldown_epochs
+        })
+    }
+
+    // Consecutive liveness failures since the node's last pass or demotion
+    pub fn liveness_failures(&self, pubkey: &PublicKey) -> u32 {
+        self.liveness_failures.get(pubkey).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+    use crate::stake::StakeAmount;
+
+    #[test]
+    fn test_consecutive_liveness_failures_demote() {
+        let (mut validator, _) = test_network(6);
+        validator.validator_count = 3;
+        validator.mid_epoch_replacement = true;
+        validator.liveness_demotion_threshold = Some(3);
+        validator.current_epoch = 5;
+        let selected = validator.select_validators(5).unwrap();
+        validator.record_selection(5, &selected);
+        let flaky = selected[0];
+
+        // A pass between failures resets the run
+        assert_eq!(validator.record_liveness(&flaky, false), Ok(false));
+        assert_eq!(validator.record_liveness(&flaky, false), Ok(false));
+        assert_eq!(validator.liveness_failures(&flaky), 2);
+        assert_eq!(validator.record_liveness(&flaky, true), Ok(false));
+        assert_eq!(validator.liveness_failures(&flaky), 0);
+
+        assert_eq!(validator.record_liveness(&flaky, false), Ok(false));
+        assert_eq!(validator.record_liveness(&flaky, false), Ok(false));
+        assert_eq!(validator.record_liveness(&flaky, false), Ok(true));
+        assert!(validator.in_liveness_demotion(&flaky, 5));
+        assert!(!validator.in_slash_cooldown(&flaky, 5));
+        assert!(!validator.current_validator_set.contains(&flaky));
+        assert_eq!(validator.current_validator_set.len(), 3);
+        assert_eq!(
+            validator.nodes[&flaky].stake_amount,
+            StakeAmount::from_meshx(100_000)
+        );
+        assert!(!validator.select_validators(5).unwrap().contains(&flaky));
+    }
+
+    #[test]
+    fn test_liveness_demotion_disabled_by_default() {
+        let (mut validator, keys) = test_network(4);
+        for _ in 0..10 {
+            assert_eq!(validator.record_liveness(&keys[0], false), Ok(false));
+        }
+        assert_eq!(validator.liveness_failures(&keys[0]), 10);
+        assert!(!validator.in_liveness_demotion(&keys[0], 0));
+    }
+
+    #[test]
+    fn test_demotion_refills_from_the_sets_own_epoch() {
+        let (mut validator, _) = test_network(8);
+        validator.validator_count = 3;
+        validator.mid_epoch_replacement = true;
+        validator.liveness_demotion_threshold = Some(1);
+        let selected = validator.select_validators(5).unwrap();
+        validator.record_selection(5, &selected);
+
+        // The local epoch counter has moved on, but the set is epoch 5's
+        let next_ranked = |validator: &PopValidator, epoch| {
+            validator
+                .ranked_candidates(epoch)
+                .into_iter()
+                .map(|node| node.pubkey)
+                .find(|pubkey| !selected.contains(pubkey))
+                .unwrap()
+        };
+        let expected = next_ranked(&validator, 5);
+        assert_ne!(expected, next_ranked(&validator, 6));
+        validator.current_epoch = 6;
+        assert_eq!(validator.record_liveness(&selected[0], false), Ok(true));
+        assert_eq!(validator.current_validator_set[0], expected);
+    }
+}
@@ -3,9 +3,58 @@
 
 use clap::{Parser, Subcommand};
 use std::error::Error;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
+mod backoff;
+mod ban;
+mod bootstrap;
+mod boundaries;
+mod capacity;
+mod challenge;
+mod clock;
+mod compaction;
+mod contribution;
+mod dcap;
+mod delegation;
+mod diversity;
+mod drift;
+mod eligibility;
+mod explain;
+mod gossip;
+mod impact;
+mod landmass;
+mod liveness;
+mod metadata;
+mod metrics;
+mod migration;
+mod participation;
+mod partition;
+mod plausibility;
+mod policy_history;
+mod presence;
 mod proof_of_presence;
-use proof_of_presence::{MeshXNode, PopValidator, Shard};
+mod releases;
+mod replacement;
+mod report;
+mod reputation;
+mod resource_proof;
+mod rewards;
+mod routing;
+mod seed;
+mod shared;
+mod snapshot;
+mod stake;
+mod tee;
+mod tee_change;
+mod trajectory;
+mod transcript;
+mod unstake;
+#[cfg(feature = "otel")]
+mod otel;
+use presence::PresenceBundle;
+use report::{NetworkReport, NetworkState};
+use proof_of_presence::{MeshXNode, PopValidator, Shard, ValidationPolicy};
 
 #[derive(Parser)]
 #[command(name = "meshx")]
@@ -30,6 +79,16 @@ enum Commands {
         /// Continental shard
         #[arg(long)]
         shard: Option<String>,
+
+        /// Revoked attestation signers, one hex key per line; reloaded
+        /// whenever the file changes
+        #[arg(long)]
+        revocation_list: Option<PathBuf>,
+
+        /// Intel SGX Root CA certificate (PEM) to pin for quote signature
+        /// checks; defaults to the built-in copy
+        #[arg(long)]
+        sgx_root_ca: Option<PathBuf>,
     },
     
     /// Check node status
@@ -44,13 +103,136 @@ enum Commands {
     
     /// Show version information
     Version,
+
+    /// Verify a JSON presence bundle, exiting nonzero if it is invalid
+    VerifyProof {
+        /// Read the bundle from a file instead of stdin
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// Validation policy as JSON (defaults to the built-in policy)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Trusted anchor nodes as a JSON array; only their signed
+        /// measurements count towards locating the node
+        #[arg(long)]
+        anchors: PathBuf,
+    },
+
+    /// Report per-shard coverage and network health from exported state
+    Report {
+        /// Read the state from a file instead of stdin
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// Nodes a shard needs before it is no longer a coverage gap
+        #[arg(long, default_value_t = 1)]
+        min_nodes: usize,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+// Verify a presence bundle read from `input`, returning the JSON result to
+// print and the process exit code: 0 valid, 1 invalid, 2 unreadable input
+fn verify_proof(
+    mut input: impl Read,
+    policy: ValidationPolicy,
+    anchors: Vec<MeshXNode>,
+) -> (serde_json::Value, i32) {
+    let mut raw = String::new();
+    let bundle = match input
+        .read_to_string(&mut raw)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::from_str::<PresenceBundle>(&raw).map_err(|e| e.to_string()))
+    {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            let error = format!("invalid bundle: {}", e);
+            return (serde_json::json!({ "valid": false, "node": null, "error": error }), 2);
+        }
+    };
+
+    let mut validator = PopValidator::with_policy(1, policy);
+    for anchor in anchors {
+        validator.nodes.insert(anchor.pubkey, anchor);
+    }
+    let node = proof_of_presence::hex_encode(bundle.node.pubkey.as_bytes());
+    match validator.verify_presence_bundle(&bundle) {
+        Ok(()) => (serde_json::json!({ "valid": true, "node": node, "error": null }), 0),
+        Err(e) => (serde_json::json!({ "valid": false, "node": node, "error": e }), 1),
+    }
+}
+
+// Reload the revocation list at `path` if it changed since `loaded_at`,
+// returning the modification time now in force
+fn reload_revocations_if_changed(
+    validator: &mut PopValidator,
+    path: &Path,
+    loaded_at: Option<std::time::SystemTime>,
+) -> Option<std::time::SystemTime> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if modified.is_none() || modified == loaded_at {
+        return loaded_at;
+    }
+    match std::fs::File::open(path)
+        .map_err(|e| e.to_string())
+        .and_then(|file| validator.reload_revocation_list(file).map_err(|e| e.to_string()))
+    {
+        Ok(count) => {
+            println!("🔒 Loaded {} revoked signers from {}", count, path.display());
+            modified
+        }
+        Err(e) => {
+            println!("⚠️  Keeping previous revocation list: {}", e);
+            loaded_at
+        }
+    }
+}
+
+// Check SGX quote signatures against the Intel root at `root_ca`, or the
+// built-in copy
+#[cfg(feature = "sgx-dcap")]
+fn enable_sgx_verification(
+    validator: &mut PopValidator,
+    root_ca: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let pem = match root_ca {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => dcap::INTEL_SGX_ROOT_CA_PEM.to_string(),
+    };
+    validator.enable_sgx_dcap(&pem)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "sgx-dcap"))]
+fn enable_sgx_verification(
+    _validator: &mut PopValidator,
+    root_ca: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    if root_ca.is_some() {
+        return Err("--sgx-root-ca needs a build with the sgx-dcap feature".into());
+    }
+    tracing::warn!("built without sgx-dcap; SGX quote signatures are not verified");
+    Ok(())
+}
+
+// Build the health report for network state read from `input`
+fn network_report(mut input: impl Read, min_nodes: usize) -> Result<NetworkReport, Box<dyn Error>> {
+    let mut raw = String::new();
+    input.read_to_string(&mut raw)?;
+    let state: NetworkState = serde_json::from_str(&raw)?;
+    Ok(state.into_validator(min_nodes).network_report())
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
     
     match &cli.command {
-        Commands::Start { earn_mode, tee_type, shard } => {
+        Commands::Start { earn_mode, tee_type, shard, revocation_list, sgx_root_ca } => {
             println!("🚀 Starting MeshX node...");
             println!("   Mode: {}", if *earn_mode { "EARNING" } else { "CLIENT" });
             println!("   TEE: {}", tee_type);
@@ -81,7 +263,34 @@ fn main() -> Result<(), Box<dyn Error>> {
             println!("\nPress Ctrl+C to stop...");
             
             // In real implementation, this would start the actual node
+            let mut validator = PopValidator::new(1);
+            enable_sgx_verification(&mut validator, sgx_root_ca.as_deref())?;
+            let mut revocations_loaded_at = None;
             loop {
+                if let Some(path) = revocation_list {
+                    revocations_loaded_at =
+                        reload_revocations_if_changed(&mut validator, path, revocations_loaded_at);
+                }
+                validator.advance_epoch();
+                // Each shard selects its validators as it enters the epoch
+                for (shard, epoch) in validator.advance_shard_epochs() {
+                    match validator.selection_transcript(shard, epoch) {
+                        Ok(transcript) => {
+                            // Selection is drawn network-wide, so the first
+                            // shard into an epoch records it for every shard
+                            if epoch > validator.validator_set_epoch {
+                                if let Ok(selected) = validator.select_validators(epoch) {
+                                    validator.record_selection(epoch, &selected);
+                                }
+                            }
+                            let selected = transcript.selected.len();
+                            tracing::info!(?shard, epoch, selected, "shard entered epoch");
+                        }
+                        Err(error) => {
+                            tracing::warn!(?shard, epoch, ?error, "shard selection failed");
+                        }
+                    }
+                }
                 std::thread::sleep(std::time::Duration::from_secs(10));
             }
         }
@@ -99,6 +308,13 @@ fn main() -> Result<(), Box<dyn Error>> {
             println!("   Creating configuration...");
             println!("   Generating keys...");
             println!("   Testing TEE attestation...");
+            match PopValidator::new(1).expected_enclave_release() {
+                Some(release) => println!(
+                    "   Enclave release: {} ({})",
+                    release.version, release.build_date
+                ),
+                None => println!("   ⚠️  Expected enclave hash is not a known MeshX release"),
+            }
             println!("\n✅ Initialization complete!");
             println!("   Run 'meshx start' to begin");
         }
@@ -109,6 +325,34 @@ fn main() -> Result<(), Box<dyn Error>> {
             println!("Network: Testnet");
             println!("Build: December 2025");
         }
+        
+        Commands::VerifyProof { file, config, anchors } => {
+            let policy = match config {
+                Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+                None => ValidationPolicy::default(),
+            };
+            let anchors = serde_json::from_str(&std::fs::read_to_string(anchors)?)?;
+            let (result, code) = match file {
+                Some(path) => verify_proof(std::fs::File::open(path)?, policy, anchors),
+                None => verify_proof(std::io::stdin().lock(), policy, anchors),
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+            if code != 0 {
+                std::process::exit(code);
+            }
+        }
+        
+        Commands::Report { file, min_nodes, json } => {
+            let report = match file {
+                Some(path) => network_report(std::fs::File::open(path)?, *min_nodes)?,
+                None => network_report(std::io::stdin().lock(), *min_nodes)?,
+            };
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{}", report);
+            }
+        }
     }
     
     Ok(())
@@ -127,4 +371,50 @@ mod tests {
             _ => assert!(false, "Wrong command parsed"),
         }
     }
+
+    #[test]
+    fn test_verify_proof_from_stdin() {
+        let bundle = presence::tests::test_bundle();
+        let anchors = presence::tests::test_anchors;
+        let input = serde_json::to_vec(&bundle).unwrap();
+        let (result, code) = verify_proof(input.as_slice(), ValidationPolicy::default(), anchors());
+        assert_eq!(code, 0);
+        assert_eq!(result["valid"], true);
+
+        // Without trusted anchors the bundle can't place the node
+        let (result, code) = verify_proof(input.as_slice(), ValidationPolicy::default(), Vec::new());
+        assert_eq!(code, 1);
+        assert_eq!(result["error"], "InsufficientLatencyData");
+
+        let mut tampered = bundle;
+        tampered.node.geo_location.latitude = -33.9;
+        let input = serde_json::to_vec(&tampered).unwrap();
+        let (result, code) = verify_proof(input.as_slice(), ValidationPolicy::default(), anchors());
+        assert_eq!(code, 1);
+        assert_eq!(result["error"], "InvalidSignature");
+
+        let (_, code) = verify_proof(&b"not json"[..], ValidationPolicy::default(), anchors());
+        assert_eq!(code, 2);
+    }
+
+    #[test]
+    fn test_report_on_synthetic_network() {
+        let (validator, _) = proof_of_presence::tests::test_network(4);
+        let state = NetworkState {
+            nodes: validator.nodes.values().cloned().collect(),
+            measurements: Vec::new(),
+        };
+        let input = serde_json::to_vec(&state).unwrap();
+        let report = network_report(input.as_slice(), 1).unwrap();
+
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+        let shards = json["shards"].as_array().unwrap();
+        assert_eq!(shards.len(), 7);
+        let europe = shards.iter().find(|s| s["shard"] == "Europe").unwrap();
+        assert_eq!(europe["node_count"], 4);
+        assert_eq!(europe["coverage_gap"], false);
+        // Without measurements every node is its own partition
+        assert_eq!(json["partitioned"], true);
+        assert!(report.to_string().contains("Europe"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,107 @@
+// MeshX - The Immutable Global Device Mesh
+// Operator node metadata
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{MeshXNode, PopValidator, ValidationError};
+
+// Size limits on the labels operators attach to nodes, in bytes of UTF-8
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataLimits {
+    pub max_key_len: usize,
+    pub max_value_len: usize,
+    pub max_total_bytes: usize, // Sum over every key and value
+}
+
+impl Default for MetadataLimits {
+    fn default() -> Self {
+        Self {
+            max_key_len: 64,
+            max_value_len: 256,
+            max_total_bytes: 4096,
+        }
+    }
+}
+
+impl PopValidator {
+    // Metadata plays no part in selection, but every validator stores it,
+    // so it is bounded like any other registration field
+    pub(crate) fn verify_metadata(&self, node: &MeshXNode) -> Result<(), ValidationError> {
+        let limits = &self.policy.metadata_limits;
+        let mut total = 0usize;
+        for (key, value) in &node.metadata {
+            if key.len() > limits.max_key_len || value.len() > limits.max_value_len {
+                return Err(ValidationError::MetadataTooLarge);
+            }
+            total += key.len() + value.len();
+        }
+        if total > limits.max_total_bytes {
+            return Err(ValidationError::MetadataTooLarge);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+
+    #[test]
+    fn test_oversized_metadata_rejected() {
+        let (validator, keys) = test_network(4);
+        let mut node = validator.nodes[&keys[0]].clone();
+        node.metadata
+            .insert("datacenter".to_string(), "fra-2".to_string());
+        node.metadata
+            .insert("hardware".to_string(), "epyc-9654".to_string());
+        assert!(validator.validate_node(&node).is_ok());
+
+        let mut long_value = node.clone();
+        long_value
+            .metadata
+            .insert("operator".to_string(), "x".repeat(257));
+        assert_eq!(
+            validator.validate_node(&long_value).err(),
+            Some(ValidationError::MetadataTooLarge)
+        );
+
+        let mut long_key = node.clone();
+        long_key.metadata.insert("k".repeat(65), String::new());
+        assert_eq!(
+            validator.validate_node(&long_key).err(),
+            Some(ValidationError::MetadataTooLarge)
+        );
+
+        // Each entry fits, but together they exceed the total
+        let mut many = node;
+        for i in 0..20 {
+            many.metadata
+                .insert(format!("label-{}", i), "y".repeat(250));
+        }
+        assert_eq!(
+            validator.validate_node(&many).err(),
+            Some(ValidationError::MetadataTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_metadata_does_not_affect_selection() {
+        let (mut validator, keys) = test_network(6);
+        validator.validator_count = 3;
+        let selected = validator.select_validators(5).unwrap();
+        let fingerprint = validator.state_fingerprint();
+
+        for (i, key) in keys.iter().enumerate() {
+            validator
+                .nodes
+                .get_mut(key)
+                .unwrap()
+                .metadata
+                .insert("rack".to_string(), format!("r{}", i));
+        }
+        assert_eq!(validator.select_validators(5).unwrap(), selected);
+        assert_eq!(validator.state_fingerprint(), fingerprint);
+    }
+}
@@ -0,0 +1,107 @@
+// MeshX - The Immutable Global Device Mesh
+// Validation metrics history
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::proof_of_presence::{PopValidator, Shard, ValidationErrorKind};
+use crate::stake::StakeAmount;
+
+// Registry health as of entering an epoch
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    pub epoch: u64,
+    pub node_count: usize,
+    pub shard_stake: HashMap<Shard, StakeAmount>,
+    pub validation_failures: HashMap<ValidationErrorKind, usize>, // Nodes failing each check
+}
+
+impl PopValidator {
+    // Snapshots recorded on entering each epoch, oldest first. Empty unless
+    // `metrics_history_len` is set.
+    pub fn metrics_timeseries(&self) -> Vec<MetricsSnapshot> {
+        self.metrics_history.iter().cloned().collect()
+    }
+
+    // Append a snapshot for the current epoch, dropping the oldest beyond
+    // `metrics_history_len`. Validates the whole registry, so only runs
+    // when history is enabled.
+    pub(crate) fn record_metrics_snapshot(&mut self) {
+        if self.metrics_history_len == 0 {
+            return;
+        }
+        let snapshot = MetricsSnapshot {
+            epoch: self.current_epoch,
+            node_count: self.nodes.len(),
+            shard_stake: Shard::all()
+                .into_iter()
+                .map(|shard| (shard, self.shard_stats(shard).total_stake))
+                .collect(),
+            validation_failures: self
+                .scan_network()
+                .into_iter()
+                .map(|(kind, nodes)| (kind, nodes.len()))
+                .collect(),
+        };
+        self.metrics_history.push_back(snapshot);
+        while self.metrics_history.len() > self.metrics_history_len {
+            self.metrics_history.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::proof_of_presence::tests::test_network;
+    use crate::proof_of_presence::ValidationError;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_metrics_timeseries_bounded() {
+        let (mut validator, keys) = test_network(4);
+        let clock = Arc::new(ManualClock::new(10 * 3600 + 60));
+        for node in validator.nodes.values_mut() {
+            node.tee_attestation.timestamp = 10 * 3600;
+        }
+        validator.policy.max_attestation_age_secs = 24 * 3600;
+        validator.set_clock(clock.clone());
+        validator.advance_epoch();
+        assert!(validator.metrics_timeseries().is_empty());
+
+        validator.metrics_history_len = 3;
+        for _ in 0..2 {
+            clock.advance(3600);
+            validator.advance_epoch();
+        }
+        // Re-checking within an epoch records nothing new
+        validator.advance_epoch();
+        let series = validator.metrics_timeseries();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[1].epoch, 12);
+        assert_eq!(series[1].node_count, 4);
+        assert_eq!(
+            series[1].shard_stake[&Shard::Europe],
+            StakeAmount::from_meshx(400_000)
+        );
+        assert!(series[1].validation_failures.is_empty());
+
+        // A node losing its anchors shows up as a failure trend
+        validator.latency_matrix.retain(|(_, to), _| *to != keys[0]);
+        for _ in 0..2 {
+            clock.advance(3600);
+            validator.advance_epoch();
+        }
+        let series = validator.metrics_timeseries();
+        assert_eq!(
+            series.iter().map(|s| s.epoch).collect::<Vec<_>>(),
+            vec![12, 13, 14]
+        );
+        assert_eq!(
+            series[2].validation_failures[&ValidationError::InsufficientLatencyData],
+            1
+        );
+    }
+}
@@ -0,0 +1,254 @@
+// MeshX - The Immutable Global Device Mesh
+// Shard migration
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{GeoLocation, PopValidator, Shard, ValidationError};
+use crate::stake::StakeAmount;
+
+// A migration accepted before the node met its new shard's minimum stake.
+// The node is excluded from selection until it tops up or the window
+// expires and the migration is reverted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvisionalMigration {
+    pub previous_shard: Shard,
+    pub previous_location: GeoLocation,
+    pub deadline_epoch: u64, // Last epoch in which a top-up promotes the node
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MigrationStatus {
+    Completed,
+    Provisional { deadline_epoch: u64 },
+}
+
+impl PopValidator {
+    // Move a node to a new location and the shard it falls in. The new
+    // location is checked against latency at the node's next validation.
+    // A node short of the new shard's minimum stake is rejected, unless
+    // `migration_topup_grace_epochs` is set, in which case it is accepted
+    // provisionally. Stake already queued for unstaking doesn't count toward
+    // the minimum, so a node can't migrate and then unstake below it.
+    pub fn migrate_shard(
+        &mut self,
+        pubkey: &PublicKey,
+        location: GeoLocation,
+    ) -> Result<MigrationStatus, ValidationError> {
+        let shard = self.shard_for_location(&location);
+        let required = self.policy.minimum_stake_for(&shard);
+        let current_epoch = self.current_epoch;
+        let grace = self.migration_topup_grace_epochs;
+        let remaining_stake = self
+            .stake_after_unstakes(pubkey)
+            .ok_or(ValidationError::UnknownNode)?;
+        let node = self
+            .nodes
+            .get_mut(pubkey)
+            .ok_or(ValidationError::UnknownNode)?;
+
+        let status = if remaining_stake >= required {
+            MigrationStatus::Completed
+        } else if let (Some(grace), true) = (grace, node.stake_amount < required) {
+            MigrationStatus::Provisional {
+                deadline_epoch: current_epoch + grace,
+            }
+        } else {
+            return Err(ValidationError::InsufficientStake);
+        };
+
+        // A provisional node migrating again keeps its original fallback
+        let (previous_shard, previous_location) = match self.provisional_migrations.remove(pubkey) {
+            Some(pending) => (pending.previous_shard, pending.previous_location),
+            None => (node.shard, node.geo_location.clone()),
+        };
+        node.shard = shard;
+        node.geo_location = location;
+        if let MigrationStatus::Provisional { deadline_epoch } = status {
+            self.provisional_migrations.insert(
+                *pubkey,
+                ProvisionalMigration {
+                    previous_shard,
+                    previous_location,
+                    deadline_epoch,
+                },
+            );
+        }
+        Ok(status)
+    }
+
+    // Add stake to a node, promoting a provisional migration once the new
+    // shard's minimum is met. Returns the node's new stake.
+    pub fn top_up_stake(
+        &mut self,
+        pubkey: &PublicKey,
+        amount: StakeAmount,
+    ) -> Result<StakeAmount, ValidationError> {
+        let node = self
+            .nodes
+            .get_mut(pubkey)
+            .ok_or(ValidationError::UnknownNode)?;
+        node.stake_amount = node.stake_amount.saturating_add(amount);
+        let stake = node.stake_amount;
+        self.settle_provisional_migrations(self.current_epoch);
+        Ok(stake)
+    }
+
+    // Promote provisional nodes that now meet their shard minimum and revert
+    // those whose grace window ended before `epoch`. As with migrating,
+    // stake queued for unstaking doesn't count toward the minimum.
+    pub fn settle_provisional_migrations(&mut self, epoch: u64) {
+        let pubkeys: Vec<PublicKey> = self.provisional_migrations.keys().copied().collect();
+        for pubkey in pubkeys {
+            let Some(remaining_stake) = self.stake_after_unstakes(&pubkey) else {
+                self.provisional_migrations.remove(&pubkey);
+                continue;
+            };
+            let node = self.nodes.get_mut(&pubkey).expect("node exists");
+            let pending = &self.provisional_migrations[&pubkey];
+            if remaining_stake >= self.policy.minimum_stake_for(&node.shard) {
+                self.provisional_migrations.remove(&pubkey);
+            } else if epoch > pending.deadline_epoch {
+                let pending = self
+                    .provisional_migrations
+                    .remove(&pubkey)
+                    .expect("pending migration exists");
+                node.shard = pending.previous_shard;
+                node.geo_location = pending.previous_location;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+
+    fn oceania() -> GeoLocation {
+        GeoLocation {
+            latitude: -33.9,
+            longitude: 151.2,
+            accuracy_meters: 20_000_000.0,
+        }
+    }
+
+    fn tokyo() -> GeoLocation {
+        GeoLocation {
+            latitude: 35.7,
+            longitude: 139.7,
+            accuracy_meters: 20_000_000.0,
+        }
+    }
+
+    // Europe node with 60K stake: enough for Oceania, short of Asia's 100K
+    fn migrating_network() -> (PopValidator, Vec<PublicKey>) {
+        let (mut validator, keys) = test_network(4);
+        validator.nodes.get_mut(&keys[0]).unwrap().stake_amount = StakeAmount::from_meshx(60_000);
+        (validator, keys)
+    }
+
+    #[test]
+    fn test_migration_without_grace() {
+        let (mut validator, keys) = migrating_network();
+        assert_eq!(
+            validator.migrate_shard(&keys[0], tokyo()),
+            Err(ValidationError::InsufficientStake)
+        );
+        assert_eq!(validator.nodes[&keys[0]].shard, Shard::Europe);
+        assert_eq!(
+            validator.migrate_shard(&keys[0], oceania()),
+            Ok(MigrationStatus::Completed)
+        );
+        assert_eq!(validator.nodes[&keys[0]].shard, Shard::Oceania);
+    }
+
+    #[test]
+    fn test_migration_counts_pending_unstakes() {
+        let (mut validator, keys) = migrating_network();
+        validator.migration_topup_grace_epochs = Some(2);
+        // 60K covers Oceania's 50K minimum only until 15K unstakes
+        validator
+            .request_unstake(&keys[0], StakeAmount::from_meshx(15_000))
+            .unwrap();
+        assert_eq!(
+            validator.migrate_shard(&keys[0], oceania()),
+            Err(ValidationError::InsufficientStake)
+        );
+        assert_eq!(validator.nodes[&keys[0]].shard, Shard::Europe);
+
+        let (mut validator, keys) = migrating_network();
+        validator
+            .request_unstake(&keys[0], StakeAmount::from_meshx(5_000))
+            .unwrap();
+        assert_eq!(
+            validator.migrate_shard(&keys[0], oceania()),
+            Ok(MigrationStatus::Completed)
+        );
+        validator.settle_unstakes(validator.current_epoch + 7);
+        assert_eq!(
+            validator.nodes[&keys[0]].stake_amount,
+            StakeAmount::from_meshx(55_000)
+        );
+        assert!(validator.pending_unstakes.is_empty());
+    }
+
+    #[test]
+    fn test_provisional_migration_promoted_by_top_up() {
+        let (mut validator, keys) = migrating_network();
+        validator.migration_topup_grace_epochs = Some(2);
+        assert_eq!(
+            validator.migrate_shard(&keys[0], tokyo()),
+            Ok(MigrationStatus::Provisional { deadline_epoch: 2 })
+        );
+        assert_eq!(validator.nodes[&keys[0]].shard, Shard::Asia);
+        assert!(!validator.select_validators(0).unwrap().contains(&keys[0]));
+
+        validator
+            .top_up_stake(&keys[0], StakeAmount::from_meshx(30_000))
+            .unwrap();
+        assert!(validator.provisional_migrations.contains_key(&keys[0]));
+        validator
+            .top_up_stake(&keys[0], StakeAmount::from_meshx(10_000))
+            .unwrap();
+        assert!(validator.provisional_migrations.is_empty());
+        assert_eq!(validator.nodes[&keys[0]].shard, Shard::Asia);
+    }
+
+    #[test]
+    fn test_pending_unstake_blocks_promotion() {
+        let (mut validator, keys) = migrating_network();
+        validator.migration_topup_grace_epochs = Some(2);
+        validator.migrate_shard(&keys[0], tokyo()).unwrap();
+        validator
+            .request_unstake(&keys[0], StakeAmount::from_meshx(50_000))
+            .unwrap();
+
+        // 100K staked reaches Asia's minimum, but only 50K will remain
+        validator
+            .top_up_stake(&keys[0], StakeAmount::from_meshx(40_000))
+            .unwrap();
+        assert!(validator.provisional_migrations.contains_key(&keys[0]));
+
+        // Unpromoted when the window closes, so the node goes back
+        validator.settle_provisional_migrations(3);
+        assert_eq!(validator.nodes[&keys[0]].shard, Shard::Europe);
+    }
+
+    #[test]
+    fn test_provisional_migration_reverts_on_expiry() {
+        let (mut validator, keys) = migrating_network();
+        let original = validator.nodes[&keys[0]].geo_location.clone();
+        validator.migration_topup_grace_epochs = Some(2);
+        validator.migrate_shard(&keys[0], tokyo()).unwrap();
+
+        validator.settle_provisional_migrations(2);
+        assert_eq!(validator.nodes[&keys[0]].shard, Shard::Asia);
+
+        validator.settle_provisional_migrations(3);
+        assert!(validator.provisional_migrations.is_empty());
+        assert_eq!(validator.nodes[&keys[0]].shard, Shard::Europe);
+        assert_eq!(validator.nodes[&keys[0]].geo_location, original);
+    }
+}
@@ -0,0 +1,163 @@
+// MeshX - The Immutable Global Device Mesh
+// OpenTelemetry resource attribute export
+// Copyright (c) 2025 MeshX Foundation
+
+use opentelemetry::KeyValue;
+
+use crate::proof_of_presence::{hex_encode, GeoLocation, MeshXNode, NodeResources, Shard, TeeType};
+
+// OTel semantic-convention keys are used where one exists (service.*, geo.*);
+// everything MeshX-specific lives under the `meshx.` namespace.
+
+impl MeshXNode {
+    // Resource attributes describing this node, for correlating MeshX nodes
+    // with an operator's existing telemetry
+    pub fn to_otel_attributes(&self) -> Vec<KeyValue> {
+        let mut attributes = vec![
+            KeyValue::new("service.name", "meshx-node"),
+            KeyValue::new("service.instance.id", hex_encode(self.pubkey.as_bytes())),
+            KeyValue::new("meshx.shard", format!("{:?}", self.shard)),
+            KeyValue::new("meshx.stake_amount", self.stake_amount.as_meshx_f64()),
+            KeyValue::new("meshx.reputation_score", self.reputation_score as f64),
+            KeyValue::new(
+                "meshx.tee.type",
+                tee_type_name(&self.tee_attestation.tee_type),
+            ),
+        ];
+        attributes.extend(self.geo_location.to_otel_attributes());
+        attributes.push(KeyValue::new(
+            "geo.continent.code",
+            continent_code(&self.shard),
+        ));
+        attributes.extend(self.resources.to_otel_attributes());
+        attributes
+    }
+}
+
+impl GeoLocation {
+    pub fn to_otel_attributes(&self) -> Vec<KeyValue> {
+        vec![
+            KeyValue::new("geo.location.lat", self.latitude),
+            KeyValue::new("geo.location.lon", self.longitude),
+            KeyValue::new("meshx.geo.accuracy_meters", self.accuracy_meters as f64),
+        ]
+    }
+}
+
+impl NodeResources {
+    pub fn to_otel_attributes(&self) -> Vec<KeyValue> {
+        let mut attributes = vec![
+            KeyValue::new("meshx.resources.cpu_cores", self.cpu_cores as i64),
+            KeyValue::new("meshx.resources.ram_gb", self.ram_gb as i64),
+            KeyValue::new("meshx.resources.storage_gb", self.storage_gb as i64),
+            KeyValue::new("meshx.resources.bandwidth_mbps", self.bandwidth_mbps as i64),
+        ];
+        if let Some(gpu_memory_gb) = self.gpu_memory_gb {
+            attributes.push(KeyValue::new(
+                "meshx.resources.gpu_memory_gb",
+                gpu_memory_gb as i64,
+            ));
+        }
+        attributes
+    }
+}
+
+// Two-letter continent codes from the geo.continent.code convention
+fn continent_code(shard: &Shard) -> &'static str {
+    match shard {
+        Shard::NorthAmerica => "NA",
+        Shard::Europe => "EU",
+        Shard::Asia => "AS",
+        Shard::SouthAmerica => "SA",
+        Shard::Africa => "AF",
+        Shard::Oceania => "OC",
+        Shard::Antarctica => "AN",
+    }
+}
+
+fn tee_type_name(tee_type: &TeeType) -> &'static str {
+    match tee_type {
+        TeeType::IntelSgx => "intel_sgx",
+        TeeType::ArmTrustZone => "arm_trustzone",
+        TeeType::AppleSecureEnclave => "apple_secure_enclave",
+        TeeType::AmdSev => "amd_sev",
+        TeeType::Custom(_) => "custom",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::TeeAttestation;
+    use crate::stake::StakeAmount;
+    use ed25519_dalek::{PublicKey, SecretKey};
+    use opentelemetry::{Key, Value};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_node_otel_attributes() {
+        let pubkey = PublicKey::from(&SecretKey::from_bytes(&[7; 32]).unwrap());
+        let node = MeshXNode {
+            pubkey,
+            tee_attestation: TeeAttestation {
+                tee_type: TeeType::AmdSev,
+                enclave_hash: [0x42; 32],
+                signer_pubkey: pubkey,
+                timestamp: 0,
+                quote: vec![1],
+            },
+            geo_location: GeoLocation {
+                latitude: 51.5074,
+                longitude: -0.1278,
+                accuracy_meters: 1000.0,
+            },
+            shard: Shard::Europe,
+            stake_amount: StakeAmount::from_meshx(150_000),
+            reputation_score: 0.5,
+            resources: NodeResources {
+                cpu_cores: 8,
+                ram_gb: 16,
+                storage_gb: 500,
+                bandwidth_mbps: 100,
+                gpu_memory_gb: Some(24),
+            },
+            metadata: BTreeMap::new(),
+        };
+
+        let attributes: std::collections::HashMap<Key, Value> = node
+            .to_otel_attributes()
+            .into_iter()
+            .map(|kv| (kv.key, kv.value))
+            .collect();
+
+        let expected = [
+            ("service.name", Value::from("meshx-node")),
+            (
+                "service.instance.id",
+                Value::from(hex_encode(pubkey.as_bytes())),
+            ),
+            ("meshx.shard", Value::from("Europe")),
+            ("meshx.stake_amount", Value::from(150_000.0)),
+            ("meshx.reputation_score", Value::from(0.5)),
+            ("meshx.tee.type", Value::from("amd_sev")),
+            ("geo.location.lat", Value::from(51.5074)),
+            ("geo.location.lon", Value::from(-0.1278)),
+            ("meshx.geo.accuracy_meters", Value::from(1000.0)),
+            ("geo.continent.code", Value::from("EU")),
+            ("meshx.resources.cpu_cores", Value::from(8i64)),
+            ("meshx.resources.ram_gb", Value::from(16i64)),
+            ("meshx.resources.storage_gb", Value::from(500i64)),
+            ("meshx.resources.bandwidth_mbps", Value::from(100i64)),
+            ("meshx.resources.gpu_memory_gb", Value::from(24i64)),
+        ];
+        assert_eq!(attributes.len(), expected.len());
+        for (key, value) in expected {
+            assert_eq!(
+                attributes.get(&Key::from(key)),
+                Some(&value),
+                "attribute {}",
+                key
+            );
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// MeshX - The Immutable Global Device Mesh
+// Selection participation history
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use std::collections::VecDeque;
+
+use crate::proof_of_presence::PopValidator;
+
+// Per-node (epoch, selected) entries, oldest first
+pub type SelectionHistory = VecDeque<(u64, bool)>;
+
+impl PopValidator {
+    // Record an epoch's selection outcome for every node eligible in it.
+    // Each node keeps at most `selection_history_epochs` entries, dropping
+    // the oldest first. The selection becomes the current validator set, and
+    // its aggregated VRF outputs are kept for checking the epoch seed chain.
+    pub fn record_selection(&mut self, epoch: u64, selected: &[PublicKey]) {
+        let limit = self.selection_history_epochs;
+        let eligible: Vec<PublicKey> = self
+            .selection_candidates(epoch)
+            .into_iter()
+            .map(|node| node.pubkey)
+            .collect();
+        for pubkey in eligible {
+            let history = self.selection_history.entry(pubkey).or_default();
+            history.push_back((epoch, selected.contains(&pubkey)));
+            while history.len() > limit {
+                history.pop_front();
+            }
+        }
+        self.selection_history
+            .retain(|pubkey, history| !history.is_empty() && self.nodes.contains_key(pubkey));
+        self.current_validator_set = selected.to_vec();
+        self.validator_set_epoch = epoch;
+
+        let aggregate = self.aggregate_vrf_outputs(epoch, selected);
+        self.vrf_aggregates.insert(epoch, aggregate);
+        while self.vrf_aggregates.len() > limit {
+            self.vrf_aggregates.pop_first();
+        }
+    }
+
+    // Share of the node's last `window_epochs` eligible epochs in which it was
+    // selected. The window is capped by the retained history; 0 when the
+    // node has no recorded eligible epochs.
+    pub fn participation_rate(&self, pubkey: &PublicKey, window_epochs: usize) -> f32 {
+        let Some(history) = self.selection_history.get(pubkey) else {
+            return 0.0;
+        };
+        let window: Vec<bool> = history
+            .iter()
+            .rev()
+            .take(window_epochs)
+            .map(|(_, selected)| *selected)
+            .collect();
+        if window.is_empty() {
+            return 0.0;
+        }
+        window.iter().filter(|selected| **selected).count() as f32 / window.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::proof_of_presence::tests::test_network;
+    use crate::stake::StakeAmount;
+
+    #[test]
+    fn test_participation_rate() {
+        let (mut validator, keys) = test_network(2);
+        validator.selection_history_epochs = 10;
+        for epoch in 0..24 {
+            let selected = if epoch >= 15 && epoch % 3 == 0 {
+                vec![keys[0]]
+            } else {
+                vec![keys[1]]
+            };
+            validator.record_selection(epoch, &selected);
+        }
+
+        // Selected in epochs 15, 18 and 21 of the last ten (14..24)
+        assert_eq!(validator.selection_history[&keys[0]].len(), 10);
+        assert_eq!(validator.participation_rate(&keys[0], 10), 0.3);
+        assert_eq!(validator.participation_rate(&keys[0], 100), 0.3);
+        assert_eq!(validator.participation_rate(&keys[0], 2), 0.0);
+        assert_eq!(validator.participation_rate(&keys[1], 10), 0.7);
+
+        // Epochs spent in slash cooldown aren't eligible and aren't counted
+        validator.current_epoch = 24;
+        validator.slash_node(&keys[1], StakeAmount::ZERO).unwrap();
+        validator.record_selection(24, &[keys[0]]);
+        assert_eq!(
+            validator.selection_history[&keys[1]].back(),
+            Some(&(23, true))
+        );
+        assert_eq!(validator.participation_rate(&keys[0], 1), 1.0);
+    }
+}
@@ -0,0 +1,113 @@
+// MeshX - The Immutable Global Device Mesh
+// Partition recovery simulation
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::proof_of_presence::{PopValidator, ValidationError};
+
+// Epochs simulated after healing before giving up on convergence
+const MAX_RECOVERY_EPOCHS: u64 = 100;
+
+// How the validator set fared through a simulated partition and its healing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecoveryReport {
+    pub components: usize,    // Latency graph components while partitioned
+    pub diverged_epochs: u64, // Partitioned epochs whose selection differed from the baseline
+    pub epochs_to_converge: Option<u64>, // After healing; None if not within MAX_RECOVERY_EPOCHS
+}
+
+impl PopValidator {
+    // Split the latency graph into `partition`, dropping every edge between
+    // nodes of different components, and step epochs alongside an unsplit
+    // baseline. After `heal_after_epochs` the cut edges are restored and
+    // stepping continues until both select the same validator set. Nodes
+    // outside every component keep their edges. Neither run touches this
+    // validator's state.
+    pub fn simulate_partition_recovery(
+        &self,
+        partition: &[HashSet<PublicKey>],
+        heal_after_epochs: u64,
+    ) -> RecoveryReport {
+        let component = |pubkey: &PublicKey| partition.iter().position(|set| set.contains(pubkey));
+        let mut baseline = self.clone();
+        let mut partitioned = self.clone();
+        partitioned
+            .latency_matrix
+            .retain(|(from, to), _| match (component(from), component(to)) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            });
+        partitioned
+            .latency_timestamps
+            .retain(|edge, _| partitioned.latency_matrix.contains_key(edge));
+
+        let mut report = RecoveryReport {
+            components: partitioned.detect_partitions().len(),
+            diverged_epochs: 0,
+            epochs_to_converge: None,
+        };
+        for step in 1..=heal_after_epochs + MAX_RECOVERY_EPOCHS {
+            if step == heal_after_epochs + 1 {
+                partitioned.latency_matrix = self.latency_matrix.clone();
+                partitioned.latency_timestamps = self.latency_timestamps.clone();
+            }
+            let epoch = self.current_epoch + step;
+            let converged = baseline.simulate_epoch(epoch) == partitioned.simulate_epoch(epoch);
+            if step <= heal_after_epochs {
+                if !converged {
+                    report.diverged_epochs += 1;
+                }
+            } else if converged {
+                report.epochs_to_converge = Some(step - heal_after_epochs);
+                break;
+            }
+        }
+        report
+    }
+
+    // Move to `epoch` and record its selection, returning it sorted
+    fn simulate_epoch(&mut self, epoch: u64) -> Result<Vec<PublicKey>, ValidationError> {
+        self.enter_epoch(epoch);
+        let mut selected = self.select_validators(epoch)?;
+        self.record_selection(epoch, &selected);
+        selected.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        Ok(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+
+    #[test]
+    fn test_two_way_partition_converges_after_healing() {
+        let (validator, keys) = test_network(6);
+        let partition = [
+            keys[..3].iter().copied().collect::<HashSet<_>>(),
+            keys[3..].iter().copied().collect::<HashSet<_>>(),
+        ];
+
+        // Each side keeps two latency anchors per node, below the three
+        // triangulation needs, so neither side can field validators
+        let report = validator.simulate_partition_recovery(&partition, 3);
+        assert_eq!(report.components, 2);
+        assert_eq!(report.diverged_epochs, 3);
+        let converged = report.epochs_to_converge.unwrap();
+        assert!((1..=MAX_RECOVERY_EPOCHS).contains(&converged));
+
+        // The simulation leaves the real latency graph alone
+        assert_eq!(validator.detect_partitions().len(), 1);
+        assert_eq!(validator.latency_matrix.len(), 30);
+
+        // A partition that cuts no edges never diverges
+        let whole = [keys.iter().copied().collect::<HashSet<_>>()];
+        let report = validator.simulate_partition_recovery(&whole, 2);
+        assert_eq!(report.components, 1);
+        assert_eq!(report.diverged_epochs, 0);
+        assert_eq!(report.epochs_to_converge, Some(1));
+    }
+}
@@ -0,0 +1,118 @@
+// MeshX - The Immutable Global Device Mesh
+// Regional resource plausibility
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{MeshXNode, PopValidator, Shard};
+
+// Upper bounds on what nodes in a shard are expected to report. Exceeding
+// one is unusual rather than invalid; unset bounds aren't checked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionalExpectation {
+    pub shard: Shard,
+    pub max_bandwidth_mbps: Option<u32>,
+    pub max_cpu_cores: Option<u32>,
+    pub max_storage_gb: Option<u64>,
+}
+
+impl RegionalExpectation {
+    // Antarctic stations share satellite links; more than 1 Gbps is unusual
+    pub fn defaults() -> Vec<Self> {
+        vec![Self {
+            shard: Shard::Antarctica,
+            max_bandwidth_mbps: Some(1_000),
+            max_cpu_cores: None,
+            max_storage_gb: None,
+        }]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PlausibilityWarning {
+    Bandwidth {
+        claimed_mbps: u32,
+        expected_max_mbps: u32,
+    },
+    Cpu {
+        claimed_cores: u32,
+        expected_max_cores: u32,
+    },
+    Storage {
+        claimed_gb: u64,
+        expected_max_gb: u64,
+    },
+}
+
+impl PopValidator {
+    // Resource claims that are unusual for the node's shard. These are
+    // signals for reputation or manual review, never grounds for rejection.
+    pub fn plausibility_check(&self, node: &MeshXNode) -> Vec<PlausibilityWarning> {
+        let resources = &node.resources;
+        let mut warnings = Vec::new();
+        for expectation in self
+            .resource_expectations
+            .iter()
+            .filter(|expectation| expectation.shard == node.shard)
+        {
+            if let Some(max) = expectation.max_bandwidth_mbps {
+                if resources.bandwidth_mbps > max {
+                    warnings.push(PlausibilityWarning::Bandwidth {
+                        claimed_mbps: resources.bandwidth_mbps,
+                        expected_max_mbps: max,
+                    });
+                }
+            }
+            if let Some(max) = expectation.max_cpu_cores {
+                if resources.cpu_cores > max {
+                    warnings.push(PlausibilityWarning::Cpu {
+                        claimed_cores: resources.cpu_cores,
+                        expected_max_cores: max,
+                    });
+                }
+            }
+            if let Some(max) = expectation.max_storage_gb {
+                if resources.storage_gb > max {
+                    warnings.push(PlausibilityWarning::Storage {
+                        claimed_gb: resources.storage_gb,
+                        expected_max_gb: max,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_node;
+
+    #[test]
+    fn test_antarctic_bandwidth_claims() {
+        let mut validator = PopValidator::new(1);
+        let mut node = test_node(1, -77.8, 166.7, 10_000);
+        assert_eq!(node.shard, Shard::Antarctica);
+
+        node.resources.bandwidth_mbps = 500;
+        assert!(validator.plausibility_check(&node).is_empty());
+
+        node.resources.bandwidth_mbps = 1_000_000;
+        assert_eq!(
+            validator.plausibility_check(&node),
+            vec![PlausibilityWarning::Bandwidth {
+                claimed_mbps: 1_000_000,
+                expected_max_mbps: 1_000,
+            }]
+        );
+
+        // The same claim elsewhere is unremarkable
+        let mut european = test_node(2, 50.0, 10.0, 100_000);
+        european.resources.bandwidth_mbps = 1_000_000;
+        assert!(validator.plausibility_check(&european).is_empty());
+
+        validator.resource_expectations.clear();
+        assert!(validator.plausibility_check(&node).is_empty());
+    }
+}
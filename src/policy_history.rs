@@ -0,0 +1,182 @@
+// MeshX - The Immutable Global Device Mesh
+// Historical validation policies for retrospective audits
+// Copyright (c) 2025 MeshX Foundation
+
+use std::sync::Arc;
+
+use crate::clock::FixedClock;
+use crate::presence::PresenceBundle;
+use crate::proof_of_presence::{PopValidator, ValidationError, ValidationPolicy};
+use crate::tee::TeeVerifierRegistry;
+
+// The rules a node was validated under from some epoch on: the validation
+// policy and the TEE trust config
+#[derive(Clone)]
+pub struct PolicySnapshot {
+    pub policy: ValidationPolicy,
+    pub tee_verifiers: TeeVerifierRegistry,
+}
+
+impl PopValidator {
+    // Record the current policy and trust config as in force from the
+    // current epoch. Trust config changes through the validator record one
+    // themselves, and policy edits are caught on entering the next epoch;
+    // a later snapshot in the same epoch replaces the earlier one.
+    pub fn record_policy_snapshot(&mut self) {
+        let snapshot = PolicySnapshot {
+            policy: self.policy.clone(),
+            tee_verifiers: self.tee_verifiers.clone(),
+        };
+        self.policy_snapshots.insert(self.current_epoch, snapshot);
+    }
+
+    // Snapshot the rules if they differ from those last recorded, e.g.
+    // after `policy` was edited directly. Run on entering each epoch.
+    pub(crate) fn record_policy_snapshot_if_changed(&mut self) {
+        let unchanged = self
+            .policy_snapshots
+            .values()
+            .next_back()
+            .is_some_and(|snapshot| {
+                snapshot.policy == self.policy
+                    && snapshot.tee_verifiers.generation() == self.tee_verifiers.generation()
+            });
+        if !unchanged {
+            self.record_policy_snapshot();
+        }
+    }
+
+    // The snapshot in force during `epoch`, i.e. the latest one taking
+    // effect at or before it
+    pub fn policy_snapshot_at(&self, epoch: u64) -> Option<&PolicySnapshot> {
+        self.policy_snapshots
+            .range(..=epoch)
+            .next_back()
+            .map(|(_, snapshot)| snapshot)
+    }
+
+    // Check a presence bundle under the rules in force during `epoch`, for
+    // disputes about a past epoch. Attestation freshness is judged as of
+    // the end of that epoch, or now if it hasn't ended yet.
+    pub fn verify_presence_bundle_at_epoch(
+        &self,
+        bundle: &PresenceBundle,
+        epoch: u64,
+    ) -> Result<(), ValidationError> {
+        let snapshot = self
+            .policy_snapshot_at(epoch)
+            .ok_or(ValidationError::MissingPolicySnapshot)?;
+        let epoch_end = self
+            .genesis_time
+            .saturating_add(
+                epoch
+                    .saturating_add(1)
+                    .saturating_mul(self.epoch_length_secs.max(1)),
+            )
+            .saturating_sub(1);
+
+        let mut view = self.with_policy_view(snapshot.policy.clone());
+        view.tee_verifiers = snapshot.tee_verifiers.clone();
+        view.current_epoch = epoch;
+        view.set_clock(Arc::new(FixedClock(epoch_end.min(self.now_secs()))));
+        view.verify_presence_bundle(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presence::tests::{test_anchored_validator, test_bundle};
+    use crate::proof_of_presence::tests::current_timestamp;
+    use crate::proof_of_presence::Shard;
+    use crate::stake::StakeAmount;
+
+    #[test]
+    fn test_bundle_checked_against_policy_of_its_epoch() {
+        let mut validator = test_anchored_validator();
+        let bundle = test_bundle();
+        let epoch = validator.current_epoch_for_time(current_timestamp());
+        validator.current_epoch = epoch;
+        validator
+            .policy
+            .minimum_stake
+            .insert(Shard::Europe, StakeAmount::from_meshx(50_000));
+        validator.record_policy_snapshot();
+        assert_eq!(
+            validator.verify_presence_bundle_at_epoch(&bundle, epoch - 1),
+            Err(ValidationError::MissingPolicySnapshot)
+        );
+
+        // The stake minimum rises after the epoch in dispute
+        validator.current_epoch = epoch + 1;
+        validator
+            .policy
+            .minimum_stake
+            .insert(Shard::Europe, StakeAmount::from_meshx(200_000));
+        validator.record_policy_snapshot();
+        assert_eq!(
+            validator.verify_presence_bundle(&bundle),
+            Err(ValidationError::InsufficientStake)
+        );
+        assert_eq!(
+            validator.verify_presence_bundle_at_epoch(&bundle, epoch),
+            Ok(())
+        );
+        assert_eq!(
+            validator.verify_presence_bundle_at_epoch(&bundle, epoch + 5),
+            Err(ValidationError::InsufficientStake)
+        );
+
+        // Trust config is historical too: a later revocation doesn't
+        // reach back
+        validator
+            .tee_verifiers
+            .revoke_signer(bundle.node.tee_attestation.signer_pubkey);
+        validator.record_policy_snapshot();
+        assert_eq!(
+            validator.verify_presence_bundle_at_epoch(&bundle, epoch + 1),
+            Err(ValidationError::RevokedSigner)
+        );
+        assert_eq!(
+            validator.verify_presence_bundle_at_epoch(&bundle, epoch),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_rule_changes_snapshotted_automatically() {
+        let mut validator = test_anchored_validator();
+        let bundle = test_bundle();
+        let epoch = validator.current_epoch_for_time(current_timestamp());
+        validator.enter_epoch(epoch);
+        assert!(validator
+            .verify_presence_bundle_at_epoch(&bundle, epoch)
+            .is_ok());
+
+        // A revocation is recorded as it happens
+        validator.enter_epoch(epoch + 1);
+        validator.revoke_attestation_signer(bundle.node.tee_attestation.signer_pubkey);
+        assert_eq!(
+            validator.verify_presence_bundle_at_epoch(&bundle, epoch + 1),
+            Err(ValidationError::RevokedSigner)
+        );
+
+        // A direct policy edit is picked up on entering the next epoch
+        let raised = StakeAmount::from_meshx(200_000);
+        validator.policy.minimum_stake.insert(Shard::Europe, raised);
+        let minimum_at = |validator: &PopValidator, epoch| {
+            validator
+                .policy_snapshot_at(epoch)
+                .unwrap()
+                .policy
+                .minimum_stake[&Shard::Europe]
+        };
+        assert_ne!(minimum_at(&validator, epoch + 1), raised);
+        validator.enter_epoch(epoch + 2);
+        assert_eq!(minimum_at(&validator, epoch + 2), raised);
+        assert_ne!(minimum_at(&validator, epoch + 1), raised);
+        let snapshots = validator.policy_snapshots.len();
+        validator.enter_epoch(epoch + 3);
+        assert_eq!(validator.policy_snapshots.len(), snapshots);
+    }
+}
@@ -0,0 +1,270 @@
+// MeshX - The Immutable Global Device Mesh
+// Self-contained presence proofs
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::{Keypair, PublicKey, Signature, Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::gossip::SignedLatencyMeasurement;
+use crate::proof_of_presence::{MeshXNode, PopValidator, ValidationError};
+
+// Everything the node can contribute to a proof of its physical presence:
+// its record and the measurements anchors took of it, each signed by the
+// anchor that took it, with the whole signed by the node. Which anchors to
+// believe is up to the verifier, so the bundle carries no anchor records.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceBundle {
+    pub node: MeshXNode,
+    pub measurements: Vec<SignedLatencyMeasurement>,
+    pub signature: Signature,
+}
+
+impl PresenceBundle {
+    pub fn sign(
+        node: MeshXNode,
+        measurements: Vec<SignedLatencyMeasurement>,
+        keypair: &Keypair,
+    ) -> Self {
+        let signature = keypair.sign(&Self::signing_payload(&node, &measurements));
+        Self {
+            node,
+            measurements,
+            signature,
+        }
+    }
+
+    // Check the node's signature over the bundle and each anchor's
+    // signature over its measurement
+    pub fn verify_signature(&self) -> Result<(), ValidationError> {
+        let payload = Self::signing_payload(&self.node, &self.measurements);
+        self.node
+            .pubkey
+            .verify(&payload, &self.signature)
+            .map_err(|_| ValidationError::InvalidSignature)?;
+        for measurement in &self.measurements {
+            measurement.verify()?;
+        }
+        Ok(())
+    }
+
+    fn signing_payload(node: &MeshXNode, measurements: &[SignedLatencyMeasurement]) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_PRESENCE_BUNDLE");
+        hasher.update(serde_json::to_vec(&(node, measurements)).expect("bundle serializes"));
+        hasher.finalize().to_vec()
+    }
+}
+
+impl PopValidator {
+    // Check a presence bundle against this validator's policy, running the
+    // same checks registration does. Anchors are taken only from the
+    // validator's own registry, the nodes it trusts to locate others;
+    // measurements by anyone else are ignored. Each anchor counts once.
+    pub fn verify_presence_bundle(&self, bundle: &PresenceBundle) -> Result<(), ValidationError> {
+        bundle.verify_signature()?;
+
+        let node = &bundle.node;
+        self.verify_tee_attestation(&node.tee_attestation)?;
+
+        let mut seen: Vec<PublicKey> = Vec::new();
+        let mut anchors: Vec<(&MeshXNode, u32)> = Vec::new();
+        for signed in &bundle.measurements {
+            let measurement = &signed.measurement;
+            if measurement.to_node != node.pubkey || seen.contains(&measurement.from_node) {
+                continue;
+            }
+            if let Some(anchor) = self.nodes.get(&measurement.from_node) {
+                seen.push(measurement.from_node);
+                anchors.push((anchor, measurement.one_way_latency_ms()));
+            }
+        }
+        self.check_location_plausible(node, &anchors)?;
+        if anchors.len() < self.policy.min_latency_measurements {
+            return Err(ValidationError::InsufficientLatencyData);
+        }
+        let calculated_location = self.triangulate_anchors(&anchors)?;
+        self.check_triangulated_location(node, &calculated_location)?;
+
+        self.verify_stake(node)?;
+        self.verify_resources_for_stake(&node.resources, node.stake_amount)?;
+        self.verify_metadata(node)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::{current_timestamp, test_node};
+    use crate::proof_of_presence::{LatencyMeasurement, Shard};
+    use crate::stake::StakeAmount;
+    use ed25519_dalek::SecretKey;
+
+    pub(crate) fn test_keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    // Three anchors near Frankfurt, as a verifier would configure them
+    pub(crate) fn test_anchors() -> Vec<MeshXNode> {
+        (2..5)
+            .map(|seed| test_node(seed, 50.5, 9.5, 100_000))
+            .collect()
+    }
+
+    // A validator trusting `test_anchors`
+    pub(crate) fn test_anchored_validator() -> PopValidator {
+        let mut validator = PopValidator::new(1);
+        for anchor in test_anchors() {
+            validator.nodes.insert(anchor.pubkey, anchor);
+        }
+        validator
+    }
+
+    fn anchor_measurement(
+        seed: u8,
+        to_node: PublicKey,
+        latency_ms: u32,
+    ) -> SignedLatencyMeasurement {
+        let anchor = test_keypair(seed);
+        let measurement = LatencyMeasurement {
+            from_node: anchor.public,
+            to_node,
+            latency_ms,
+            timestamp: current_timestamp(),
+            is_rtt: false,
+        };
+        SignedLatencyMeasurement::sign(measurement, &anchor)
+    }
+
+    // A valid bundle for a European node measured by the three test anchors
+    pub(crate) fn test_bundle() -> PresenceBundle {
+        let keypair = test_keypair(1);
+        let node = test_node(1, 50.0, 10.0, 100_000);
+        let measurements = (2..5)
+            .map(|seed| anchor_measurement(seed, node.pubkey, 12))
+            .collect();
+        PresenceBundle::sign(node, measurements, &keypair)
+    }
+
+    #[test]
+    fn test_presence_bundle_verification() {
+        let validator = test_anchored_validator();
+        let bundle = test_bundle();
+        assert_eq!(validator.verify_presence_bundle(&bundle), Ok(()));
+
+        let mut tampered = bundle.clone();
+        tampered.node.stake_amount = tampered
+            .node
+            .stake_amount
+            .saturating_add(StakeAmount::from_base_units(1));
+        assert_eq!(
+            validator.verify_presence_bundle(&tampered),
+            Err(ValidationError::InvalidSignature)
+        );
+
+        // Correctly signed, but too few anchors to locate the node
+        let keypair = test_keypair(1);
+        let mut measurements = bundle.measurements.clone();
+        measurements.pop();
+        let sparse = PresenceBundle::sign(bundle.node.clone(), measurements.clone(), &keypair);
+        assert_eq!(
+            validator.verify_presence_bundle(&sparse),
+            Err(ValidationError::InsufficientLatencyData)
+        );
+
+        // Repeating an anchor's measurement doesn't make up the shortfall
+        measurements.push(measurements[0].clone());
+        let repeated = PresenceBundle::sign(bundle.node, measurements, &keypair);
+        assert_eq!(
+            validator.verify_presence_bundle(&repeated),
+            Err(ValidationError::InsufficientLatencyData)
+        );
+    }
+
+    #[test]
+    fn test_bundle_cannot_invent_anchors_or_measurements() {
+        let keypair = test_keypair(1);
+        let bundle = test_bundle();
+
+        // The node signs a latency in an anchor's name
+        let mut measurements = bundle.measurements.clone();
+        let mut forged = measurements[0].measurement.clone();
+        forged.latency_ms = 1;
+        measurements[0] = SignedLatencyMeasurement::sign(forged, &keypair);
+        let forged = PresenceBundle::sign(bundle.node.clone(), measurements, &keypair);
+        assert_eq!(
+            test_anchored_validator().verify_presence_bundle(&forged),
+            Err(ValidationError::UnauthorizedMeasurement)
+        );
+
+        // Genuinely signed measurements from anchors the verifier doesn't
+        // trust count for nothing
+        let measurements = (5..8)
+            .map(|seed| anchor_measurement(seed, bundle.node.pubkey, 12))
+            .collect();
+        let self_anchored = PresenceBundle::sign(bundle.node.clone(), measurements, &keypair);
+        assert_eq!(
+            test_anchored_validator().verify_presence_bundle(&self_anchored),
+            Err(ValidationError::InsufficientLatencyData)
+        );
+        assert_eq!(
+            PopValidator::new(1).verify_presence_bundle(&bundle),
+            Err(ValidationError::InsufficientLatencyData)
+        );
+    }
+
+    #[test]
+    fn test_bundle_runs_registration_location_checks() {
+        let keypair = test_keypair(1);
+        let bundle = test_bundle();
+
+        let mut offshore_only = test_anchored_validator();
+        offshore_only.policy.habitable_regions = Some(Vec::new());
+        assert_eq!(
+            offshore_only.verify_presence_bundle(&bundle),
+            Err(ValidationError::UninhabitableLocation)
+        );
+
+        // Faster than light in fiber over the ~65 km to the anchors, once
+        // every position is claimed to within a kilometre
+        let mut physical = test_anchored_validator();
+        physical.policy.min_latency_fraction = Some(0.5);
+        for anchor in physical.nodes.values_mut() {
+            anchor.geo_location.accuracy_meters = 1_000.0;
+        }
+        let mut precise = bundle.node.clone();
+        precise.geo_location.accuracy_meters = 1_000.0;
+        let measurements = (2..5)
+            .map(|seed| anchor_measurement(seed, precise.pubkey, 0))
+            .collect();
+        let instant = PresenceBundle::sign(precise, measurements, &keypair);
+        assert_eq!(
+            physical.verify_presence_bundle(&instant),
+            Err(ValidationError::LatencyBelowPhysicalMinimum)
+        );
+
+        // Latency places the node in Europe, whatever shard it declares
+        let mut shard_checked = test_anchored_validator();
+        shard_checked.policy.triangulated_shard_tolerance_m = Some(100_000.0);
+        let mut misfiled = bundle.node.clone();
+        misfiled.shard = Shard::Asia;
+        let misfiled = PresenceBundle::sign(misfiled, bundle.measurements.clone(), &keypair);
+        assert_eq!(
+            shard_checked.verify_presence_bundle(&misfiled),
+            Err(ValidationError::TriangulatedShardMismatch)
+        );
+
+        let mut labelled = bundle.node.clone();
+        labelled
+            .metadata
+            .insert("operator".to_string(), "x".repeat(257));
+        let labelled = PresenceBundle::sign(labelled, bundle.measurements, &keypair);
+        assert_eq!(
+            test_anchored_validator().verify_presence_bundle(&labelled),
+            Err(ValidationError::MetadataTooLarge)
+        );
+    }
+}
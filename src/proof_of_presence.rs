@@ -6,16 +6,42 @@ use ed25519_dalek::{Keypair, PublicKey, Signature, Signer, Verifier};
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::backoff::{BackoffPolicy, FailureBackoff};
+use crate::boundaries::ShardBoundaries;
+use crate::challenge::AttestationChallenge;
+use crate::clock::{Clock, ClockAnchor, SystemClock};
+use crate::contribution::ContributionWeights;
+use crate::delegation::DelegationCaps;
+use crate::diversity::{DiversityFallback, DiversityShortfall, ShortfallError};
+use crate::gossip::{LatencyConflictPolicy, SeenAnnouncements};
+use crate::metadata::MetadataLimits;
+use crate::metrics::MetricsSnapshot;
+use crate::migration::ProvisionalMigration;
+use crate::participation::SelectionHistory;
+use crate::plausibility::RegionalExpectation;
+use crate::policy_history::PolicySnapshot;
+use crate::releases::ReleaseInfo;
+use crate::report::RedundancyWeights;
+use crate::reputation::{ReputationBootstrap, ReputationDecay};
+use crate::rewards::RewardPolicy;
+use crate::routing::ServiceRoutingWeights;
+use crate::stake::StakeAmount;
+use crate::tee::TeeVerifierRegistry;
+use crate::tee_change::TeeChangeHold;
+use crate::unstake::PendingUnstake;
+
 // TEE attestation types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TeeType {
     IntelSgx,
     ArmTrustZone,
     AppleSecureEnclave,
     AmdSev,
+    Custom(u32), // Externally registered verifier, see TeeVerifierRegistry
 }
 
 // Continental shards in MeshX network
@@ -31,7 +57,7 @@ pub enum Shard {
 }
 
 // Geographic coordinates for location verification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GeoLocation {
     pub latitude: f64,
     pub longitude: f64,
@@ -48,6 +74,13 @@ pub struct TeeAttestation {
     pub quote: Vec<u8>,
 }
 
+// Speed of light in fiber: ~200km/ms
+pub(crate) const FIBER_KM_PER_MS: f64 = 200.0;
+
+// Generous TCP window for bandwidth plausibility: a single flow can't move
+// more than one window per round trip
+const MAX_TCP_WINDOW_BYTES: f64 = 16.0 * 1024.0 * 1024.0;
+
 // Network latency measurement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LatencyMeasurement {
@@ -55,6 +88,29 @@ pub struct LatencyMeasurement {
     pub to_node: PublicKey,
     pub latency_ms: u32,
     pub timestamp: u64,
+    // Round-trip rather than one-way time. Older records predate the field
+    // and were one-way.
+    #[serde(default)]
+    pub is_rtt: bool,
+}
+
+impl LatencyMeasurement {
+    pub fn one_way_latency_ms(&self) -> u32 {
+        if self.is_rtt {
+            self.latency_ms / 2
+        } else {
+            self.latency_ms
+        }
+    }
+
+    // Upper bound on the distance between the two nodes
+    pub fn max_distance_km(&self) -> f64 {
+        if self.is_rtt {
+            self.latency_ms as f64 / 2.0 * FIBER_KM_PER_MS
+        } else {
+            self.latency_ms as f64 * FIBER_KM_PER_MS
+        }
+    }
 }
 
 // VRF proof for random selection
@@ -72,13 +128,17 @@ pub struct MeshXNode {
     pub tee_attestation: TeeAttestation,
     pub geo_location: GeoLocation,
     pub shard: Shard,
-    pub stake_amount: u64, // MESHX tokens staked
+    pub stake_amount: StakeAmount,
     pub reputation_score: f32,
     pub resources: NodeResources,
+    // Operator labels for their own tooling. Kept out of the state
+    // fingerprint, and of serialized records when empty.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
 }
 
 // Node computational resources
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NodeResources {
     pub cpu_cores: u32,
     pub ram_gb: u32,
@@ -87,309 +147,2250 @@ pub struct NodeResources {
     pub gpu_memory_gb: Option<u32>,
 }
 
-// Proof of Physical Presence validator
-pub struct PopValidator {
-    pub current_epoch: u64,
-    pub nodes: HashMap<PublicKey, MeshXNode>,
-    pub latency_matrix: HashMap<(PublicKey, PublicKey), u32>,
-    pub minimum_nodes: usize,
+// Lightweight public view of a node for gossip. Omits the attestation quote,
+// which dominates the size of a full record; full records are fetched on demand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeSummary {
+    pub pubkey: PublicKey,
+    pub shard: Shard,
+    pub stake_amount: StakeAmount,
+    pub reputation_score: f32,
+    pub geo_location: GeoLocation,
+    pub resources: NodeResources,
 }
 
-impl PopValidator {
-    pub fn new(minimum_nodes: usize) -> Self {
-        Self {
-            current_epoch: 0,
-            nodes: HashMap::new(),
-            latency_matrix: HashMap::new(),
-            minimum_nodes,
+impl MeshXNode {
+    pub fn summary(&self) -> NodeSummary {
+        NodeSummary {
+            pubkey: self.pubkey,
+            shard: self.shard,
+            stake_amount: self.stake_amount,
+            reputation_score: self.reputation_score,
+            geo_location: self.geo_location.clone(),
+            resources: self.resources.clone(),
+        }
+    }
+}
+
+impl Shard {
+    // Built-in minimum stake for the shard
+    pub fn default_minimum_stake(&self) -> StakeAmount {
+        match self {
+            Shard::NorthAmerica | Shard::Europe | Shard::Asia => StakeAmount::from_meshx(100_000), // 100K MESHX
+            Shard::SouthAmerica | Shard::Africa | Shard::Oceania => StakeAmount::from_meshx(50_000), // 50K MESHX
+            Shard::Antarctica => StakeAmount::from_meshx(10_000), // 10K MESHX (encourage Antarctic nodes!)
         }
     }
 
-    // Validate a node's proof of physical presence
-    pub fn validate_node(&self, node: &MeshXNode) -> Result<bool, ValidationError> {
-        // Step 1: Verify TEE attestation
-        self.verify_tee_attestation(&node.tee_attestation)?;
+    pub fn all() -> [Shard; 7] {
+        [
+            Shard::NorthAmerica,
+            Shard::Europe,
+            Shard::Asia,
+            Shard::SouthAmerica,
+            Shard::Africa,
+            Shard::Oceania,
+            Shard::Antarctica,
+        ]
+    }
+
+    // Extents of every region assigned to the shard. Shard boxes overlap;
+    // `assign_shard` resolves overlaps by region order, so a point inside a
+    // shard's box may still belong to an earlier region's shard. Points
+    // matching no region fall back to North America outside its box.
+    pub fn bounding_box(&self) -> BoundingBox {
+        let regions: Vec<_> = SHARD_REGIONS
+            .iter()
+            .filter(|region| region.shard == *self)
+            .map(|region| region.bounds)
+            .collect();
+        let min_latitude = regions
+            .iter()
+            .map(|b| b.min_latitude)
+            .fold(f64::INFINITY, f64::min);
+        let max_latitude = regions
+            .iter()
+            .map(|b| b.max_latitude)
+            .fold(f64::NEG_INFINITY, f64::max);
 
-        // Step 2: Verify geographic location via latency
-        self.verify_location(&node.pubkey, &node.geo_location)?;
+        // A shard with regions on both sides of the antimeridian (but not
+        // spanning it in one region) wraps: its box runs east from the
+        // eastern region's western edge to the western region's eastern edge
+        let east = regions
+            .iter()
+            .find(|b| b.max_longitude >= 180.0 && b.min_longitude > -180.0);
+        let west = regions
+            .iter()
+            .find(|b| b.min_longitude <= -180.0 && b.max_longitude < 180.0);
+        let (min_longitude, max_longitude) = match (east, west) {
+            (Some(east), Some(west)) => (east.min_longitude, west.max_longitude),
+            _ => (
+                regions
+                    .iter()
+                    .map(|b| b.min_longitude)
+                    .fold(f64::INFINITY, f64::min),
+                regions
+                    .iter()
+                    .map(|b| b.max_longitude)
+                    .fold(f64::NEG_INFINITY, f64::max),
+            ),
+        };
 
-        // Step 3: Check minimum stake requirement
-        if node.stake_amount < self.get_minimum_stake(&node.shard) {
-            return Err(ValidationError::InsufficientStake);
+        BoundingBox {
+            min_latitude: min_latitude.max(-90.0),
+            max_latitude: max_latitude.min(90.0),
+            min_longitude: min_longitude.max(-180.0),
+            max_longitude: max_longitude.min(180.0),
         }
+    }
+}
 
-        // Step 4: Verify node resources
-        self.verify_resources(&node.resources)?;
+// Latitude/longitude extents. When `min_longitude > max_longitude` the box
+// crosses the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub min_latitude: f64,
+    pub max_latitude: f64,
+    pub min_longitude: f64,
+    pub max_longitude: f64,
+}
 
-        Ok(true)
+impl BoundingBox {
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_longitude > self.max_longitude
     }
 
-    // Verify TEE attestation is valid and recent
-    fn verify_tee_attestation(&self, attestation: &TeeAttestation) -> Result<(), ValidationError> {
-        // Check attestation age (must be < 1 hour)
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+    // Edges are inclusive
+    pub fn contains(&self, location: &GeoLocation) -> bool {
+        let lon = normalize_longitude(location.longitude);
+        let in_longitude = if self.crosses_antimeridian() {
+            lon >= self.min_longitude || lon <= self.max_longitude
+        } else {
+            lon >= self.min_longitude && lon <= self.max_longitude
+        };
+        location.latitude >= self.min_latitude
+            && location.latitude <= self.max_latitude
+            && in_longitude
+    }
+}
 
-        if current_time - attestation.timestamp > 3600 {
-            return Err(ValidationError::StaleAttestation);
-        }
+// A box of the map claimed by a shard. Edges are exclusive unless
+// `includes_max_latitude` is set; unbounded edges are infinite.
+struct ShardRegion {
+    shard: Shard,
+    bounds: BoundingBox,
+    includes_max_latitude: bool,
+}
 
-        // Verify enclave hash matches expected MeshX code
-        let expected_hash = self.get_expected_enclave_hash();
-        if attestation.enclave_hash != expected_hash {
-            return Err(ValidationError::InvalidEnclaveCode);
+impl ShardRegion {
+    const fn new(shard: Shard, latitude: (f64, f64), longitude: (f64, f64)) -> Self {
+        Self {
+            shard,
+            bounds: BoundingBox {
+                min_latitude: latitude.0,
+                max_latitude: latitude.1,
+                min_longitude: longitude.0,
+                max_longitude: longitude.1,
+            },
+            includes_max_latitude: false,
         }
+    }
 
-        // TODO: Implement actual TEE quote verification for each type
-        match attestation.tee_type {
-            TeeType::IntelSgx => self.verify_sgx_quote(&attestation.quote)?,
-            TeeType::ArmTrustZone => self.verify_trustzone_quote(&attestation.quote)?,
-            TeeType::AppleSecureEnclave => self.verify_secure_enclave_quote(&attestation.quote)?,
-            TeeType::AmdSev => self.verify_sev_quote(&attestation.quote)?,
+    fn contains(&self, lat: f64, lon: f64) -> bool {
+        let b = &self.bounds;
+        let below_max =
+            lat < b.max_latitude || (self.includes_max_latitude && lat == b.max_latitude);
+        lat > b.min_latitude && below_max && lon > b.min_longitude && lon < b.max_longitude
+    }
+}
+
+const INF: f64 = f64::INFINITY;
+
+// Single source of truth for shard geography. Regions are checked in order,
+// so earlier regions win where boxes overlap (e.g. the Middle East region
+// claims Arabia before the wider Africa box can).
+const SHARD_REGIONS: [ShardRegion; 14] = [
+    ShardRegion::new(Shard::Antarctica, (-INF, -60.0), (-INF, INF)),
+    ShardRegion::new(Shard::NorthAmerica, (15.0, 84.0), (-170.0, -50.0)),
+    // Extends west to -25 to take in Iceland
+    ShardRegion::new(Shard::Europe, (35.0, 82.0), (-25.0, 40.0)),
+    // Africa's Red Sea coast and hinterland east of 34°E, stepped along the
+    // middle of the sea so the Arabian coast stays with the Asia box below
+    ShardRegion::new(Shard::Africa, (12.0, 14.5), (34.0, 43.0)),
+    ShardRegion::new(Shard::Africa, (14.5, 18.0), (34.0, 41.5)),
+    ShardRegion::new(Shard::Africa, (18.0, 22.0), (34.0, 38.6)),
+    ShardRegion::new(Shard::Africa, (22.0, 24.0), (34.0, 37.5)),
+    ShardRegion::new(Shard::Africa, (24.0, 27.0), (34.0, 35.5)),
+    // Levant, Arabia and Iran belong to Asia despite the Africa box
+    ShardRegion::new(Shard::Asia, (12.0, 40.0), (34.0, 60.0)),
+    ShardRegion::new(Shard::SouthAmerica, (-60.0, 15.0), (-85.0, -30.0)),
+    ShardRegion::new(Shard::Africa, (-40.0, 40.0), (-20.0, 55.0)),
+    ShardRegion::new(Shard::Asia, (-10.0, 82.0), (40.0, INF)),
+    // Oceania straddles the antimeridian: Australia and New Zealand on the
+    // east side, Samoa and Tonga on the west
+    ShardRegion {
+        includes_max_latitude: true,
+        ..ShardRegion::new(Shard::Oceania, (-50.0, -10.0), (110.0, INF))
+    },
+    ShardRegion::new(Shard::Oceania, (-50.0, 0.0), (-INF, -150.0)),
+];
+
+// Shard of the first region containing the point, if any
+fn region_shard(latitude: f64, longitude: f64) -> Option<Shard> {
+    SHARD_REGIONS
+        .iter()
+        .find(|region| region.contains(latitude, longitude))
+        .map(|region| region.shard)
+}
+
+// Higher resource bars for nodes staking more, since more depends on them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceTier {
+    pub min_stake: StakeAmount, // Applies to nodes staking at least this
+    pub min_cpu_cores: u32,
+    pub min_ram_gb: u32,
+}
+
+// Every threshold applied by node validation, configured in one place
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationPolicy {
+    pub max_attestation_age_secs: u64,
+    // Clock skew tolerated for attestations dated ahead of now
+    pub max_attestation_future_secs: u64,
+    pub challenge_windows_secs: Vec<(TeeType, u64)>, // Per-type nonce response windows
+    pub default_challenge_window_secs: u64,          // For types without their own window
+    pub placeholder_enclave_hashes: Vec<[u8; 32]>,   // Known stub values, rejected like all-zero
+    pub allow_placeholder_attestations: bool,        // Test/dev mode only
+    pub known_releases: Vec<ReleaseInfo>,            // Published enclave builds
+    pub min_latency_measurements: usize,
+    pub max_triangulation_anchors: usize, // Larger anchor sets are reduced to the best-spread subset
+    pub triangulated_shard_tolerance_m: Option<f64>, // None skips the triangulated shard check
+    pub habitable_regions: Option<Vec<Vec<[f64; 2]>>>, // [lon, lat] land outlines supplied by the operator; None skips
+    pub offshore_allowlist: Vec<PublicKey>,            // Exempt from the habitable-region check
+    pub min_latency_fraction: Option<f64>, // Of the great-circle minimum a latency may reach; None skips
+    pub minimum_stake: HashMap<Shard, StakeAmount>,
+    pub total_supply: StakeAmount,
+    pub max_stake_fraction: f64, // Largest share of total supply one node may claim
+    pub max_stake_per_resource_unit: Option<StakeAmount>, // Per CPU core or GB of RAM; None skips
+    pub min_cpu_cores: u32,
+    pub min_ram_gb: u32,
+    pub resource_tiers: Vec<ResourceTier>, // Stake-scaled CPU/RAM minimums; empty keeps them flat
+    pub min_storage_gb: u64,
+    pub min_bandwidth_mbps: u32,
+    pub min_gpu_memory_gb: Option<u32>, // None for workloads that don't need a GPU
+    pub resource_fluctuation_tolerance: f64, // Fractional drop allowed between registrations
+    pub anchor_reputation_influence: f64, // 0..=1; 0 weights anchors by distance alone
+    pub soft_failures: Vec<ValidationError>, // Admitted with a reputation penalty
+    pub soft_failure_penalty: f32,      // Reputation lost per soft failure
+    pub delegation_caps: DelegationCaps,
+    pub metadata_limits: MetadataLimits,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            max_attestation_age_secs: 3600, // 1 hour
+            max_attestation_future_secs: 60,
+            challenge_windows_secs: Vec::new(),
+            default_challenge_window_secs: 60,
+            placeholder_enclave_hashes: Vec::new(),
+            allow_placeholder_attestations: false,
+            known_releases: Vec::new(),
+            min_latency_measurements: 3,
+            max_triangulation_anchors: 8,
+            triangulated_shard_tolerance_m: None,
+            habitable_regions: None,
+            offshore_allowlist: Vec::new(),
+            min_latency_fraction: None,
+            minimum_stake: Shard::all()
+                .into_iter()
+                .map(|shard| (shard, shard.default_minimum_stake()))
+                .collect(),
+            total_supply: StakeAmount::from_meshx(10_000_000_000), // Fixed MESHX supply
+            max_stake_fraction: 0.05,
+            max_stake_per_resource_unit: None,
+            min_cpu_cores: 2,
+            min_ram_gb: 4,
+            resource_tiers: Vec::new(),
+            min_storage_gb: 100,
+            min_bandwidth_mbps: 10,
+            min_gpu_memory_gb: None,
+            resource_fluctuation_tolerance: 0.1,
+            anchor_reputation_influence: 0.0,
+            soft_failures: Vec::new(), // Every failure rejects
+            soft_failure_penalty: 0.1,
+            delegation_caps: DelegationCaps::default(), // Uncapped
+            metadata_limits: MetadataLimits::default(),
         }
+    }
+}
 
-        Ok(())
+impl ValidationPolicy {
+    // Shards missing from the map fall back to the built-in minimum rather
+    // than accepting zero stake
+    pub fn minimum_stake_for(&self, shard: &Shard) -> StakeAmount {
+        self.minimum_stake
+            .get(shard)
+            .copied()
+            .unwrap_or_else(|| shard.default_minimum_stake())
     }
 
-    // Verify node's claimed location using latency triangulation
-    fn verify_location(
+    // CPU and RAM minimums for a node staking `stake`: the highest of the
+    // base minimum and every tier the stake reaches
+    pub fn min_cpu_cores_for(&self, stake: StakeAmount) -> u32 {
+        self.reached_tiers(stake)
+            .map(|tier| tier.min_cpu_cores)
+            .fold(self.min_cpu_cores, u32::max)
+    }
+
+    pub fn min_ram_gb_for(&self, stake: StakeAmount) -> u32 {
+        self.reached_tiers(stake)
+            .map(|tier| tier.min_ram_gb)
+            .fold(self.min_ram_gb, u32::max)
+    }
+
+    // Check resources against the minimums for a node staking `stake`
+    pub fn check_resources(
         &self,
-        node_pubkey: &PublicKey,
-        claimed_location: &GeoLocation,
+        resources: &NodeResources,
+        stake: StakeAmount,
     ) -> Result<(), ValidationError> {
-        // Get latency measurements to this node from others
-        let measurements: Vec<_> = self
-            .latency_matrix
+        if resources.cpu_cores < self.min_cpu_cores_for(stake) {
+            return Err(ValidationError::InsufficientCPU);
+        }
+        if resources.ram_gb < self.min_ram_gb_for(stake) {
+            return Err(ValidationError::InsufficientRAM);
+        }
+        if resources.storage_gb < self.min_storage_gb {
+            return Err(ValidationError::InsufficientStorage);
+        }
+        if resources.bandwidth_mbps < self.min_bandwidth_mbps {
+            return Err(ValidationError::InsufficientBandwidth);
+        }
+        // None means no GPU; a GPU reporting zero memory means detection
+        // failed, which shouldn't pass as either
+        if resources.gpu_memory_gb == Some(0) {
+            return Err(ValidationError::InvalidGpuReport);
+        }
+        if let Some(min_gpu_memory_gb) = self.min_gpu_memory_gb {
+            if resources.gpu_memory_gb.unwrap_or(0) < min_gpu_memory_gb {
+                return Err(ValidationError::InsufficientGPU);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reached_tiers(&self, stake: StakeAmount) -> impl Iterator<Item = &ResourceTier> {
+        self.resource_tiers
             .iter()
-            .filter(|((_, to), _)| to == node_pubkey)
-            .map(|((from, _), latency)| (from, *latency))
-            .collect();
+            .filter(move |tier| stake >= tier.min_stake)
+    }
 
-        if measurements.len() < 3 {
-            return Err(ValidationError::InsufficientLatencyData);
+    // Seconds a node of the type has to answer an attestation challenge
+    pub fn challenge_window_secs(&self, tee_type: &TeeType) -> u64 {
+        self.challenge_windows_secs
+            .iter()
+            .find(|(configured, _)| configured == tee_type)
+            .map(|(_, window)| *window)
+            .unwrap_or(self.default_challenge_window_secs)
+    }
+
+    pub fn severity(&self, error: &ValidationError) -> FailureSeverity {
+        if self.soft_failures.contains(error) {
+            FailureSeverity::Soft
+        } else {
+            FailureSeverity::Hard
         }
+    }
+}
 
-        // Triangulate position based on latency
-        let calculated_location = self.triangulate_position(&measurements)?;
+// Whether a failed check rejects the node or only counts against it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailureSeverity {
+    Hard,
+    Soft,
+}
 
-        // Check if calculated location matches claimed (within accuracy)
-        let distance = haversine_distance(&calculated_location, claimed_location);
-        if distance > claimed_location.accuracy_meters as f64 {
-            return Err(ValidationError::LocationMismatch);
-        }
+// Result of validating an admitted node. Hard failures are returned as
+// errors instead, so a node that reaches an outcome has been admitted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationOutcome {
+    pub soft_failures: Vec<ValidationError>,
+}
 
-        Ok(())
+impl ValidationOutcome {
+    // Reputation after the penalty for each soft failure
+    pub fn penalized_reputation(&self, reputation: f32, penalty: f32) -> f32 {
+        (reputation - penalty * self.soft_failures.len() as f32).max(0.0)
     }
+}
 
-    // Calculate position from latency measurements
-    fn triangulate_position(
-        &self,
-        measurements: &[(&PublicKey, u32)],
-    ) -> Result<GeoLocation, ValidationError> {
-        // Simplified triangulation based on speed of light
-        // Real implementation would use more sophisticated algorithms
+// Structured breakdown of every validation check for one node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub pubkey: PublicKey,
+    pub shard: Shard,
+    pub attestation: AttestationCheck,
+    pub location: LocationCheck,
+    pub stake: StakeCheck,
+    pub resources: Vec<ResourceCheck>,
+    pub metadata: MetadataCheck,
+}
 
-        let mut lat_sum = 0.0;
-        let mut lon_sum = 0.0;
-        let mut weight_sum = 0.0;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestationCheck {
+    pub tee_type: TeeType,
+    pub age_secs: i64, // Negative for attestations dated in the future
+    pub max_age_secs: u64,
+    pub error: Option<ValidationError>,
+}
 
-        for (peer_key, latency_ms) in measurements {
-            if let Some(peer) = self.nodes.get(peer_key) {
-                // Convert latency to approximate distance
-                // Speed of light in fiber: ~200km/ms
-                let distance_km = (*latency_ms as f64) * 200.0;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationCheck {
+    pub claimed: GeoLocation,
+    pub triangulated: Option<GeoLocation>,
+    pub distance_meters: Option<f64>,
+    pub measurement_count: usize,
+    pub error: Option<ValidationError>,
+}
 
-                // Weight by inverse distance
-                let weight = 1.0 / distance_km;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeCheck {
+    pub effective_stake: StakeAmount,
+    pub required_stake: StakeAmount,
+    pub error: Option<ValidationError>,
+}
 
-                lat_sum += peer.geo_location.latitude * weight;
-                lon_sum += peer.geo_location.longitude * weight;
-                weight_sum += weight;
-            }
-        }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceCheck {
+    pub resource: String,
+    pub actual: u64,
+    pub required: u64,
+    pub error: Option<ValidationError>,
+}
 
-        Ok(GeoLocation {
-            latitude: lat_sum / weight_sum,
-            longitude: lon_sum / weight_sum,
-            accuracy_meters: 50000.0, // 50km accuracy for now
-        })
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataCheck {
+    pub total_bytes: usize, // Sum over every key and value
+    pub max_total_bytes: usize,
+    pub error: Option<ValidationError>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors().is_empty()
     }
 
-    // Select validators for next epoch using VRF
-    pub fn select_validators(&self, epoch: u64) -> Result<Vec<PublicKey>, ValidationError> {
-        let mut selected = Vec::new();
-        let mut candidates: Vec<_> = self.nodes.values().collect();
+    // Every failed check, in validation order
+    pub fn errors(&self) -> Vec<&ValidationError> {
+        self.attestation
+            .error
+            .iter()
+            .chain(self.location.error.iter())
+            .chain(self.stake.error.iter())
+            .chain(
+                self.resources
+                    .iter()
+                    .filter_map(|check| check.error.as_ref()),
+            )
+            .chain(self.metadata.error.iter())
+            .collect()
+    }
+}
 
-        // Sort by VRF output for deterministic selection
-        candidates.sort_by_key(|node| {
-            let vrf_input = self.compute_vrf_input(epoch, &node.pubkey);
-            self.compute_vrf_output(&vrf_input, &node.pubkey)
-        });
+// Hypothetical changes to a node for `simulate_validation`; unset fields keep
+// the base node's value
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeOverrides {
+    pub stake_amount: Option<StakeAmount>,
+    pub geo_location: Option<GeoLocation>, // Also reassigns the shard unless overridden
+    pub shard: Option<Shard>,
+    pub resources: Option<NodeResources>,
+    pub tee_attestation: Option<TeeAttestation>,
+}
 
-        // Select top N nodes weighted by stake
-        for node in candidates.iter().take(self.get_validator_count()) {
-            if self.validate_node(node).is_ok() {
-                selected.push(node.pubkey);
-            }
-        }
+// Proof of Physical Presence validator
+#[derive(Clone)]
+pub struct PopValidator {
+    pub network_id: String, // Binds VRF inputs to one network; empty for the original network
+    pub current_epoch: u64,
+    pub genesis_time: u64,
+    pub epoch_length_secs: u64,
+    pub shard_epoch_offsets_secs: HashMap<Shard, u64>, // Per-shard transition delay; missing shards transition on the global boundary
+    pub shard_epochs: HashMap<Shard, u64>, // Epoch each shard has entered per its offset
+    clock: Arc<dyn Clock>,
+    clock_anchor: ClockAnchor,
+    pub policy: ValidationPolicy,
+    pub shard_boundaries: Option<ShardBoundaries>, // None uses the built-in regions
+    pub tee_verifiers: TeeVerifierRegistry,
+    pub nodes: HashMap<PublicKey, MeshXNode>,
+    pub pending_nodes: HashMap<PublicKey, MeshXNode>, // Awaiting location verification
+    pub latency_matrix: HashMap<(PublicKey, PublicKey), u32>,
+    pub latency_timestamps: HashMap<(PublicKey, PublicKey), u64>, // Of each edge's applied measurement
+    pub latency_conflict_policy: LatencyConflictPolicy, // For differing latencies at the same timestamp
+    pub latency_conflicts: HashMap<(PublicKey, PublicKey), Vec<u32>>, // Distinct latencies at the applied timestamp, once they differ
+    pub clock_samples: HashMap<PublicKey, VecDeque<i64>>, // Apparent clock offsets in ms, newest last
+    pub clock_drift_tolerance_ms: i64,
+    pub attestation_timestamps: HashMap<PublicKey, (u64, u64)>, // Epoch and timestamp of each node's latest attestation
+    pub minimum_nodes: usize,
+    pub slash_cooldown_epochs: u64,
+    pub slashed_at: HashMap<PublicKey, u64>, // Epoch of each node's most recent slash
+    pub demoted_at: HashMap<PublicKey, u64>, // Epoch of each node's most recent liveness demotion
+    pub triangle_slack_ms: u32,
+    pub attestation_batch_threads: usize,
+    pub triangulation_threads: usize,
+    pub validator_count: usize,
+    pub min_tee_types_per_shard: usize, // 1 leaves selection unconstrained
+    pub diversity_fallback: DiversityFallback, // For shards short of TEE types
+    pub tee_balance_strength: f64,      // Down-weighting of over-represented TEE types; 0 disables
+    pub current_validator_set: Vec<PublicKey>, // Most recently recorded selection
+    pub validator_set_epoch: u64,       // Epoch that selection was made for
+    pub mid_epoch_replacement: bool,    // Refill dropped validators from the same epoch's ranking
+    pub reward_policy: RewardPolicy,
+    pub reward_balances: HashMap<PublicKey, StakeAmount>,
+    pub rewarded_epochs: HashSet<u64>,
+    pub delegations: HashMap<PublicKey, HashMap<PublicKey, StakeAmount>>, // Validator -> delegator -> amount
+    pub delegation_nonces: HashMap<PublicKey, u64>, // Delegations applied per delegator
+    pub reputation_stake_influence: f64, // 0..=1; 0 leaves effective stake independent of reputation
+    pub seen_announcements: SeenAnnouncements,
+    pub announcement_verifications: u64, // Announcements that reached verification
+    pub reputation_bootstrap: Option<ReputationBootstrap>, // None keeps declared reputation
+    pub reputation_decay: ReputationDecay,
+    pub migration_topup_grace_epochs: Option<u64>, // None rejects under-staked migrations
+    pub provisional_migrations: HashMap<PublicKey, ProvisionalMigration>,
+    pub contribution_weights: ContributionWeights,
+    pub redundancy_weights: RedundancyWeights,
+    pub service_routing_weights: ServiceRoutingWeights,
+    pub node_uptime: HashMap<PublicKey, f32>, // Fraction of liveness checks passed
+    pub liveness_failures: HashMap<PublicKey, u32>, // Consecutive failed liveness checks
+    pub liveness_demotion_threshold: Option<u32>, // Failures in a row that freeze a node; None never demotes
+    pub selection_history: HashMap<PublicKey, SelectionHistory>,
+    pub selection_history_epochs: usize, // Entries kept per node
+    pub vrf_aggregates: BTreeMap<u64, [u8; 32]>, // Selected VRF outputs per recorded epoch, same retention
+    pub epoch_seed: Option<[u8; 32]>,            // Rolled forward by advance_epoch; None disables
+    pub validation_backoff: Option<BackoffPolicy>, // None re-validates on every attempt
+    pub failure_backoff: HashMap<PublicKey, FailureBackoff>,
+    pub attestation_challenges: HashMap<PublicKey, AttestationChallenge>, // Outstanding nonce per node
+    pub trajectory_len: usize, // Verified locations kept per node; 0 keeps none
+    pub trajectories: HashMap<PublicKey, VecDeque<(u64, GeoLocation)>>,
+    pub unstake_delay_epochs: u64,
+    pub pending_unstakes: HashMap<PublicKey, Vec<PendingUnstake>>,
+    pub bans: HashMap<PublicKey, Option<u64>>, // Expiry epoch; None is permanent
+    pub probation_reputation: f32,             // Reputation a node restarts at after a ban
+    pub resource_expectations: Vec<RegionalExpectation>,
+    pub tee_type_history: HashMap<PublicKey, Vec<(u64, TeeType)>>, // Epoch each type was first registered
+    pub tee_change_hold_epochs: Option<u64>, // None accepts TEE type changes silently
+    pub tee_change_holds: HashMap<PublicKey, TeeChangeHold>,
+    pub warmup_epochs: u64, // Epochs a newly registered node waits before selection; 0 selects at once
+    pub registered_epochs: HashMap<PublicKey, u64>, // Epoch each node last joined the registry
+    pub metrics_history_len: usize, // Snapshots kept, one per epoch entered; 0 records none
+    pub metrics_history: VecDeque<MetricsSnapshot>,
+    pub policy_snapshots: BTreeMap<u64, PolicySnapshot>, // Keyed by the epoch each took effect
+}
 
-        if selected.len() < self.minimum_nodes {
-            return Err(ValidationError::InsufficientValidators);
+impl PopValidator {
+    pub fn new(minimum_nodes: usize) -> Self {
+        Self::with_policy(minimum_nodes, ValidationPolicy::default())
+    }
+
+    pub fn with_policy(minimum_nodes: usize, policy: ValidationPolicy) -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock::new());
+        Self {
+            network_id: String::new(),
+            current_epoch: 0,
+            genesis_time: 0,
+            epoch_length_secs: 3600, // 1 hour epochs
+            shard_epoch_offsets_secs: HashMap::new(),
+            shard_epochs: HashMap::new(),
+            clock_anchor: ClockAnchor::new(clock.as_ref()),
+            clock,
+            policy,
+            shard_boundaries: None,
+            tee_verifiers: TeeVerifierRegistry::default(),
+            nodes: HashMap::new(),
+            pending_nodes: HashMap::new(),
+            latency_matrix: HashMap::new(),
+            latency_timestamps: HashMap::new(),
+            latency_conflict_policy: LatencyConflictPolicy::default(),
+            latency_conflicts: HashMap::new(),
+            clock_samples: HashMap::new(),
+            clock_drift_tolerance_ms: 5_000,
+            attestation_timestamps: HashMap::new(),
+            minimum_nodes,
+            slash_cooldown_epochs: 3,
+            slashed_at: HashMap::new(),
+            demoted_at: HashMap::new(),
+            triangle_slack_ms: 10,
+            attestation_batch_threads: 4,
+            triangulation_threads: 4,
+            validator_count: 1000, // 1000 validators per shard
+            min_tee_types_per_shard: 1,
+            diversity_fallback: DiversityFallback::default(),
+            tee_balance_strength: 0.0,
+            current_validator_set: Vec::new(),
+            validator_set_epoch: 0,
+            mid_epoch_replacement: false,
+            reward_policy: RewardPolicy::default(),
+            reward_balances: HashMap::new(),
+            rewarded_epochs: HashSet::new(),
+            delegations: HashMap::new(),
+            delegation_nonces: HashMap::new(),
+            reputation_stake_influence: 0.0,
+            seen_announcements: SeenAnnouncements::new(10_000, 600),
+            announcement_verifications: 0,
+            reputation_bootstrap: None,
+            reputation_decay: ReputationDecay::default(),
+            migration_topup_grace_epochs: None,
+            provisional_migrations: HashMap::new(),
+            contribution_weights: ContributionWeights::default(),
+            redundancy_weights: RedundancyWeights::default(),
+            service_routing_weights: ServiceRoutingWeights::default(),
+            node_uptime: HashMap::new(),
+            liveness_failures: HashMap::new(),
+            liveness_demotion_threshold: None,
+            selection_history: HashMap::new(),
+            selection_history_epochs: 100,
+            vrf_aggregates: BTreeMap::new(),
+            epoch_seed: None,
+            validation_backoff: None,
+            failure_backoff: HashMap::new(),
+            attestation_challenges: HashMap::new(),
+            trajectory_len: 0,
+            trajectories: HashMap::new(),
+            unstake_delay_epochs: 7,
+            pending_unstakes: HashMap::new(),
+            bans: HashMap::new(),
+            probation_reputation: 0.0,
+            resource_expectations: RegionalExpectation::defaults(),
+            tee_type_history: HashMap::new(),
+            tee_change_hold_epochs: None,
+            tee_change_holds: HashMap::new(),
+            warmup_epochs: 0,
+            registered_epochs: HashMap::new(),
+            metrics_history_len: 0,
+            metrics_history: VecDeque::new(),
+            policy_snapshots: BTreeMap::new(),
         }
+    }
 
-        Ok(selected)
+    // A validator sharing this one's clock, trust config, registry and
+    // latency graph but validating under `policy`, for asking what a policy
+    // change would do without applying it
+    pub(crate) fn with_policy_view(&self, policy: ValidationPolicy) -> Self {
+        let mut view = Self::with_policy(self.minimum_nodes, policy);
+        view.network_id = self.network_id.clone();
+        view.current_epoch = self.current_epoch;
+        view.genesis_time = self.genesis_time;
+        view.epoch_length_secs = self.epoch_length_secs;
+        view.shard_epoch_offsets_secs = self.shard_epoch_offsets_secs.clone();
+        view.clock = self.clock.clone();
+        view.clock_anchor = self.clock_anchor;
+        view.shard_boundaries = self.shard_boundaries.clone();
+        view.tee_verifiers = self.tee_verifiers.clone();
+        view.nodes = self.nodes.clone();
+        view.pending_nodes = self.pending_nodes.clone();
+        view.latency_matrix = self.latency_matrix.clone();
+        view.latency_timestamps = self.latency_timestamps.clone();
+        view.triangle_slack_ms = self.triangle_slack_ms;
+        view
     }
 
-    // Compute VRF input for deterministic randomness
-    fn compute_vrf_input(&self, epoch: u64, pubkey: &PublicKey) -> [u8; 32] {
-        let mut hasher = Sha3_256::new();
-        hasher.update(b"MESHX_VRF_INPUT");
-        hasher.update(epoch.to_le_bytes());
-        hasher.update(pubkey.as_bytes());
-        
-        let mut output = [0u8; 32];
-        output.copy_from_slice(&hasher.finalize());
-        output
+    // Replace the time source, re-anchoring wall time to it
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock_anchor = ClockAnchor::new(clock.as_ref());
+        self.clock = clock;
     }
 
-    // Compute VRF output (simplified - real implementation needs VRF)
-    fn compute_vrf_output(&self, input: &[u8; 32], pubkey: &PublicKey) -> [u8; 32] {
-        let mut hasher = Sha3_256::new();
-        hasher.update(b"MESHX_VRF_OUTPUT");
-        hasher.update(input);
-        hasher.update(pubkey.as_bytes());
-        
-        let mut output = [0u8; 32];
-        output.copy_from_slice(&hasher.finalize());
-        output
+    // Current wall time in seconds, advanced monotonically from the anchor so
+    // backwards wall-clock steps are not observed
+    pub fn now_secs(&self) -> u64 {
+        self.clock_anchor.now_secs(self.clock.as_ref())
     }
 
-    // Assign node to continental shard based on location
-    pub fn assign_shard(location: &GeoLocation) -> Shard {
-        match (location.latitude, location.longitude) {
-            (lat, lon) if lat > 15.0 && lat < 75.0 && lon > -170.0 && lon < -50.0 => {
-                Shard::NorthAmerica
-            }
-            (lat, lon) if lat > 35.0 && lat < 75.0 && lon > -15.0 && lon < 40.0 => Shard::Europe,
-            (lat, lon) if lat > -10.0 && lat < 55.0 && lon > 40.0 && lon < 150.0 => Shard::Asia,
-            (lat, lon) if lat > -60.0 && lat < 15.0 && lon > -85.0 && lon < -30.0 => {
-                Shard::SouthAmerica
-            }
-            (lat, lon) if lat > -40.0 && lat < 40.0 && lon > -20.0 && lon < 55.0 => Shard::Africa,
-            (lat, lon) if lat > -50.0 && lat < -10.0 && lon > 110.0 && lon < 180.0 => {
-                Shard::Oceania
-            }
-            (lat, _) if lat < -60.0 => Shard::Antarctica,
-            _ => Shard::NorthAmerica, // Default fallback
-        }
+    // Epoch containing the given wall time
+    pub fn current_epoch_for_time(&self, time_secs: u64) -> u64 {
+        time_secs.saturating_sub(self.genesis_time) / self.epoch_length_secs.max(1)
     }
 
-    // Get minimum stake for a shard
-    fn get_minimum_stake(&self, shard: &Shard) -> u64 {
-        match shard {
-            Shard::NorthAmerica | Shard::Europe | Shard::Asia => 100_000, // 100K MESHX
-            Shard::SouthAmerica | Shard::Africa | Shard::Oceania => 50_000, // 50K MESHX
-            Shard::Antarctica => 10_000, // 10K MESHX (encourage Antarctic nodes!)
-        }
+    // Epoch a shard is in at the given wall time. Shards enter each global
+    // epoch their offset (modulo the epoch length) after the global
+    // boundary, spreading selection work across the epoch.
+    pub fn shard_epoch_for_time(&self, shard: Shard, time_secs: u64) -> u64 {
+        self.current_epoch_for_time(time_secs.saturating_sub(self.shard_epoch_offset_secs(shard)))
     }
 
-    // Get validator count per shard
-    fn get_validator_count(&self) -> usize {
-        1000 // 1000 validators per shard
+    // Wall time at which a shard enters `epoch`
+    pub fn shard_transition_time(&self, shard: Shard, epoch: u64) -> u64 {
+        self.genesis_time
+            .saturating_add(epoch.saturating_mul(self.epoch_length_secs.max(1)))
+            .saturating_add(self.shard_epoch_offset_secs(shard))
     }
 
-    // Verify node has minimum resources
-    fn verify_resources(&self, resources: &NodeResources) -> Result<(), ValidationError> {
-        if resources.cpu_cores < 2 {
-            return Err(ValidationError::InsufficientCPU);
-        }
-        if resources.ram_gb < 4 {
-            return Err(ValidationError::InsufficientRAM);
-        }
-        if resources.storage_gb < 100 {
-            return Err(ValidationError::InsufficientStorage);
+    fn shard_epoch_offset_secs(&self, shard: Shard) -> u64 {
+        self.shard_epoch_offsets_secs
+            .get(&shard)
+            .map_or(0, |offset| offset % self.epoch_length_secs.max(1))
+    }
+
+    // Move to the epoch implied by the clock. Forward wall-clock corrections
+    // are adopted, but the epoch never moves backwards.
+    pub fn advance_epoch(&mut self) -> u64 {
+        self.clock_anchor.resync(self.clock.as_ref());
+        let epoch = self.current_epoch_for_time(self.now_secs());
+        if epoch < self.current_epoch {
+            tracing::warn!(
+                current_epoch = self.current_epoch,
+                derived_epoch = epoch,
+                "clock implies an earlier epoch; keeping current epoch"
+            );
         }
-        if resources.bandwidth_mbps < 10 {
-            return Err(ValidationError::InsufficientBandwidth);
+        self.enter_epoch(epoch.max(self.current_epoch))
+    }
+
+    // Move each shard into the epoch implied by the clock and its offset,
+    // returning the shards that transitioned with their new epoch. A shard
+    // never runs ahead of the global epoch or moves backwards.
+    pub fn advance_shard_epochs(&mut self) -> Vec<(Shard, u64)> {
+        let now = self.now_secs();
+        let mut transitioned = Vec::new();
+        for shard in Shard::all() {
+            let epoch = self
+                .shard_epoch_for_time(shard, now)
+                .min(self.current_epoch);
+            if epoch > self.shard_epoch(shard) {
+                self.shard_epochs.insert(shard, epoch);
+                transitioned.push((shard, epoch));
+            }
         }
+        transitioned
+    }
 
-        Ok(())
+    // Epoch a shard has entered, as last advanced
+    pub fn shard_epoch(&self, shard: Shard) -> u64 {
+        self.shard_epochs.get(&shard).copied().unwrap_or(0)
     }
 
-    // Placeholder TEE verification methods
-    fn verify_sgx_quote(&self, quote: &[u8]) -> Result<(), ValidationError> {
-        // TODO: Implement Intel SGX quote verification
-        // This would involve EPID/DCAP verification
-        if quote.is_empty() {
-            return Err(ValidationError::InvalidQuote);
+    // Epoch-boundary bookkeeping for moving to `epoch`, which must not be
+    // behind the current one
+    pub(crate) fn enter_epoch(&mut self, epoch: u64) -> u64 {
+        let entered = epoch > self.current_epoch;
+        self.roll_epoch_seed(self.current_epoch, epoch);
+        self.current_epoch = epoch;
+        self.settle_unstakes(epoch);
+        self.settle_provisional_migrations(epoch);
+        if entered {
+            self.record_metrics_snapshot();
+            self.record_policy_snapshot_if_changed();
         }
-        Ok(())
+        epoch
     }
 
-    fn verify_trustzone_quote(&self, quote: &[u8]) -> Result<(), ValidationError> {
-        // TODO: Implement ARM TrustZone attestation verification
-        if quote.is_empty() {
-            return Err(ValidationError::InvalidQuote);
+    // Validate a node and add it to the registry. Re-registering a known key
+    // (e.g. after the node lost its local state) reconciles with the
+    // existing record rather than replacing it.
+    pub fn register_node(&mut self, node: MeshXNode) -> Result<(), ValidationError> {
+        if self.is_banned(&node.pubkey, self.current_epoch) {
+            return Err(ValidationError::Banned);
         }
-        Ok(())
+        let outcome = self.validate_with_backoff(&node)?;
+        self.admit_node(node, outcome)
     }
 
-    fn verify_secure_enclave_quote(&self, quote: &[u8]) -> Result<(), ValidationError> {
-        // TODO: Implement Apple Secure Enclave verification
-        if quote.is_empty() {
-            return Err(ValidationError::InvalidQuote);
+    // Enter a validated node into the registry, reconciling it with any
+    // record already held for the key
+    fn admit_node(
+        &mut self,
+        mut node: MeshXNode,
+        outcome: ValidationOutcome,
+    ) -> Result<(), ValidationError> {
+        // The history outlives the registry entry, so a node that was banned
+        // or removed is still compared with the type it last ran on
+        let previous_tee_type = self
+            .tee_type_history
+            .get(&node.pubkey)
+            .and_then(|history| history.last())
+            .map(|(_, tee_type)| tee_type.clone())
+            .or_else(|| {
+                self.nodes
+                    .get(&node.pubkey)
+                    .map(|existing| existing.tee_attestation.tee_type.clone())
+            });
+        let joining = match self.nodes.get(&node.pubkey) {
+            Some(existing) => {
+                node = reconcile_registration(existing, node)?;
+                self.check_resource_fluctuation(&existing.resources, &node.resources)?;
+                false
+            }
+            None => {
+                self.bootstrap_reputation(&mut node);
+                true
+            }
+        };
+        self.record_attestation_timestamp(&node.pubkey, &node.tee_attestation)?;
+        self.end_expired_ban(&mut node);
+        if joining {
+            self.registered_epochs
+                .insert(node.pubkey, self.current_epoch);
         }
+        self.track_tee_type(&node, previous_tee_type.as_ref());
+        self.record_trajectory_point(&node);
+        node.reputation_score =
+            outcome.penalized_reputation(node.reputation_score, self.policy.soft_failure_penalty);
+        self.nodes.insert(node.pubkey, node);
         Ok(())
     }
 
-    fn verify_sev_quote(&self, quote: &[u8]) -> Result<(), ValidationError> {
-        // TODO: Implement AMD SEV attestation verification
-        if quote.is_empty() {
-            return Err(ValidationError::InvalidQuote);
+    // First phase of registration for a node that may not yet have enough
+    // latency anchors: everything except location is verified, and the node
+    // waits in the pending pool (never selected) until promoted. A node
+    // already registered re-registers through `register_node` instead.
+    pub fn register_node_pending(&mut self, mut node: MeshXNode) -> Result<(), ValidationError> {
+        if self.nodes.contains_key(&node.pubkey) {
+            return self.register_node(node);
+        }
+        if self.is_banned(&node.pubkey, self.current_epoch) {
+            return Err(ValidationError::Banned);
         }
+        self.verify_tee_attestation(&node.tee_attestation)?;
+        self.verify_stake(&node)?;
+        self.verify_resources_for_stake(&node.resources, node.stake_amount)?;
+        self.verify_metadata(&node)?;
+        self.record_attestation_timestamp(&node.pubkey, &node.tee_attestation)?;
+        self.bootstrap_reputation(&mut node);
+        self.end_expired_ban(&mut node);
+        self.pending_nodes.insert(node.pubkey, node);
         Ok(())
     }
 
-    fn get_expected_enclave_hash(&self) -> [u8; 32] {
-        // Hash of the expected MeshX validator code
-        [0x42; 32] // Placeholder
+    // Second phase: once peers have measured latency to the pending node, run
+    // full validation and move it into the active registry. A node that still
+    // lacks anchors stays pending.
+    pub fn promote_pending(&mut self, pubkey: &PublicKey) -> Result<(), ValidationError> {
+        let node = self
+            .pending_nodes
+            .get(pubkey)
+            .cloned()
+            .ok_or(ValidationError::UnknownNode)?;
+        let outcome = self.validate_with_backoff(&node)?;
+        self.admit_node(node, outcome)?;
+        self.pending_nodes.remove(pubkey);
+        Ok(())
     }
-}
 
-// Calculate distance between two geographic points (Haversine formula)
-fn haversine_distance(loc1: &GeoLocation, loc2: &GeoLocation) -> f64 {
-    const EARTH_RADIUS_M: f64 = 6_371_000.0;
+    // Validate a node's proof of physical presence. The first hard failure
+    // rejects the node; soft failures, as classified by the policy, are
+    // collected and the node admitted.
+    pub fn validate_node(&self, node: &MeshXNode) -> Result<ValidationOutcome, ValidationError> {
+        let checks = [
+            // Step 1: Verify TEE attestation
+            self.verify_tee_attestation(&node.tee_attestation),
+            // Step 2: Verify geographic location via latency
+            self.verify_location(node),
+            // Step 3: Check stake is within the plausible range for the shard
+            self.verify_stake(node),
+            // Step 4: Verify node resources
+            self.verify_resources_for_stake(&node.resources, node.stake_amount),
+            // Step 5: Check operator metadata fits the limits
+            self.verify_metadata(node),
+        ];
 
-    let lat1_rad = loc1.latitude.to_radians();
-    let lat2_rad = loc2.latitude.to_radians();
-    let delta_lat = (loc2.latitude - loc1.latitude).to_radians();
-    let delta_lon = (loc2.longitude - loc1.longitude).to_radians();
+        let mut outcome = ValidationOutcome::default();
+        for error in checks.into_iter().filter_map(Result::err) {
+            match self.policy.severity(&error) {
+                FailureSeverity::Hard => return Err(error),
+                FailureSeverity::Soft => outcome.soft_failures.push(error),
+            }
+        }
+        Ok(outcome)
+    }
 
-    let a = (delta_lat / 2.0).sin().powi(2)
-        + lat1_rad.cos() * lat2_rad.cos() * (delta_lon / 2.0).sin().powi(2);
+    // Run every check on a node without stopping at the first failure
+    pub fn validation_report(&self, node: &MeshXNode) -> ValidationReport {
+        let current_time = self.now_secs();
 
-    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+        let attestation = AttestationCheck {
+            tee_type: node.tee_attestation.tee_type.clone(),
+            age_secs: current_time as i64 - node.tee_attestation.timestamp as i64,
+            max_age_secs: self.policy.max_attestation_age_secs,
+            error: self.verify_tee_attestation(&node.tee_attestation).err(),
+        };
 
-    EARTH_RADIUS_M * c
-}
+        let triangulated = self.triangulate_node(&node.pubkey).ok();
+        let location = LocationCheck {
+            claimed: node.geo_location.clone(),
+            distance_meters: triangulated
+                .as_ref()
+                .map(|calculated| haversine_distance(calculated, &node.geo_location)),
+            triangulated,
+            measurement_count: self.measurements_to(&node.pubkey).len(),
+            error: self.verify_location(node).err(),
+        };
 
-// Validation errors
-#[derive(Debug, thiserror::Error)]
-pub enum ValidationError {
-    #[error("TEE attestation is too old")]
-    StaleAttestation,
-    #[error("Invalid enclave code hash")]
+        let required_stake = self.get_minimum_stake(&node.shard);
+        let stake = StakeCheck {
+            effective_stake: self.effective_stake_of(node),
+            required_stake,
+            error: self.verify_stake(node).err(),
+        };
+
+        let resources = &node.resources;
+        let policy = &self.policy;
+        // None means no GPU, which only fails when the policy wants one;
+        // zero memory means detection failed
+        let gpu_memory_gb = resources.gpu_memory_gb.unwrap_or(0) as u64;
+        let required_gpu_memory_gb = policy.min_gpu_memory_gb.unwrap_or(0) as u64;
+        let gpu = ResourceCheck {
+            resource: "gpu_memory_gb".to_string(),
+            actual: gpu_memory_gb,
+            required: required_gpu_memory_gb,
+            error: if resources.gpu_memory_gb == Some(0) {
+                Some(ValidationError::InvalidGpuReport)
+            } else {
+                (gpu_memory_gb < required_gpu_memory_gb).then_some(ValidationError::InsufficientGPU)
+            },
+        };
+        let mut resources: Vec<ResourceCheck> = [
+            (
+                "cpu_cores",
+                resources.cpu_cores as u64,
+                policy.min_cpu_cores_for(node.stake_amount) as u64,
+                ValidationError::InsufficientCPU,
+            ),
+            (
+                "ram_gb",
+                resources.ram_gb as u64,
+                policy.min_ram_gb_for(node.stake_amount) as u64,
+                ValidationError::InsufficientRAM,
+            ),
+            (
+                "storage_gb",
+                resources.storage_gb,
+                policy.min_storage_gb,
+                ValidationError::InsufficientStorage,
+            ),
+            (
+                "bandwidth_mbps",
+                resources.bandwidth_mbps as u64,
+                policy.min_bandwidth_mbps as u64,
+                ValidationError::InsufficientBandwidth,
+            ),
+        ]
+        .into_iter()
+        .map(|(resource, actual, required, error)| ResourceCheck {
+            resource: resource.to_string(),
+            actual,
+            required,
+            error: (actual < required).then_some(error),
+        })
+        .collect();
+        resources.push(gpu);
+
+        let metadata = MetadataCheck {
+            total_bytes: node
+                .metadata
+                .iter()
+                .map(|(key, value)| key.len() + value.len())
+                .sum(),
+            max_total_bytes: policy.metadata_limits.max_total_bytes,
+            error: self.verify_metadata(node).err(),
+        };
+
+        ValidationReport {
+            pubkey: node.pubkey,
+            shard: node.shard,
+            attestation,
+            location,
+            stake,
+            resources,
+            metadata,
+        }
+    }
+
+    // Report what validation would conclude for the node with the overrides
+    // applied, without touching live state
+    pub fn simulate_validation(
+        &self,
+        base: &MeshXNode,
+        overrides: NodeOverrides,
+    ) -> ValidationReport {
+        let mut node = base.clone();
+        if let Some(stake_amount) = overrides.stake_amount {
+            node.stake_amount = stake_amount;
+        }
+        if let Some(geo_location) = overrides.geo_location {
+            node.shard = self.shard_for_location(&geo_location);
+            node.geo_location = geo_location;
+        }
+        if let Some(shard) = overrides.shard {
+            node.shard = shard;
+        }
+        if let Some(resources) = overrides.resources {
+            node.resources = resources;
+        }
+        if let Some(tee_attestation) = overrides.tee_attestation {
+            node.tee_attestation = tee_attestation;
+        }
+        self.validation_report(&node)
+    }
+
+    // Stake must meet the shard minimum without exceeding the share of total
+    // supply any single node could plausibly hold, or outweighing the
+    // capacity the node contributes
+    pub(crate) fn verify_stake(&self, node: &MeshXNode) -> Result<(), ValidationError> {
+        if node.stake_amount.base_units() as f64
+            > self.policy.max_stake_fraction * self.policy.total_supply.base_units() as f64
+        {
+            return Err(ValidationError::ImplausibleStake);
+        }
+        if let Some(max_per_unit) = self.policy.max_stake_per_resource_unit {
+            let units = node.resources.cpu_cores as u128 + node.resources.ram_gb as u128;
+            if node.stake_amount.base_units() as u128 > max_per_unit.base_units() as u128 * units {
+                return Err(ValidationError::StakeResourceImbalance);
+            }
+        }
+        if node.stake_amount < self.get_minimum_stake(&node.shard) {
+            return Err(ValidationError::InsufficientStake);
+        }
+        Ok(())
+    }
+
+    // Verify TEE attestation is valid and recent
+    pub(crate) fn verify_tee_attestation(
+        &self,
+        attestation: &TeeAttestation,
+    ) -> Result<(), ValidationError> {
+        let current_time = self.now_secs();
+        let expected_hash = self.get_expected_enclave_hash();
+
+        self.check_attestation(attestation, current_time, &expected_hash)
+    }
+
+    // Verify many attestations at once, e.g. during bulk onboarding at network
+    // launch. The clock read and expected enclave hash are shared across the
+    // batch, and the quotes passing the cheap checks go to the verifier
+    // registry as one batch, which shares per-quote work between them and
+    // runs it in parallel. Results are in input order.
+    pub fn verify_attestation_batch(
+        &self,
+        attestations: &[TeeAttestation],
+    ) -> Vec<Result<(), ValidationError>> {
+        let current_time = self.now_secs();
+        let expected_hash = self.get_expected_enclave_hash();
+
+        let mut results: Vec<Result<(), ValidationError>> = attestations
+            .iter()
+            .map(|attestation| {
+                self.check_attestation_claims(attestation, current_time, &expected_hash)
+            })
+            .collect();
+        let passed: Vec<usize> = (0..attestations.len())
+            .filter(|index| results[*index].is_ok())
+            .collect();
+        let quotes: Vec<&TeeAttestation> =
+            passed.iter().map(|index| &attestations[*index]).collect();
+        let verified = self.tee_verifiers.verify_attestation_batch(
+            &quotes,
+            &expected_hash,
+            self.attestation_batch_threads,
+        );
+        for (index, result) in passed.into_iter().zip(verified) {
+            results[index] = result;
+        }
+        results
+    }
+
+    fn check_attestation(
+        &self,
+        attestation: &TeeAttestation,
+        current_time: u64,
+        expected_hash: &[u8; 32],
+    ) -> Result<(), ValidationError> {
+        self.check_attestation_claims(attestation, current_time, expected_hash)?;
+        self.tee_verifiers
+            .verify_attestation(attestation, expected_hash)
+    }
+
+    // The attestation's timestamp and enclave hash, checked before its quote
+    fn check_attestation_claims(
+        &self,
+        attestation: &TeeAttestation,
+        current_time: u64,
+        expected_hash: &[u8; 32],
+    ) -> Result<(), ValidationError> {
+        // Check attestation age. A small lead over our clock is skew; a
+        // larger one can't be a genuine quote.
+        if attestation.timestamp
+            > current_time.saturating_add(self.policy.max_attestation_future_secs)
+        {
+            return Err(ValidationError::FutureAttestation);
+        }
+        if current_time.saturating_sub(attestation.timestamp) > self.policy.max_attestation_age_secs
+        {
+            return Err(ValidationError::StaleAttestation);
+        }
+
+        // Stub attestations from test setups must never pass in production
+        if !self.policy.allow_placeholder_attestations
+            && (attestation.enclave_hash == [0; 32]
+                || self
+                    .policy
+                    .placeholder_enclave_hashes
+                    .contains(&attestation.enclave_hash))
+        {
+            return Err(ValidationError::PlaceholderAttestation);
+        }
+
+        // Verify enclave hash matches expected MeshX code
+        if attestation.enclave_hash != *expected_hash {
+            return Err(ValidationError::InvalidEnclaveCode);
+        }
+        Ok(())
+    }
+
+    // Within an epoch a node's successive attestations must not go back in
+    // time; an earlier timestamp indicates a replayed or manipulated quote.
+    // The first attestation seen in a new epoch starts the ordering afresh.
+    pub fn record_attestation_timestamp(
+        &mut self,
+        pubkey: &PublicKey,
+        attestation: &TeeAttestation,
+    ) -> Result<(), ValidationError> {
+        if let Some((epoch, last_seen)) = self.attestation_timestamps.get(pubkey) {
+            if *epoch == self.current_epoch && attestation.timestamp < *last_seen {
+                return Err(ValidationError::AttestationRegression);
+            }
+        }
+        self.attestation_timestamps
+            .insert(*pubkey, (self.current_epoch, attestation.timestamp));
+        Ok(())
+    }
+
+    // Verify node's claimed location using latency triangulation
+    fn verify_location(&self, node: &MeshXNode) -> Result<(), ValidationError> {
+        let peers: Vec<(&MeshXNode, u32)> = self
+            .measurements_to(&node.pubkey)
+            .into_iter()
+            .filter_map(|(peer, latency_ms)| Some((self.nodes.get(peer)?, latency_ms)))
+            .collect();
+        self.check_location_plausible(node, &peers)?;
+        let calculated_location = self.triangulate_node(&node.pubkey)?;
+        self.check_triangulated_location(node, &calculated_location)
+    }
+
+    // Location checks that come before triangulation, given the located
+    // peers that measured the node and their latencies. Shared by registry
+    // validation and presence bundles.
+    pub(crate) fn check_location_plausible(
+        &self,
+        node: &MeshXNode,
+        peers: &[(&MeshXNode, u32)],
+    ) -> Result<(), ValidationError> {
+        self.check_habitable(node)?;
+        self.check_latency_floor(node, peers)
+    }
+
+    // Location checks against where latency places the node
+    pub(crate) fn check_triangulated_location(
+        &self,
+        node: &MeshXNode,
+        calculated_location: &GeoLocation,
+    ) -> Result<(), ValidationError> {
+        let claimed_location = &node.geo_location;
+
+        // Check if calculated location matches claimed (within accuracy)
+        let distance = haversine_distance(calculated_location, claimed_location);
+        if distance > claimed_location.accuracy_meters as f64 {
+            return Err(ValidationError::LocationMismatch);
+        }
+
+        // Coordinates copied from a valid location elsewhere pass the check
+        // above when accuracy is loose, but latency still places the node in
+        // another shard
+        if let Some(tolerance_m) = self.policy.triangulated_shard_tolerance_m {
+            if !self.shard_within(calculated_location, tolerance_m, node.shard) {
+                return Err(ValidationError::TriangulatedShardMismatch);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Light in fiber needs distance / FIBER_KM_PER_MS to cover even the
+    // great-circle path between two located nodes, and real routes are
+    // longer, so a faster measurement can't be genuine. The distance is
+    // reduced by both nodes' claimed accuracy to allow for honest error.
+    fn check_latency_floor(
+        &self,
+        node: &MeshXNode,
+        peers: &[(&MeshXNode, u32)],
+    ) -> Result<(), ValidationError> {
+        let Some(fraction) = self.policy.min_latency_fraction else {
+            return Ok(());
+        };
+        for (peer, latency_ms) in peers {
+            let uncertainty_m =
+                node.geo_location.accuracy_meters as f64 + peer.geo_location.accuracy_meters as f64;
+            let distance_km = (haversine_distance(&node.geo_location, &peer.geo_location)
+                - uncertainty_m)
+                .max(0.0)
+                / 1000.0;
+            let floor_ms = distance_km / FIBER_KM_PER_MS * fraction;
+            if (*latency_ms as f64) < floor_ms {
+                return Err(ValidationError::LatencyBelowPhysicalMinimum);
+            }
+        }
+        Ok(())
+    }
+
+    // Whether the point, or any point `radius_m` from it in one of eight
+    // compass directions, falls in the shard. Lets nodes triangulated just
+    // across a shard boundary keep their declared shard.
+    fn shard_within(&self, location: &GeoLocation, radius_m: f64, shard: Shard) -> bool {
+        const METERS_PER_DEGREE: f64 = 111_320.0;
+        let lat_step = radius_m / METERS_PER_DEGREE;
+        let lon_step = lat_step / location.latitude.to_radians().cos().max(0.01);
+        let diagonal = std::f64::consts::FRAC_1_SQRT_2;
+        let offsets = [
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (-1.0, 0.0),
+            (0.0, 1.0),
+            (0.0, -1.0),
+            (diagonal, diagonal),
+            (diagonal, -diagonal),
+            (-diagonal, diagonal),
+            (-diagonal, -diagonal),
+        ];
+        offsets.iter().any(|(north, east)| {
+            let probe = GeoLocation {
+                latitude: (location.latitude + north * lat_step).clamp(-90.0, 90.0),
+                longitude: normalize_longitude(location.longitude + east * lon_step),
+                accuracy_meters: location.accuracy_meters,
+            };
+            self.shard_for_location(&probe) == shard
+        })
+    }
+
+    // Latency measurements towards a node, as (measuring peer, latency)
+    fn measurements_to(&self, node_pubkey: &PublicKey) -> Vec<(&PublicKey, u32)> {
+        self.latency_matrix
+            .iter()
+            .filter(|((_, to), _)| to == node_pubkey)
+            .map(|((from, _), latency)| (from, *latency))
+            .collect()
+    }
+
+    // Triangulate a node's position from the latency measurements towards it
+    fn triangulate_node(&self, node_pubkey: &PublicKey) -> Result<GeoLocation, ValidationError> {
+        let mut measurements = self.measurements_to(node_pubkey);
+        if measurements.len() < self.policy.min_latency_measurements {
+            return Err(ValidationError::InsufficientLatencyData);
+        }
+
+        let limit = self.policy.max_triangulation_anchors;
+        if limit > 0 && measurements.len() > limit {
+            let chosen = self.select_triangulation_anchors(node_pubkey, limit);
+            measurements.retain(|(peer, _)| chosen.contains(peer));
+        }
+        self.triangulate_position(&measurements)
+    }
+
+    // Triangulate every pending node at once, e.g. after a partition heals.
+    // Each triangulation only reads shared state, so they are spread across
+    // `triangulation_threads` workers.
+    pub fn triangulate_all_pending(
+        &self,
+    ) -> HashMap<PublicKey, Result<GeoLocation, ValidationError>> {
+        let pending: Vec<&PublicKey> = self.pending_nodes.keys().collect();
+        let threads = self.triangulation_threads.max(1);
+        let chunk_size = pending.len().div_ceil(threads).max(1);
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = pending
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(|| {
+                        chunk
+                            .iter()
+                            .map(|pubkey| (**pubkey, self.triangulate_node(pubkey)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("triangulation worker panicked"))
+                .collect()
+        })
+    }
+
+    // Choose up to k anchors of a node with the best spatial spread, i.e. the
+    // lowest geometric dilution of precision around a preliminary estimate
+    // from all anchors. Greedy: start from the nearest anchor (every single
+    // anchor ties at infinite GDOP) and repeatedly add the one that lowers
+    // GDOP most.
+    pub fn select_triangulation_anchors(&self, pubkey: &PublicKey, k: usize) -> Vec<PublicKey> {
+        let measurements = self.measurements_to(pubkey);
+        let mut candidates: Vec<(&MeshXNode, u32)> = measurements
+            .iter()
+            .filter_map(|(peer_key, latency_ms)| {
+                self.nodes.get(*peer_key).map(|peer| (peer, *latency_ms))
+            })
+            .collect();
+        candidates.sort_by(|(a, a_latency), (b, b_latency)| {
+            a_latency
+                .cmp(b_latency)
+                .then_with(|| a.pubkey.as_bytes().cmp(b.pubkey.as_bytes()))
+        });
+        let Ok(estimate) = self.triangulate_anchors(&candidates) else {
+            return Vec::new();
+        };
+
+        let mut chosen: Vec<&MeshXNode> = Vec::new();
+        while chosen.len() < k && chosen.len() < candidates.len() {
+            let next = candidates
+                .iter()
+                .map(|(peer, _)| *peer)
+                .filter(|peer| !chosen.iter().any(|c| c.pubkey == peer.pubkey))
+                .min_by(|a, b| {
+                    let score = |peer: &MeshXNode| {
+                        let mut set: Vec<_> = chosen.iter().map(|c| &c.geo_location).collect();
+                        set.push(&peer.geo_location);
+                        geometric_dilution(&estimate, &set)
+                    };
+                    score(a).total_cmp(&score(b))
+                })
+                .expect("unchosen candidate remains");
+            chosen.push(next);
+        }
+        chosen.into_iter().map(|peer| peer.pubkey).collect()
+    }
+
+    // Calculate position from latency measurements
+    fn triangulate_position(
+        &self,
+        measurements: &[(&PublicKey, u32)],
+    ) -> Result<GeoLocation, ValidationError> {
+        let anchors: Vec<_> = measurements
+            .iter()
+            .filter_map(|(peer_key, latency_ms)| {
+                self.nodes.get(*peer_key).map(|peer| (peer, *latency_ms))
+            })
+            .collect();
+        self.triangulate_anchors(&anchors)
+    }
+
+    // Calculate position from latencies measured by known anchor nodes
+    pub(crate) fn triangulate_anchors(
+        &self,
+        anchors: &[(&MeshXNode, u32)],
+    ) -> Result<GeoLocation, ValidationError> {
+        // Simplified triangulation based on speed of light
+        // Real implementation would use more sophisticated algorithms
+
+        let mut lat_sum = 0.0;
+        let mut lon_sum = 0.0;
+        let mut weight_sum = 0.0;
+        let influence = self.policy.anchor_reputation_influence.clamp(0.0, 1.0);
+
+        for (peer, latency_ms) in anchors {
+            // Convert one-way latency to approximate distance
+            let distance_km = (*latency_ms as f64) * FIBER_KM_PER_MS;
+
+            // Weight by inverse distance, scaled down for low-reputation
+            // anchors as far as the policy allows
+            let reputation = (peer.reputation_score as f64).clamp(0.0, 1.0);
+            let weight = (1.0 - influence + influence * reputation) / distance_km;
+
+            lat_sum += peer.geo_location.latitude * weight;
+            lon_sum += peer.geo_location.longitude * weight;
+            weight_sum += weight;
+        }
+        if weight_sum <= 0.0 {
+            return Err(ValidationError::InsufficientLatencyData);
+        }
+
+        Ok(GeoLocation {
+            latitude: lat_sum / weight_sum,
+            longitude: lon_sum / weight_sum,
+            accuracy_meters: 50000.0, // 50km accuracy for now
+        })
+    }
+
+    // Every node appearing in the latency matrix, sorted by key bytes
+    fn latency_graph_nodes(&self) -> Vec<PublicKey> {
+        let mut keys: Vec<PublicKey> = Vec::new();
+        for (from, to) in self.latency_matrix.keys() {
+            for key in [from, to] {
+                if !keys.contains(key) {
+                    keys.push(*key);
+                }
+            }
+        }
+        keys.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        keys
+    }
+
+    // Worst-case propagation latency across the mesh: the largest shortest-path
+    // latency between any two nodes in the latency graph. Returns None when the
+    // graph has fewer than two nodes or is disconnected.
+    pub fn network_diameter(&self) -> Option<(PublicKey, PublicKey, u32)> {
+        // Latency edges are treated as undirected, using the faster direction
+        let vertices = self.latency_graph_nodes();
+        if vertices.len() < 2 {
+            return None;
+        }
+
+        let index: HashMap<PublicKey, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (*key, i))
+            .collect();
+        let n = vertices.len();
+        let mut dist = vec![vec![u64::MAX; n]; n];
+        for (i, row) in dist.iter_mut().enumerate() {
+            row[i] = 0;
+        }
+        for ((from, to), latency) in &self.latency_matrix {
+            let (i, j) = (index[from], index[to]);
+            let latency = *latency as u64;
+            if latency < dist[i][j] {
+                dist[i][j] = latency;
+                dist[j][i] = latency;
+            }
+        }
+
+        // Floyd-Warshall; fine for shard-sized graphs
+        for k in 0..n {
+            for i in 0..n {
+                if dist[i][k] == u64::MAX {
+                    continue;
+                }
+                for j in 0..n {
+                    if dist[k][j] == u64::MAX {
+                        continue;
+                    }
+                    let through = dist[i][k] + dist[k][j];
+                    if through < dist[i][j] {
+                        dist[i][j] = through;
+                    }
+                }
+            }
+        }
+
+        let mut diameter: Option<(usize, usize, u64)> = None;
+        for (i, row) in dist.iter().enumerate() {
+            for (j, &d) in row.iter().enumerate().skip(i + 1) {
+                if d == u64::MAX {
+                    return None; // Disconnected graph
+                }
+                if diameter.is_none_or(|(_, _, longest)| d > longest) {
+                    diameter = Some((i, j, d));
+                }
+            }
+        }
+
+        diameter.map(|(i, j, d)| (vertices[i], vertices[j], d.min(u32::MAX as u64) as u32))
+    }
+
+    // Store a measurement in the latency matrix, which holds one-way
+    // latencies; round-trip measurements are halved on the way in. Each
+    // (from, to) edge keeps its newest measurement: older measurements are
+    // ignored, and one with the same timestamp is resolved against the
+    // applied latency by `latency_conflict_policy`. Returns whether the
+    // matrix changed.
+    pub fn record_latency(&mut self, measurement: &LatencyMeasurement) -> bool {
+        let edge = (measurement.from_node, measurement.to_node);
+        if let Some(applied) = self.latency_timestamps.get(&edge) {
+            if measurement.timestamp < *applied {
+                return false;
+            }
+            if measurement.timestamp == *applied {
+                return self.resolve_latency_conflict(edge, measurement.one_way_latency_ms());
+            }
+        }
+        self.latency_timestamps.insert(edge, measurement.timestamp);
+        self.latency_conflicts.remove(&edge);
+        self.latency_matrix
+            .insert(edge, measurement.one_way_latency_ms());
+        self.record_clock_sample(&measurement.from_node, measurement.timestamp);
+        true
+    }
+
+    // Latency between two nodes in either direction, preferring the faster one
+    fn measured_latency(&self, a: &PublicKey, b: &PublicKey) -> Option<u32> {
+        match (
+            self.latency_matrix.get(&(*a, *b)),
+            self.latency_matrix.get(&(*b, *a)),
+        ) {
+            (Some(ab), Some(ba)) => Some(*ab.min(ba)),
+            (Some(latency), None) | (None, Some(latency)) => Some(*latency),
+            (None, None) => None,
+        }
+    }
+
+    // Check lat(a,c) <= lat(a,b) + lat(b,c) + slack. Triples with a missing
+    // edge have nothing to contradict and are treated as consistent.
+    pub fn check_triangle_consistency(&self, a: &PublicKey, b: &PublicKey, c: &PublicKey) -> bool {
+        self.triangle_holds(a, b, c, self.triangle_slack_ms)
+    }
+
+    fn triangle_holds(&self, a: &PublicKey, b: &PublicKey, c: &PublicKey, slack_ms: u32) -> bool {
+        match (
+            self.measured_latency(a, c),
+            self.measured_latency(a, b),
+            self.measured_latency(b, c),
+        ) {
+            (Some(ac), Some(ab), Some(bc)) => ac as u64 <= ab as u64 + bc as u64 + slack_ms as u64,
+            _ => true,
+        }
+    }
+
+    // Sweep every measured triangle for gross violations, which indicate
+    // spoofed measurements or routing anomalies. Each result is (a, b, c)
+    // where the direct a-c latency exceeds the detour through b.
+    pub fn find_inconsistent_triples(
+        &self,
+        slack_ms: u32,
+    ) -> Vec<(PublicKey, PublicKey, PublicKey)> {
+        let keys = self.latency_graph_nodes();
+        let mut inconsistent = Vec::new();
+        for (i, a) in keys.iter().enumerate() {
+            for (j, b) in keys.iter().enumerate().skip(i + 1) {
+                for c in keys.iter().skip(j + 1) {
+                    // Test each edge of the triangle against the other two
+                    for (x, via, y) in [(a, b, c), (a, c, b), (b, a, c)] {
+                        if !self.triangle_holds(x, via, y, slack_ms) {
+                            inconsistent.push((*x, *via, *y));
+                        }
+                    }
+                }
+            }
+        }
+        inconsistent
+    }
+
+    // Select validators for next epoch using VRF
+    pub fn select_validators(&self, epoch: u64) -> Result<Vec<PublicKey>, ValidationError> {
+        self.select_validators_with_shortfalls(epoch)
+            .map(|(selected, _)| selected)
+            .map_err(|(error, _)| error)
+    }
+
+    // `select_validators`, also reporting the shards whose selection couldn't
+    // meet `min_tee_types_per_shard` and how `diversity_fallback` handled them.
+    // The shortfalls come with the error too, since seats left unfilled can
+    // be what pushed the selection below `minimum_nodes`.
+    pub fn select_validators_with_shortfalls(
+        &self,
+        epoch: u64,
+    ) -> Result<(Vec<PublicKey>, Vec<DiversityShortfall>), ShortfallError> {
+        let candidates = self.ranked_candidates(epoch);
+
+        // Select the top N of the stake-weighted ranking
+        let count = self.get_validator_count().min(candidates.len());
+        let (top, reserve) = candidates.split_at(count);
+        let mut selected: Vec<_> = top
+            .iter()
+            .copied()
+            .filter(|node| self.validate_node(node).is_ok())
+            .collect();
+        let shortfalls = self.enforce_tee_diversity(&mut selected, reserve);
+
+        if selected.len() < self.minimum_nodes {
+            return Err((ValidationError::InsufficientValidators, shortfalls));
+        }
+
+        Ok((
+            selected.into_iter().map(|node| node.pubkey).collect(),
+            shortfalls,
+        ))
+    }
+
+    // Candidates for the epoch in selection order
+    pub(crate) fn ranked_candidates(&self, epoch: u64) -> Vec<&MeshXNode> {
+        let mut candidates = self.selection_candidates(epoch);
+
+        // Sort by VRF output so equal draws break ties deterministically
+        candidates.sort_by_key(|node| {
+            let vrf_input = self.compute_vrf_input(epoch, &node.pubkey);
+            self.compute_vrf_output(&vrf_input, &node.pubkey)
+        });
+        self.apply_weighted_draw(epoch, &mut candidates);
+        candidates
+    }
+
+    // Reorder candidates by weighted VRF draw, u^(1 / weight) with u one
+    // minus the VRF output as a fraction, highest first, so each node's
+    // chance of a seat follows its `draw_weight` and equal weights keep VRF
+    // order. Priorities are compared as ln(u) / weight, which orders the
+    // same without underflowing for large weights.
+    fn apply_weighted_draw(&self, epoch: u64, candidates: &mut [&MeshXNode]) {
+        let tee_weights = self.tee_balance_weights(epoch, candidates);
+        let priority = |node: &MeshXNode| {
+            let weight = self.draw_weight(node, tee_weights.as_ref());
+            let vrf_input = self.compute_vrf_input(epoch, &node.pubkey);
+            let output = self.compute_vrf_output(&vrf_input, &node.pubkey);
+            let mut draw = [0u8; 8];
+            draw.copy_from_slice(&output[..8]);
+            let fraction = (u64::from_be_bytes(draw) as f64 + 1.0) / (u64::MAX as f64 + 2.0);
+            (1.0 - fraction).ln() / weight
+        };
+        let mut keyed: Vec<(f64, &MeshXNode)> = candidates
+            .iter()
+            .map(|node| (priority(node), *node))
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        for (slot, (_, node)) in candidates.iter_mut().zip(keyed) {
+            *slot = node;
+        }
+    }
+
+    // A node's weight in the selection draw: its effective stake, scaled by
+    // its TEE type's balancing weight when balancing applies. A node whose
+    // TEE type holds share s of its shard's previous selection, against a
+    // fair share f of one over the shard's candidate TEE types, is scaled by
+    // 1 / (1 + strength * (s / f - 1)) when s > f.
+    fn draw_weight(
+        &self,
+        node: &MeshXNode,
+        tee_weights: Option<&HashMap<(Shard, TeeType), f64>>,
+    ) -> f64 {
+        let tee_weight = tee_weights.map_or(1.0, |weights| {
+            weights[&(node.shard, node.tee_attestation.tee_type.clone())]
+        });
+        self.effective_stake_of(node).base_units() as f64 * tee_weight
+    }
+
+    // Balancing weight of each shard's candidate TEE types under
+    // `tee_balance_strength`, or None when balancing is off or no previous
+    // selection was recorded. Shares come from the shard's part of the
+    // selection recorded for the epoch before, which every node agrees on,
+    // against a fair share over the shard's own candidate types.
+    fn tee_balance_weights(
+        &self,
+        epoch: u64,
+        candidates: &[&MeshXNode],
+    ) -> Option<HashMap<(Shard, TeeType), f64>> {
+        if self.tee_balance_strength <= 0.0 {
+            return None;
+        }
+        let previous = self.recorded_selection(epoch.checked_sub(1)?);
+        if previous.is_empty() {
+            return None;
+        }
+
+        let mut weights = HashMap::new();
+        for node in candidates {
+            let key = (node.shard, node.tee_attestation.tee_type.clone());
+            if weights.contains_key(&key) {
+                continue;
+            }
+            let mut candidate_types: Vec<&TeeType> = Vec::new();
+            for other in candidates.iter().filter(|other| other.shard == node.shard) {
+                if !candidate_types.contains(&&other.tee_attestation.tee_type) {
+                    candidate_types.push(&other.tee_attestation.tee_type);
+                }
+            }
+            let fair_share = 1.0 / candidate_types.len().max(1) as f64;
+            let shard_set: Vec<&&MeshXNode> = previous
+                .iter()
+                .filter(|selected| selected.shard == node.shard)
+                .collect();
+            let weight = if shard_set.is_empty() {
+                1.0
+            } else {
+                let same_type = shard_set
+                    .iter()
+                    .filter(|selected| selected.tee_attestation.tee_type == key.1)
+                    .count();
+                let share = same_type as f64 / shard_set.len() as f64;
+                let excess = (share / fair_share - 1.0).max(0.0);
+                1.0 / (1.0 + self.tee_balance_strength * excess)
+            };
+            weights.insert(key, weight);
+        }
+        Some(weights)
+    }
+
+    // Registered nodes recorded as selected in `epoch`
+    fn recorded_selection(&self, epoch: u64) -> Vec<&MeshXNode> {
+        self.selection_history
+            .iter()
+            .filter(|(_, history)| history.contains(&(epoch, true)))
+            .filter_map(|(pubkey, _)| self.nodes.get(pubkey))
+            .collect()
+    }
+
+    // Registered nodes that may be drawn in the epoch's selection
+    pub(crate) fn selection_candidates(&self, epoch: u64) -> Vec<&MeshXNode> {
+        self.nodes
+            .values()
+            .filter(|node| !self.in_slash_cooldown(&node.pubkey, epoch))
+            .filter(|node| !self.in_liveness_demotion(&node.pubkey, epoch))
+            .filter(|node| !self.provisional_migrations.contains_key(&node.pubkey))
+            .filter(|node| !self.in_tee_change_hold(&node.pubkey, epoch))
+            .filter(|node| !self.in_warmup(&node.pubkey, epoch))
+            .collect()
+    }
+
+    // Chance that a node is selected in the next epoch: the validator count
+    // times its share of the draw weight across the ranked candidates, which
+    // follows effective stake unless TEE balancing down-weights its type.
+    // This is the first-seat share, a close estimate while the seats are few
+    // next to the candidates. Seats go to the
+    // top of the ranking before validity is checked, so invalid candidates
+    // still dilute the draw. None for nodes that can't be selected at all.
+    pub fn selection_probability(&self, pubkey: &PublicKey) -> Option<f64> {
+        let epoch = self.current_epoch + 1;
+        let ranked = self.ranked_candidates(epoch);
+        let node = ranked.iter().find(|node| node.pubkey == *pubkey)?;
+        if self.validate_node(node).is_err() {
+            return None;
+        }
+        let tee_weights = self.tee_balance_weights(epoch, &ranked);
+        let weight = |node: &MeshXNode| self.draw_weight(node, tee_weights.as_ref());
+        let total: f64 = ranked.iter().map(|node| weight(node)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        Some((self.get_validator_count() as f64 * weight(node) / total).min(1.0))
+    }
+
+    // Mean number of epochs until the node is next selected, for earnings
+    // estimates; selection is memoryless, so this is 1 / probability
+    pub fn expected_epochs_until_selection(&self, pubkey: &PublicKey) -> Option<f64> {
+        self.selection_probability(pubkey)
+            .filter(|probability| *probability > 0.0)
+            .map(|probability| 1.0 / probability)
+    }
+
+    // The expected wait converted to wall time via the epoch length; None
+    // when the wait is too long for a Duration
+    pub fn expected_time_until_selection(&self, pubkey: &PublicKey) -> Option<Duration> {
+        self.expected_epochs_until_selection(pubkey)
+            .and_then(|epochs| {
+                Duration::try_from_secs_f64(epochs * self.epoch_length_secs as f64).ok()
+            })
+    }
+
+    // Hedge against a single-vendor TEE vulnerability: while a shard's
+    // selection has fewer than `min_tee_types_per_shard` TEE types, swap its
+    // lowest-ranked node of a repeated type for the best-ranked reserve node
+    // of a missing type. A shard whose reserve runs out of such nodes falls
+    // back to `diversity_fallback`.
+    fn enforce_tee_diversity<'a>(
+        &self,
+        selected: &mut Vec<&'a MeshXNode>,
+        reserve: &[&'a MeshXNode],
+    ) -> Vec<DiversityShortfall> {
+        let mut shortfalls = Vec::new();
+        if self.min_tee_types_per_shard <= 1 {
+            return shortfalls;
+        }
+        let shards: Vec<Shard> = selected.iter().map(|node| node.shard).collect();
+        for shard in Shard::all()
+            .into_iter()
+            .filter(|shard| shards.contains(shard))
+        {
+            let tee_types = loop {
+                let mut tee_types: Vec<&TeeType> = Vec::new();
+                for node in selected.iter().filter(|node| node.shard == shard) {
+                    if !tee_types.contains(&&node.tee_attestation.tee_type) {
+                        tee_types.push(&node.tee_attestation.tee_type);
+                    }
+                }
+                if tee_types.len() >= self.min_tee_types_per_shard {
+                    break tee_types.len();
+                }
+
+                let Some(replacement) = reserve.iter().find(|node| {
+                    node.shard == shard
+                        && !tee_types.contains(&&node.tee_attestation.tee_type)
+                        && self.validate_node(node).is_ok()
+                }) else {
+                    break tee_types.len();
+                };
+                let Some(position) = selected.iter().rposition(|node| {
+                    node.shard == shard
+                        && selected
+                            .iter()
+                            .filter(|other| {
+                                other.shard == shard
+                                    && other.tee_attestation.tee_type
+                                        == node.tee_attestation.tee_type
+                            })
+                            .count()
+                            > 1
+                }) else {
+                    break tee_types.len();
+                };
+                selected[position] = replacement;
+            };
+            if tee_types < self.min_tee_types_per_shard {
+                shortfalls.push(self.apply_diversity_fallback(shard, tee_types, selected));
+            }
+        }
+        shortfalls
+    }
+
+    // Slash a node's stake and start its selection cooldown. Returns the
+    // amount actually removed.
+    pub fn slash_node(
+        &mut self,
+        pubkey: &PublicKey,
+        amount: StakeAmount,
+    ) -> Result<StakeAmount, ValidationError> {
+        let node = self
+            .nodes
+            .get_mut(pubkey)
+            .ok_or(ValidationError::UnknownNode)?;
+        let slashed = amount.min(node.stake_amount);
+        node.stake_amount = node.stake_amount.saturating_sub(slashed);
+        self.slashed_at.insert(*pubkey, self.current_epoch);
+        Ok(slashed)
+    }
+
+    // A node slashed in epoch N sits out epochs N..=N+cooldown, even if it
+    // tops its stake back up in the meantime
+    pub fn in_slash_cooldown(&self, pubkey: &PublicKey, epoch: u64) -> bool {
+        match self.slashed_at.get(pubkey) {
+            Some(&slash_epoch) => {
+                epoch >= slash_epoch && epoch - slash_epoch <= self.slash_cooldown_epochs
+            }
+            None => false,
+        }
+    }
+
+    // Compute VRF input for deterministic randomness
+    fn compute_vrf_input(&self, epoch: u64, pubkey: &PublicKey) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_VRF_INPUT");
+        hasher.update(epoch.to_le_bytes());
+        hasher.update(pubkey.as_bytes());
+        if !self.network_id.is_empty() {
+            hasher.update((self.network_id.len() as u64).to_le_bytes());
+            hasher.update(self.network_id.as_bytes());
+        }
+        
+        let mut output = [0u8; 32];
+        output.copy_from_slice(&hasher.finalize());
+        output
+    }
+
+    // A node's VRF evaluation for an epoch, as it would be gossiped
+    pub fn vrf_prove(&self, epoch: u64, pubkey: &PublicKey) -> VrfProof {
+        let input = self.compute_vrf_input(epoch, pubkey);
+        VrfProof {
+            output: self.compute_vrf_output(&input, pubkey),
+            input,
+            proof: Vec::new(), // The simplified VRF has no separate proof
+        }
+    }
+
+    // Check a VRF proof for the given epoch on this validator's network. A
+    // proof made for another epoch or network embeds a different input and
+    // is rejected before its output is considered.
+    pub fn vrf_verify(
+        &self,
+        epoch: u64,
+        pubkey: &PublicKey,
+        proof: &VrfProof,
+    ) -> Result<(), ValidationError> {
+        if proof.input != self.compute_vrf_input(epoch, pubkey) {
+            return Err(ValidationError::VrfInputMismatch);
+        }
+        if proof.output != self.compute_vrf_output(&proof.input, pubkey) {
+            return Err(ValidationError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    // Compute VRF output (simplified - real implementation needs VRF)
+    fn compute_vrf_output(&self, input: &[u8; 32], pubkey: &PublicKey) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_VRF_OUTPUT");
+        hasher.update(input);
+        hasher.update(pubkey.as_bytes());
+        
+        let mut output = [0u8; 32];
+        output.copy_from_slice(&hasher.finalize());
+        output
+    }
+
+    // Digest of the registry, latency graph and epoch. Validators with the
+    // same view produce the same fingerprint; a mismatch means their state
+    // has diverged and needs reconciling.
+    pub fn state_fingerprint(&self) -> [u8; 32] {
+        compute_state_fingerprint(self.current_epoch, &self.nodes, &self.latency_matrix)
+    }
+
+    // Assign node to continental shard based on location, using the first
+    // region in `SHARD_REGIONS` that contains it
+    pub fn assign_shard(location: &GeoLocation) -> Shard {
+        region_shard(location.latitude, normalize_longitude(location.longitude))
+            .unwrap_or(Shard::NorthAmerica) // Default fallback
+    }
+
+    // Geographic center of a shard's nodes
+    pub fn shard_centroid(&self, shard: Shard) -> Option<GeoLocation> {
+        let points: Vec<_> = self
+            .nodes
+            .values()
+            .filter(|node| node.shard == shard)
+            .map(|node| (&node.geo_location, 1.0))
+            .collect();
+        spherical_mean(&points)
+    }
+
+    // Where the economic mass of a shard sits. A large divergence from the
+    // plain centroid indicates stake concentrated in one location.
+    pub fn stake_weighted_centroid(&self, shard: Shard) -> Option<GeoLocation> {
+        let points: Vec<_> = self
+            .nodes
+            .values()
+            .filter(|node| node.shard == shard)
+            .map(|node| (node, self.effective_stake_of(node)))
+            .filter(|(_, stake)| *stake > StakeAmount::ZERO)
+            .map(|(node, stake)| (&node.geo_location, stake.base_units() as f64))
+            .collect();
+        spherical_mean(&points)
+    }
+
+    // A node's weight in selection, rewards and attack cost: its own stake
+    // plus stake delegated to it, discounted by up to
+    // `reputation_stake_influence` as its reputation falls from 1 to 0.
+    // Zero for unknown nodes.
+    pub fn effective_stake(&self, pubkey: &PublicKey) -> StakeAmount {
+        self.nodes
+            .get(pubkey)
+            .map_or(StakeAmount::ZERO, |node| self.effective_stake_of(node))
+    }
+
+    // The same for a node record that may differ from the registered one
+    pub(crate) fn effective_stake_of(&self, node: &MeshXNode) -> StakeAmount {
+        let stake = node
+            .stake_amount
+            .saturating_add(self.delegated_to(&node.pubkey));
+        let influence = self.reputation_stake_influence.clamp(0.0, 1.0);
+        if influence == 0.0 {
+            return stake;
+        }
+        let shortfall = 1.0 - node.reputation_score.clamp(0.0, 1.0) as f64;
+        let retained_ppm = ((1.0 - influence * shortfall) * 1e6).round() as u128;
+        StakeAmount::from_base_units((stake.base_units() as u128 * retained_ppm / 1_000_000) as u64)
+    }
+
+    // Additional stake an adversary would need to hold more than half of a
+    // shard's selection weight, counting nodes currently eligible for selection
+    pub fn attack_cost(&self, shard: Shard) -> StakeAmount {
+        let honest_weight = self
+            .nodes
+            .values()
+            .filter(|node| node.shard == shard)
+            .filter(|node| !self.in_slash_cooldown(&node.pubkey, self.current_epoch))
+            .filter(|node| !self.in_liveness_demotion(&node.pubkey, self.current_epoch))
+            .map(|node| self.effective_stake(&node.pubkey))
+            .fold(StakeAmount::ZERO, |total, weight| {
+                total.saturating_add(weight)
+            });
+
+        // The attacker also needs at least one node meeting the shard minimum
+        honest_weight
+            .saturating_add(StakeAmount::from_base_units(1))
+            .max(self.get_minimum_stake(&shard))
+    }
+
+    // Least honest stake that still fields `minimum_nodes` valid validators
+    // in the shard if everyone else colludes or goes offline: the smallest
+    // selection weights among nodes eligible next epoch (slashed and
+    // provisional nodes don't count), with any shortfall filled by new nodes
+    // at the shard minimum
+    pub fn min_honest_stake_for_liveness(&self, shard: Shard) -> StakeAmount {
+        let mut weights: Vec<StakeAmount> = self
+            .selection_candidates(self.current_epoch + 1)
+            .into_iter()
+            .filter(|node| node.shard == shard && self.validate_node(node).is_ok())
+            .map(|node| self.effective_stake(&node.pubkey))
+            .collect();
+        weights.sort();
+        weights.resize(
+            self.minimum_nodes.max(weights.len()),
+            self.get_minimum_stake(&shard),
+        );
+        weights[..self.minimum_nodes]
+            .iter()
+            .fold(StakeAmount::ZERO, |total, weight| {
+                total.saturating_add(*weight)
+            })
+    }
+
+    // Get minimum stake for a shard
+    fn get_minimum_stake(&self, shard: &Shard) -> StakeAmount {
+        self.policy.minimum_stake_for(shard)
+    }
+
+    // Get validator count per shard
+    pub(crate) fn get_validator_count(&self) -> usize {
+        self.validator_count
+    }
+
+    // Resources reported by a re-registering node may dip a little under
+    // background load, but a drop of more than the policy tolerance against
+    // the previous record (e.g. halving the cores) is flagged
+    pub fn check_resource_fluctuation(
+        &self,
+        previous: &NodeResources,
+        current: &NodeResources,
+    ) -> Result<(), ValidationError> {
+        let floor = 1.0 - self.policy.resource_fluctuation_tolerance.clamp(0.0, 1.0);
+        let pairs = [
+            (previous.cpu_cores as u64, current.cpu_cores as u64),
+            (previous.ram_gb as u64, current.ram_gb as u64),
+            (previous.storage_gb, current.storage_gb),
+            (
+                previous.bandwidth_mbps as u64,
+                current.bandwidth_mbps as u64,
+            ),
+            (
+                previous.gpu_memory_gb.unwrap_or(0) as u64,
+                current.gpu_memory_gb.unwrap_or(0) as u64,
+            ),
+        ];
+        if pairs
+            .iter()
+            .any(|(previous, current)| (*current as f64) < *previous as f64 * floor)
+        {
+            return Err(ValidationError::ResourceDowngrade);
+        }
+        Ok(())
+    }
+
+    // Sanity-check a node's claimed bandwidth against its connectivity. Even
+    // its best-connected shard peer bounds throughput at one TCP window per
+    // round trip, so a 1 Gbps claim from a node hundreds of milliseconds
+    // from every peer is implausible.
+    pub fn cross_check_bandwidth(&self, pubkey: &PublicKey) -> Result<(), ValidationError> {
+        let node = self.nodes.get(pubkey).ok_or(ValidationError::UnknownNode)?;
+        let best_rtt_ms = self
+            .nodes
+            .values()
+            .filter(|peer| peer.shard == node.shard && peer.pubkey != *pubkey)
+            .filter_map(|peer| self.measured_latency(pubkey, &peer.pubkey))
+            .min()
+            .ok_or(ValidationError::InsufficientLatencyData)?
+            .max(1) as f64
+            * 2.0;
+
+        let achievable_mbps = MAX_TCP_WINDOW_BYTES * 8.0 / (best_rtt_ms / 1000.0) / 1_000_000.0;
+        if node.resources.bandwidth_mbps as f64 > achievable_mbps {
+            return Err(ValidationError::ImplausibleBandwidth);
+        }
+        Ok(())
+    }
+
+    // Verify node has minimum resources
+    pub(crate) fn verify_resources(
+        &self,
+        resources: &NodeResources,
+    ) -> Result<(), ValidationError> {
+        self.verify_resources_for_stake(resources, StakeAmount::ZERO)
+    }
+
+    // Verify resources against the minimums for a node staking `stake`,
+    // which rise with stake when `resource_tiers` are configured
+    pub(crate) fn verify_resources_for_stake(
+        &self,
+        resources: &NodeResources,
+        stake: StakeAmount,
+    ) -> Result<(), ValidationError> {
+        self.policy.check_resources(resources, stake)
+    }
+
+    pub(crate) fn get_expected_enclave_hash(&self) -> [u8; 32] {
+        // Hash of the expected MeshX validator code
+        [0x42; 32] // Placeholder
+    }
+}
+
+// Horizontal dilution of precision of anchors seen from a position: small
+// when the directions to the anchors are well spread, infinite when they
+// are collinear (or there is only one). Directions come from a local flat
+// projection.
+fn geometric_dilution(position: &GeoLocation, anchors: &[&GeoLocation]) -> f64 {
+    let (mut xx, mut xy, mut yy) = (0.0, 0.0, 0.0);
+    for anchor in anchors {
+        let dx = normalize_longitude(anchor.longitude - position.longitude)
+            * position.latitude.to_radians().cos();
+        let dy = anchor.latitude - position.latitude;
+        let norm = (dx * dx + dy * dy).sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        let (ux, uy) = (dx / norm, dy / norm);
+        xx += ux * ux;
+        xy += ux * uy;
+        yy += uy * uy;
+    }
+    let det = xx * yy - xy * xy;
+    if det <= 1e-12 {
+        return f64::INFINITY;
+    }
+    ((xx + yy) / det).sqrt()
+}
+
+// Fingerprint over an epoch, registry and latency graph, shared by live
+// validators and snapshots that haven't been adopted yet
+pub(crate) fn compute_state_fingerprint(
+    epoch: u64,
+    nodes: &HashMap<PublicKey, MeshXNode>,
+    latency_matrix: &HashMap<(PublicKey, PublicKey), u32>,
+) -> [u8; 32] {
+    let mut nodes: Vec<_> = nodes.values().collect();
+    nodes.sort_by(|a, b| a.pubkey.as_bytes().cmp(b.pubkey.as_bytes()));
+    let mut edges: Vec<_> = latency_matrix.iter().collect();
+    edges.sort_by(|((a_from, a_to), _), ((b_from, b_to), _)| {
+        (a_from.as_bytes(), a_to.as_bytes()).cmp(&(b_from.as_bytes(), b_to.as_bytes()))
+    });
+
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"MESHX_STATE_FINGERPRINT");
+    hasher.update(epoch.to_le_bytes());
+    hasher.update((nodes.len() as u64).to_le_bytes());
+    for node in nodes {
+        let node = MeshXNode {
+            metadata: BTreeMap::new(),
+            ..node.clone()
+        };
+        let node_hash = Sha3_256::digest(serde_json::to_vec(&node).expect("node serializes"));
+        hasher.update(node.pubkey.as_bytes());
+        hasher.update(node_hash);
+    }
+    hasher.update((edges.len() as u64).to_le_bytes());
+    for ((from, to), latency) in edges {
+        hasher.update(from.as_bytes());
+        hasher.update(to.as_bytes());
+        hasher.update(latency.to_le_bytes());
+    }
+
+    let mut output = [0u8; 32];
+    output.copy_from_slice(&hasher.finalize());
+    output
+}
+
+// Merge a re-registration into the existing record for the same key. The
+// declared attestation, location, resources and stake come from the new
+// record, provided its attestation isn't older than the one on file;
+// reputation earned on the network carries over.
+fn reconcile_registration(
+    existing: &MeshXNode,
+    incoming: MeshXNode,
+) -> Result<MeshXNode, ValidationError> {
+    if incoming.tee_attestation.timestamp < existing.tee_attestation.timestamp {
+        return Err(ValidationError::StaleAttestation);
+    }
+    Ok(MeshXNode {
+        reputation_score: existing.reputation_score,
+        ..incoming
+    })
+}
+
+// Wrap a longitude into [-180, 180) so points past the dateline (e.g. 190)
+// are assigned like their canonical equivalents (-170)
+fn normalize_longitude(longitude: f64) -> f64 {
+    (longitude + 180.0).rem_euclid(360.0) - 180.0
+}
+
+// Lowercase hex rendering for keys and digests
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Weighted mean of points on the sphere, averaged as ECEF unit vectors so
+// that longitudes on either side of the antimeridian don't cancel out. The
+// result's accuracy is the weighted mean distance of the points from it.
+fn spherical_mean(points: &[(&GeoLocation, f64)]) -> Option<GeoLocation> {
+    let weight_sum: f64 = points.iter().map(|(_, weight)| weight).sum();
+    if points.is_empty() || weight_sum <= 0.0 {
+        return None;
+    }
+
+    let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+    for (location, weight) in points {
+        let lat = location.latitude.to_radians();
+        let lon = location.longitude.to_radians();
+        x += weight * lat.cos() * lon.cos();
+        y += weight * lat.cos() * lon.sin();
+        z += weight * lat.sin();
+    }
+
+    // Points spread evenly around the globe have no meaningful center
+    let norm = (x * x + y * y + z * z).sqrt();
+    if norm < 1e-9 * weight_sum {
+        return None;
+    }
+
+    let mut mean = GeoLocation {
+        latitude: (z / norm).asin().to_degrees(),
+        longitude: y.atan2(x).to_degrees(),
+        accuracy_meters: 0.0,
+    };
+    let spread: f64 = points
+        .iter()
+        .map(|(location, weight)| weight * haversine_distance(&mean, location))
+        .sum();
+    mean.accuracy_meters = (spread / weight_sum) as f32;
+    Some(mean)
+}
+
+// Calculate distance between two geographic points (Haversine formula)
+pub(crate) fn haversine_distance(loc1: &GeoLocation, loc2: &GeoLocation) -> f64 {
+    const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+    let lat1_rad = loc1.latitude.to_radians();
+    let lat2_rad = loc2.latitude.to_radians();
+    let delta_lat = (loc2.latitude - loc1.latitude).to_radians();
+    let delta_lon = (loc2.longitude - loc1.longitude).to_radians();
+
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat1_rad.cos() * lat2_rad.cos() * (delta_lon / 2.0).sin().powi(2);
+
+    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+
+    EARTH_RADIUS_M * c
+}
+
+// Category of a validation failure, e.g. for grouping nodes by failure.
+// Every error is a bare variant, so the error is its own kind.
+pub type ValidationErrorKind = ValidationError;
+
+// Validation errors
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, thiserror::Error)]
+pub enum ValidationError {
+    #[error("TEE attestation is too old")]
+    StaleAttestation,
+    #[error("Invalid enclave code hash")]
     InvalidEnclaveCode,
+    #[error("Attestation carries a placeholder enclave hash")]
+    PlaceholderAttestation,
     #[error("Invalid TEE quote")]
     InvalidQuote,
+    #[error("No verifier registered for TEE type")]
+    UnsupportedTeeType,
+    #[error("Attestation signer has been revoked")]
+    RevokedSigner,
     #[error("Insufficient stake amount")]
     InsufficientStake,
+    #[error("Claimed stake exceeds the plausible share of total supply")]
+    ImplausibleStake,
     #[error("Not enough latency measurements")]
     InsufficientLatencyData,
     #[error("Location doesn't match latency triangulation")]
     LocationMismatch,
+    #[error("Triangulated position falls in a different shard than declared")]
+    TriangulatedShardMismatch,
+    #[error("Latency is below the physical minimum for the great-circle distance")]
+    LatencyBelowPhysicalMinimum,
     #[error("Not enough validators available")]
     InsufficientValidators,
     #[error("Insufficient CPU cores")]
@@ -400,54 +2401,1669 @@ pub enum ValidationError {
     InsufficientStorage,
     #[error("Insufficient bandwidth")]
     InsufficientBandwidth,
+    #[error("Insufficient GPU memory")]
+    InsufficientGPU,
+    #[error("GPU reported with zero memory")]
+    InvalidGpuReport,
+    #[error("Node is not registered")]
+    UnknownNode,
+    #[error("Invalid signature")]
+    InvalidSignature,
+    #[error("Claimed bandwidth exceeds what the node's latency allows")]
+    ImplausibleBandwidth,
+    #[error("Resources dropped beyond the allowed fluctuation")]
+    ResourceDowngrade,
+    #[error("Latency measurement not signed by its reporting node")]
+    UnauthorizedMeasurement,
+    #[error("Attestation timestamp is earlier than the last one seen this epoch")]
+    AttestationRegression,
+    #[error("Node is backing off after repeated validation failures")]
+    RetryBackoff,
+    #[error("VRF proof was made for a different epoch or network")]
+    VrfInputMismatch,
+    #[error("Selection transcript doesn't replay to its recorded selection")]
+    TranscriptMismatch,
+    #[error("Delegation would exceed the delegator's influence cap")]
+    DelegationCapExceeded,
+    #[error("State snapshot is malformed")]
+    MalformedStateSnapshot,
+    #[error("State snapshot doesn't match the trusted fingerprint")]
+    StateFingerprintMismatch,
+    #[error("Epoch seed doesn't follow from the previous seed and VRF outputs")]
+    InvalidSeedChain,
+    #[error("Stake is out of proportion to the node's CPU and RAM")]
+    StakeResourceImbalance,
+    #[error("No outstanding attestation challenge matches the response")]
+    UnknownChallenge,
+    #[error("Attestation challenge response arrived after the TEE type's window")]
+    ChallengeExpired,
+    #[error("Node is banned")]
+    Banned,
+    #[error("Claimed location is outside every habitable region")]
+    UninhabitableLocation,
+    #[error("Node metadata exceeds the configured size limits")]
+    MetadataTooLarge,
+    #[error("Shard's selected validators fall short of the required resources")]
+    InsufficientShardCapacity,
+    #[error("Quote format doesn't match the declared TEE type")]
+    TeeTypeQuoteMismatch,
+    #[error("Delegation would close a loop in the delegation graph")]
+    CircularDelegation,
+    #[error("No policy snapshot covers the requested epoch")]
+    MissingPolicySnapshot,
+    #[error("Attestation timestamp is too far ahead of the validator's clock")]
+    FutureAttestation,
+    #[error("Revocation list is malformed")]
+    MalformedRevocationList,
+    #[error("TEE quote signature doesn't verify")]
+    QuoteSignatureInvalid,
+    #[error("PCK certificate chain doesn't lead to the trusted Intel root")]
+    UntrustedPckChain,
+    #[error("Delegator's balance doesn't cover the delegation")]
+    InsufficientBalance,
+    #[error("Delegation nonce isn't the delegator's next")]
+    DelegationReplayed,
+    #[error("Resource proof was made under a different attestation or stake")]
+    StaleResourceProof,
+    #[error("Rewards for the epoch were already distributed")]
+    RewardsAlreadyDistributed,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::dcap::tests::test_quote;
+    use ed25519_dalek::SecretKey;
+
+    // Deterministic public key derived from a single seed byte
+    pub(crate) fn test_pubkey(seed: u8) -> PublicKey {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        PublicKey::from(&secret)
+    }
+
+    pub(crate) fn current_timestamp() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    // A node that passes every validation check once it has latency anchors
+    pub(crate) fn test_node(
+        seed: u8,
+        latitude: f64,
+        longitude: f64,
+        stake_amount: u64,
+    ) -> MeshXNode {
+        let geo_location = GeoLocation {
+            latitude,
+            longitude,
+            accuracy_meters: 20_000_000.0,
+        };
+        MeshXNode {
+            pubkey: test_pubkey(seed),
+            tee_attestation: TeeAttestation {
+                tee_type: TeeType::IntelSgx,
+                enclave_hash: [0x42; 32],
+                signer_pubkey: test_pubkey(seed),
+                timestamp: current_timestamp(),
+                quote: test_quote(0),
+            },
+            shard: PopValidator::assign_shard(&geo_location),
+            geo_location,
+            stake_amount: StakeAmount::from_meshx(stake_amount),
+            reputation_score: 1.0,
+            resources: NodeResources {
+                cpu_cores: 8,
+                ram_gb: 16,
+                storage_gb: 500,
+                bandwidth_mbps: 100,
+                gpu_memory_gb: None,
+            },
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    // Fully meshed network of valid European nodes with 10ms links
+    pub(crate) fn test_network(count: u8) -> (PopValidator, Vec<PublicKey>) {
+        let mut validator = PopValidator::new(1);
+        let mut keys = Vec::new();
+        for seed in 1..=count {
+            let node = test_node(seed, 50.0 + seed as f64 * 0.1, 10.0, 100_000);
+            keys.push(node.pubkey);
+            validator.nodes.insert(node.pubkey, node);
+        }
+        for from in &keys {
+            for to in &keys {
+                if from != to {
+                    validator.latency_matrix.insert((*from, *to), 10);
+                }
+            }
+        }
+        (validator, keys)
+    }
+
+    #[test]
+    fn test_shard_assignment() {
+        // Test New York
+        let ny = GeoLocation {
+            latitude: 40.7128,
+            longitude: -74.0060,
+            accuracy_meters: 1000.0,
+        };
+        assert_eq!(PopValidator::assign_shard(&ny), Shard::NorthAmerica);
+
+        // Test London
+        let london = GeoLocation {
+            latitude: 51.5074,
+            longitude: -0.1278,
+            accuracy_meters: 1000.0,
+        };
+        assert_eq!(PopValidator::assign_shard(&london), Shard::Europe);
+
+        // Test Tokyo
+        let tokyo = GeoLocation {
+            latitude: 35.6762,
+            longitude: 139.6503,
+            accuracy_meters: 1000.0,
+        };
+        assert_eq!(PopValidator::assign_shard(&tokyo), Shard::Asia);
+    }
+
+    #[test]
+    fn test_haversine_distance() {
+        let loc1 = GeoLocation {
+            latitude: 40.7128,
+            longitude: -74.0060,
+            accuracy_meters: 1000.0,
+        };
+
+        let loc2 = GeoLocation {
+            latitude: 51.5074,
+            longitude: -0.1278,
+            accuracy_meters: 1000.0,
+        };
+
+        let distance = haversine_distance(&loc1, &loc2);
+        assert!((distance - 5_570_000.0).abs() < 10_000.0); // ~5570km ± 10km
+    }
+
+    #[test]
+    fn test_network_diameter() {
+        let mut validator = PopValidator::new(1);
+        let (a, b, c, d) = (
+            test_pubkey(1),
+            test_pubkey(2),
+            test_pubkey(3),
+            test_pubkey(4),
+        );
+
+        // Line topology a - b - c - d with a shortcut a - c
+        validator.latency_matrix.insert((a, b), 10);
+        validator.latency_matrix.insert((b, c), 20);
+        validator.latency_matrix.insert((c, d), 30);
+        validator.latency_matrix.insert((a, c), 25);
+
+        let (x, y, latency) = validator.network_diameter().unwrap();
+        assert_eq!(latency, 55); // a -> c -> d
+        assert!((x == a && y == d) || (x == d && y == a));
+
+        // An isolated pair makes the graph disconnected
+        validator
+            .latency_matrix
+            .insert((test_pubkey(5), test_pubkey(6)), 5);
+        assert!(validator.network_diameter().is_none());
+    }
+
+    #[test]
+    fn test_slashed_node_cooldown() {
+        let (mut validator, keys) = test_network(5);
+        validator.slash_cooldown_epochs = 2;
+        validator.current_epoch = 10;
+
+        let slashed = validator
+            .slash_node(&keys[0], StakeAmount::from_meshx(50_000))
+            .unwrap();
+        assert_eq!(slashed, StakeAmount::from_meshx(50_000));
+        // Topping stake back up does not shorten the cooldown
+        validator.nodes.get_mut(&keys[0]).unwrap().stake_amount = StakeAmount::from_meshx(100_000);
+
+        for epoch in 10..=12 {
+            let selected = validator.select_validators(epoch).unwrap();
+            assert!(
+                !selected.contains(&keys[0]),
+                "selected during cooldown epoch {}",
+                epoch
+            );
+            assert_eq!(selected.len(), 4);
+        }
+        let selected = validator.select_validators(13).unwrap();
+        assert!(selected.contains(&keys[0]));
+    }
+
+    #[test]
+    fn test_triangle_consistency() {
+        let mut validator = PopValidator::new(1);
+        let (a, b, c) = (test_pubkey(1), test_pubkey(2), test_pubkey(3));
+        validator.latency_matrix.insert((a, b), 20);
+        validator.latency_matrix.insert((b, c), 30);
+        validator.latency_matrix.insert((a, c), 45);
+
+        assert!(validator.check_triangle_consistency(&a, &b, &c));
+        assert!(validator.find_inconsistent_triples(5).is_empty());
+
+        // a-c claims 120ms although the detour through b takes 50ms
+        validator.latency_matrix.insert((a, c), 120);
+        assert!(!validator.check_triangle_consistency(&a, &b, &c));
+        let inconsistent = validator.find_inconsistent_triples(5);
+        assert_eq!(inconsistent.len(), 1);
+        let (x, via, y) = inconsistent[0];
+        assert_eq!(via, b);
+        assert!((x == a && y == c) || (x == c && y == a));
+    }
+
+    #[test]
+    fn test_node_summary_omits_quote() {
+        let mut node = test_node(1, 51.5, -0.1, 100_000);
+        // DCAP quotes are several kilobytes
+        node.tee_attestation.quote = vec![0xAB; 4096];
+
+        let summary = node.summary();
+        let summary_json = serde_json::to_string(&summary).unwrap();
+        let full_json = serde_json::to_string(&node).unwrap();
+
+        let decoded: NodeSummary = serde_json::from_str(&summary_json).unwrap();
+        assert_eq!(decoded, summary);
+        assert!(!summary_json.contains("quote"));
+        assert!(summary_json.len() * 10 < full_json.len());
+    }
+
+    #[test]
+    fn test_stake_weighted_centroid() {
+        let mut validator = PopValidator::new(1);
+        let lisbon = test_node(1, 38.72, -9.14, 100_000);
+        let warsaw = test_node(2, 52.23, 21.01, 100_000);
+        let (lisbon_key, warsaw_key) = (lisbon.pubkey, warsaw.pubkey);
+        let lisbon_location = lisbon.geo_location.clone();
+        validator.nodes.insert(lisbon.pubkey, lisbon);
+        validator.nodes.insert(warsaw.pubkey, warsaw);
+
+        let plain = validator.shard_centroid(Shard::Europe).unwrap();
+        let even = validator.stake_weighted_centroid(Shard::Europe).unwrap();
+        assert!(haversine_distance(&plain, &even) < 1.0);
+
+        // Moving stake onto the Lisbon node pulls the center of gravity west
+        validator.nodes.get_mut(&lisbon_key).unwrap().stake_amount =
+            StakeAmount::from_meshx(900_000);
+        validator.nodes.get_mut(&warsaw_key).unwrap().stake_amount =
+            StakeAmount::from_meshx(100_000);
+        let weighted = validator.stake_weighted_centroid(Shard::Europe).unwrap();
+        assert!(
+            haversine_distance(&weighted, &lisbon_location)
+                < haversine_distance(&even, &lisbon_location) / 2.0
+        );
+        assert!(validator.stake_weighted_centroid(Shard::Asia).is_none());
+    }
+
+    #[test]
+    fn test_triangulated_shard_mismatch() {
+        let mut validator = PopValidator::new(1);
+        let anchors = [
+            test_node(1, 35.0, 100.0, 100_000),
+            test_node(2, 36.0, 102.0, 100_000),
+            test_node(3, 34.0, 101.0, 100_000),
+        ];
+        // Claims valid European coordinates, but sits among Asian anchors
+        let node = test_node(9, 50.1, 10.0, 100_000);
+        assert_eq!(node.shard, Shard::Europe);
+        for anchor in anchors {
+            validator
+                .latency_matrix
+                .insert((anchor.pubkey, node.pubkey), 5);
+            validator.nodes.insert(anchor.pubkey, anchor);
+        }
+        assert!(validator.validate_node(&node).is_ok());
+
+        validator.policy.triangulated_shard_tolerance_m = Some(100_000.0);
+        assert_eq!(
+            validator.validate_node(&node),
+            Err(ValidationError::TriangulatedShardMismatch)
+        );
+    }
+
+    #[test]
+    fn test_latency_below_great_circle_minimum() {
+        let mut validator = PopValidator::new(1);
+        validator.policy.min_latency_fraction = Some(1.0);
+        let precise = |mut node: MeshXNode| {
+            node.geo_location.accuracy_meters = 50_000.0;
+            node
+        };
+        // Madrid and its antipode near Weber, New Zealand: ~20,000 km apart,
+        // so at least ~100ms one way
+        let node = precise(test_node(9, 40.4, -3.7, 100_000));
+        let anchors = [
+            precise(test_node(1, -40.4, 176.3, 100_000)),
+            precise(test_node(2, 41.4, 2.2, 100_000)),
+            precise(test_node(3, 38.7, -9.1, 100_000)),
+        ];
+        for anchor in anchors {
+            validator
+                .latency_matrix
+                .insert((anchor.pubkey, node.pubkey), 100);
+            validator.nodes.insert(anchor.pubkey, anchor);
+        }
+        let check_floor = |validator: &PopValidator| {
+            let peers: Vec<(&MeshXNode, u32)> = validator
+                .measurements_to(&node.pubkey)
+                .into_iter()
+                .map(|(peer, latency_ms)| (&validator.nodes[peer], latency_ms))
+                .collect();
+            validator.check_latency_floor(&node, &peers)
+        };
+        assert_eq!(check_floor(&validator), Ok(()));
+
+        validator
+            .latency_matrix
+            .insert((test_pubkey(1), node.pubkey), 60);
+        assert_eq!(
+            validator.validate_node(&node),
+            Err(ValidationError::LatencyBelowPhysicalMinimum)
+        );
+
+        validator.policy.min_latency_fraction = Some(0.5);
+        assert_eq!(check_floor(&validator), Ok(()));
+        validator.policy.min_latency_fraction = None;
+        assert_eq!(check_floor(&validator), Ok(()));
+        assert_eq!(ValidationPolicy::default().min_latency_fraction, None);
+    }
+
+    #[test]
+    fn test_attestation_batch_matches_individual() {
+        let mut validator = PopValidator::new(1);
+        validator.attestation_batch_threads = 3;
+
+        let mut attestations = Vec::new();
+        for seed in 1..=10u8 {
+            let mut attestation = test_node(seed, 50.0, 10.0, 100_000).tee_attestation;
+            match seed % 4 {
+                0 => attestation.timestamp -= 7200, // Stale
+                1 => attestation.quote.clear(),     // Empty quote
+                _ => {}
+            }
+            attestations.push(attestation);
+        }
+
+        let batch = validator.verify_attestation_batch(&attestations);
+        assert_eq!(batch.len(), attestations.len());
+        for (attestation, result) in attestations.iter().zip(&batch) {
+            assert_eq!(*result, validator.verify_tee_attestation(attestation));
+        }
+        assert!(batch.contains(&Err(ValidationError::StaleAttestation)));
+        assert!(batch.contains(&Ok(())));
+    }
+
+    #[test]
+    fn test_future_attestation_rejected() {
+        let (validator, keys) = test_network(4);
+        let now = current_timestamp();
+        let mut node = validator.nodes[&keys[0]].clone();
+        node.tee_attestation.timestamp = now + 300;
+        assert_eq!(
+            validator.validate_node(&node),
+            Err(ValidationError::FutureAttestation)
+        );
+        assert_eq!(
+            validator.verify_attestation_batch(&[node.tee_attestation.clone()]),
+            vec![Err(ValidationError::FutureAttestation)]
+        );
+
+        // Slight clock skew is tolerated
+        node.tee_attestation.timestamp = now + 30;
+        assert!(validator.validate_node(&node).is_ok());
+    }
 
     #[test]
-    fn test_shard_assignment() {
-        // Test New York
-        let ny = GeoLocation {
-            latitude: 40.7128,
-            longitude: -74.0060,
-            accuracy_meters: 1000.0,
+    fn test_placeholder_attestation_rejected() {
+        let mut validator = PopValidator::new(1);
+        let mut attestation = test_node(1, 50.0, 10.0, 100_000).tee_attestation;
+        attestation.enclave_hash = [0; 32];
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::PlaceholderAttestation)
+        );
+
+        let stub = [0xAA; 32];
+        validator.policy.placeholder_enclave_hashes.push(stub);
+        attestation.enclave_hash = stub;
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::PlaceholderAttestation)
+        );
+
+        // Dev mode lets stubs through to the usual enclave hash check
+        validator.policy.allow_placeholder_attestations = true;
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::InvalidEnclaveCode)
+        );
+    }
+
+    #[test]
+    fn test_validation_policy_defaults() {
+        let validator = PopValidator::new(1);
+        assert_eq!(
+            validator.get_minimum_stake(&Shard::Europe),
+            StakeAmount::from_meshx(100_000)
+        );
+        assert_eq!(
+            validator.get_minimum_stake(&Shard::Africa),
+            StakeAmount::from_meshx(50_000)
+        );
+        assert_eq!(
+            validator.get_minimum_stake(&Shard::Antarctica),
+            StakeAmount::from_meshx(10_000)
+        );
+        assert_eq!(validator.policy.max_attestation_age_secs, 3600);
+        assert_eq!(validator.policy.min_latency_measurements, 3);
+
+        let node = test_node(1, 50.0, 10.0, 100_000);
+        let mut resources = node.resources.clone();
+        resources.cpu_cores = 2;
+        resources.ram_gb = 4;
+        resources.storage_gb = 100;
+        resources.bandwidth_mbps = 10;
+        assert_eq!(validator.verify_resources(&resources), Ok(()));
+    }
+
+    #[test]
+    fn test_gpu_memory_reports() {
+        let mut validator = PopValidator::new(1);
+        let mut resources = test_node(1, 50.0, 10.0, 100_000).resources;
+        assert_eq!(validator.verify_resources(&resources), Ok(()));
+        resources.gpu_memory_gb = Some(0);
+        assert_eq!(
+            validator.verify_resources(&resources),
+            Err(ValidationError::InvalidGpuReport)
+        );
+
+        validator.policy.min_gpu_memory_gb = Some(8);
+        resources.gpu_memory_gb = Some(16);
+        assert_eq!(validator.verify_resources(&resources), Ok(()));
+        resources.gpu_memory_gb = None;
+        assert_eq!(
+            validator.verify_resources(&resources),
+            Err(ValidationError::InsufficientGPU)
+        );
+    }
+
+    #[test]
+    fn test_stake_scaled_resource_minimums() {
+        let mut validator = PopValidator::new(1);
+        let mut resources = test_node(1, 50.0, 10.0, 100_000).resources;
+        resources.cpu_cores = 2;
+        resources.ram_gb = 4;
+        let whale = StakeAmount::from_meshx(1_000_000);
+        let minimum = StakeAmount::from_meshx(100_000);
+        assert_eq!(
+            validator.verify_resources_for_stake(&resources, whale),
+            Ok(())
+        );
+
+        validator.policy.resource_tiers = vec![
+            ResourceTier {
+                min_stake: StakeAmount::from_meshx(500_000),
+                min_cpu_cores: 8,
+                min_ram_gb: 16,
+            },
+            ResourceTier {
+                min_stake: whale,
+                min_cpu_cores: 16,
+                min_ram_gb: 32,
+            },
+        ];
+        assert_eq!(
+            validator.verify_resources_for_stake(&resources, minimum),
+            Ok(())
+        );
+        assert_eq!(
+            validator.verify_resources_for_stake(&resources, whale),
+            Err(ValidationError::InsufficientCPU)
+        );
+        resources.cpu_cores = 16;
+        assert_eq!(
+            validator.verify_resources_for_stake(&resources, whale),
+            Err(ValidationError::InsufficientRAM)
+        );
+        resources.ram_gb = 32;
+        assert_eq!(
+            validator.verify_resources_for_stake(&resources, whale),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_stake_resource_ratio() {
+        let mut validator = PopValidator::new(1);
+        validator.policy.max_stake_per_resource_unit = Some(StakeAmount::from_meshx(10_000));
+        let mut balanced = test_node(1, 50.0, 10.0, 100_000);
+        balanced.resources.cpu_cores = 4;
+        balanced.resources.ram_gb = 8;
+        assert_eq!(validator.verify_stake(&balanced), Ok(()));
+
+        let mut whale = test_node(2, 50.0, 10.0, 5_000_000);
+        whale.resources.cpu_cores = 2;
+        whale.resources.ram_gb = 4;
+        assert_eq!(
+            validator.verify_stake(&whale),
+            Err(ValidationError::StakeResourceImbalance)
+        );
+
+        validator.policy.max_stake_per_resource_unit = None;
+        assert_eq!(validator.verify_stake(&whale), Ok(()));
+    }
+
+    #[test]
+    fn test_validation_policy_override() {
+        let node = test_node(1, 50.0, 10.0, 100_000);
+        let default_validator = PopValidator::new(1);
+        assert_eq!(default_validator.verify_resources(&node.resources), Ok(()));
+
+        let policy = ValidationPolicy {
+            min_cpu_cores: 16,
+            ..ValidationPolicy::default()
         };
-        assert_eq!(PopValidator::assign_shard(&ny), Shard::NorthAmerica);
+        let strict_validator = PopValidator::with_policy(1, policy);
+        assert_eq!(
+            strict_validator.verify_resources(&node.resources),
+            Err(ValidationError::InsufficientCPU)
+        );
+    }
 
-        // Test London
-        let london = GeoLocation {
-            latitude: 51.5074,
-            longitude: -0.1278,
+    // Frozen consensus vectors: (key seed, public key, VRF input, VRF output)
+    // at epoch 42. Any change to key derivation, VRF or selection ordering
+    // that alters these must come with a protocol version bump.
+    const VRF_VECTOR_EPOCH: u64 = 42;
+    const VRF_VECTORS: [(u8, &str, &str, &str); 5] = [
+        (
+            1,
+            "8a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c",
+            "5e2357736a48e2efbacc675a2435776a98be6c19be7f6f0a7fded93dde9775b7",
+            "e8eb7c6b869ac0880aced1accf453242f525a03952a1e2a035272017aa12f420",
+        ),
+        (
+            2,
+            "8139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b394",
+            "83fa54414da629e155e4d664121d0e65db5995260ed7aa507a1bd8a08150219b",
+            "125730b52b0ab77eb42cfec84f359614034c60edce0a30ce91cb9e6a0bb77dc3",
+        ),
+        (
+            3,
+            "ed4928c628d1c2c6eae90338905995612959273a5c63f93636c14614ac8737d1",
+            "041863466aef0fd3810acef2e8d916dc98d0bd26d68f1f0ed4a3a2eaa6cf2f58",
+            "623b56a301f47a166cb79ef6a72eacbe8a160cece3ab8df42bb628b4aaabbe71",
+        ),
+        (
+            4,
+            "ca93ac1705187071d67b83c7ff0efe8108e8ec4530575d7726879333dbdabe7c",
+            "2b088f41bbc9d75a224cbcf0271d7591a274e440eed23bced5dced156c63b85e",
+            "58c3035baf74f9352842b48bb94efef7ce804b4302cbf9037cbfdd5ff4e2ace8",
+        ),
+        (
+            5,
+            "6e7a1cdd29b0b78fd13af4c5598feff4ef2a97166e3ca6f2e4fbfccd80505bf1",
+            "2e2eab652e28fc721db3eec17ece8dc49bc14313af471cd78d1a08c9c1ce2551",
+            "c2b88f7cd57341cfc91c2451c02a7c69e345d0b6ee616865c8764e3a604406c7",
+        ),
+    ];
+    // Key seeds in selection order for the vector network at epoch 42
+    const SELECTION_VECTOR: [u8; 5] = [2, 4, 3, 5, 1];
+
+    #[test]
+    fn test_vrf_and_selection_vectors() {
+        let (validator, keys) = test_network(5);
+
+        for (seed, pubkey_hex, input_hex, output_hex) in VRF_VECTORS {
+            let pubkey = test_pubkey(seed);
+            assert_eq!(
+                hex_encode(pubkey.as_bytes()),
+                pubkey_hex,
+                "key seed {}",
+                seed
+            );
+
+            let input = validator.compute_vrf_input(VRF_VECTOR_EPOCH, &pubkey);
+            assert_eq!(hex_encode(&input), input_hex, "VRF input for seed {}", seed);
+            let output = validator.compute_vrf_output(&input, &pubkey);
+            assert_eq!(
+                hex_encode(&output),
+                output_hex,
+                "VRF output for seed {}",
+                seed
+            );
+        }
+
+        let expected: Vec<PublicKey> = SELECTION_VECTOR
+            .iter()
+            .map(|seed| test_pubkey(*seed))
+            .collect();
+        assert_eq!(keys.len(), expected.len());
+        assert_eq!(
+            validator.select_validators(VRF_VECTOR_EPOCH).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_vrf_proof_bound_to_epoch_and_network() {
+        let mut validator = PopValidator::new(1);
+        let pubkey = test_pubkey(1);
+        let proof = validator.vrf_prove(5, &pubkey);
+        assert_eq!(validator.vrf_verify(5, &pubkey, &proof), Ok(()));
+        assert_eq!(
+            validator.vrf_verify(6, &pubkey, &proof),
+            Err(ValidationError::VrfInputMismatch)
+        );
+        assert_eq!(
+            validator.vrf_verify(5, &test_pubkey(2), &proof),
+            Err(ValidationError::VrfInputMismatch)
+        );
+
+        let mut forged = proof.clone();
+        forged.output[0] ^= 1;
+        assert_eq!(
+            validator.vrf_verify(5, &pubkey, &forged),
+            Err(ValidationError::InvalidSignature)
+        );
+
+        validator.network_id = "testnet".to_string();
+        let testnet_proof = validator.vrf_prove(5, &pubkey);
+        assert_eq!(validator.vrf_verify(5, &pubkey, &testnet_proof), Ok(()));
+        validator.network_id = "mainnet".to_string();
+        assert_eq!(
+            validator.vrf_verify(5, &pubkey, &testnet_proof),
+            Err(ValidationError::VrfInputMismatch)
+        );
+    }
+
+    #[test]
+    fn test_validation_report_for_valid_node() {
+        let (validator, keys) = test_network(4);
+        let node = &validator.nodes[&keys[0]];
+
+        let report = validator.validation_report(node);
+        assert!(report.is_valid());
+        assert!(report.attestation.error.is_none());
+        assert!(report.attestation.age_secs <= 1);
+        assert_eq!(report.location.measurement_count, 3);
+        assert!(report.location.triangulated.is_some());
+        let distance = report.location.distance_meters.unwrap();
+        assert!(distance > 0.0 && distance < 100_000.0);
+        assert_eq!(
+            report.stake.required_stake,
+            StakeAmount::from_meshx(100_000)
+        );
+        let checked: Vec<&str> = report
+            .resources
+            .iter()
+            .map(|check| check.resource.as_str())
+            .collect();
+        assert_eq!(
+            checked,
+            [
+                "cpu_cores",
+                "ram_gb",
+                "storage_gb",
+                "bandwidth_mbps",
+                "gpu_memory_gb"
+            ]
+        );
+        assert!(report.resources.iter().all(|check| check.error.is_none()));
+        assert!(report.metadata.error.is_none());
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("distance_meters"));
+    }
+
+    #[test]
+    fn test_validation_report_collects_all_failures() {
+        let (validator, keys) = test_network(4);
+        let mut node = validator.nodes[&keys[0]].clone();
+        node.stake_amount = StakeAmount::from_meshx(1_000);
+        node.resources.ram_gb = 1;
+
+        let report = validator.validation_report(&node);
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.errors(),
+            vec![
+                &ValidationError::InsufficientStake,
+                &ValidationError::InsufficientRAM
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validation_report_agrees_with_validate_node() {
+        let (mut validator, keys) = test_network(4);
+        let node = validator.nodes[&keys[0]].clone();
+
+        let mut failed_detection = node.clone();
+        failed_detection.resources.gpu_memory_gb = Some(0);
+        let mut oversized_metadata = node.clone();
+        oversized_metadata
+            .metadata
+            .insert("operator".to_string(), "x".repeat(257));
+        for (broken, error) in [
+            (failed_detection, ValidationError::InvalidGpuReport),
+            (oversized_metadata, ValidationError::MetadataTooLarge),
+        ] {
+            assert_eq!(validator.validate_node(&broken).err(), Some(error.clone()));
+            assert_eq!(validator.validation_report(&broken).errors(), vec![&error]);
+        }
+
+        // A GPU workload rejects nodes without one, or with too little memory
+        validator.policy.min_gpu_memory_gb = Some(16);
+        for gpu_memory_gb in [None, Some(8)] {
+            let mut small = node.clone();
+            small.resources.gpu_memory_gb = gpu_memory_gb;
+            assert_eq!(
+                validator.validate_node(&small).err(),
+                Some(ValidationError::InsufficientGPU)
+            );
+            let report = validator.validation_report(&small);
+            assert!(!report.is_valid());
+            assert_eq!(report.errors(), vec![&ValidationError::InsufficientGPU]);
+        }
+        let mut large = node;
+        large.resources.gpu_memory_gb = Some(24);
+        assert!(validator.validate_node(&large).is_ok());
+        assert!(validator.validation_report(&large).is_valid());
+    }
+
+    #[test]
+    fn test_simulate_validation_leaves_live_node_unchanged() {
+        let (mut validator, keys) = test_network(4);
+        validator.nodes.get_mut(&keys[0]).unwrap().stake_amount = StakeAmount::from_meshx(1_000);
+        let node = validator.nodes[&keys[0]].clone();
+        assert!(!validator.validation_report(&node).is_valid());
+
+        let report = validator.simulate_validation(
+            &node,
+            NodeOverrides {
+                stake_amount: Some(StakeAmount::from_meshx(150_000)),
+                ..Default::default()
+            },
+        );
+        assert!(report.is_valid());
+        assert_eq!(
+            report.stake.effective_stake,
+            StakeAmount::from_meshx(150_000)
+        );
+        assert_eq!(
+            validator.nodes[&keys[0]].stake_amount,
+            StakeAmount::from_meshx(1_000)
+        );
+    }
+
+    // Reference cities for shard assignment: (city, latitude, longitude, shard).
+    // Intentionally ambiguous entries:
+    // - Honolulu is routed with North America rather than Oceania
+    // - Istanbul sits on the Europe side of the 40°E meridian
+    // - Port Moresby lies north of 10°S and is grouped with Asia along with the
+    //   rest of New Guinea
+    const CITY_SHARDS: [(&str, f64, f64, Shard); 40] = [
+        ("New York", 40.7128, -74.0060, Shard::NorthAmerica),
+        ("Los Angeles", 34.0522, -118.2437, Shard::NorthAmerica),
+        ("Mexico City", 19.4326, -99.1332, Shard::NorthAmerica),
+        ("Anchorage", 61.2181, -149.9003, Shard::NorthAmerica),
+        ("Toronto", 43.6532, -79.3832, Shard::NorthAmerica),
+        ("Nuuk", 64.1814, -51.6941, Shard::NorthAmerica),
+        ("Honolulu", 21.3069, -157.8583, Shard::NorthAmerica),
+        ("London", 51.5074, -0.1278, Shard::Europe),
+        ("Reykjavik", 64.1466, -21.9426, Shard::Europe),
+        ("Lisbon", 38.7223, -9.1393, Shard::Europe),
+        ("Moscow", 55.7558, 37.6173, Shard::Europe),
+        ("Athens", 37.9838, 23.7275, Shard::Europe),
+        ("Istanbul", 41.0082, 28.9784, Shard::Europe),
+        ("Tokyo", 35.6762, 139.6503, Shard::Asia),
+        ("Mumbai", 19.0760, 72.8777, Shard::Asia),
+        ("Singapore", 1.3521, 103.8198, Shard::Asia),
+        ("Vladivostok", 43.1198, 131.8869, Shard::Asia),
+        ("Novosibirsk", 55.0084, 82.9357, Shard::Asia),
+        ("Dubai", 25.2048, 55.2708, Shard::Asia),
+        ("Riyadh", 24.7136, 46.6753, Shard::Asia),
+        ("Anadyr", 64.7337, 177.4968, Shard::Asia),
+        ("Port Moresby", -9.4438, 147.1803, Shard::Asia),
+        ("Jeddah", 21.4858, 39.1925, Shard::Asia),
+        ("Hodeidah", 14.7978, 42.9545, Shard::Asia),
+        ("Sanaa", 15.3694, 44.1910, Shard::Asia),
+        ("Sao Paulo", -23.5505, -46.6333, Shard::SouthAmerica),
+        ("Bogota", 4.7110, -74.0721, Shard::SouthAmerica),
+        ("Ushuaia", -54.8019, -68.3030, Shard::SouthAmerica),
+        ("Cairo", 30.0444, 31.2357, Shard::Africa),
+        ("Lagos", 6.5244, 3.3792, Shard::Africa),
+        ("Mogadishu", 2.0469, 45.3182, Shard::Africa),
+        ("Cape Town", -33.9249, 18.4241, Shard::Africa),
+        ("Asmara", 15.3229, 38.9251, Shard::Africa),
+        ("Port Sudan", 19.6158, 37.2164, Shard::Africa),
+        ("Mekelle", 13.4967, 39.4753, Shard::Africa),
+        ("Perth", -31.9505, 115.8605, Shard::Oceania),
+        ("Sydney", -33.8688, 151.2093, Shard::Oceania),
+        ("Suva", -18.1416, 178.4419, Shard::Oceania),
+        ("Apia", -13.8333, -171.7500, Shard::Oceania),
+        ("McMurdo Station", -77.8419, 166.6863, Shard::Antarctica),
+    ];
+
+    #[test]
+    fn test_shard_assignment_city_corpus() {
+        for (city, latitude, longitude, expected) in CITY_SHARDS {
+            let location = GeoLocation {
+                latitude,
+                longitude,
+                accuracy_meters: 1000.0,
+            };
+            assert_eq!(PopValidator::assign_shard(&location), expected, "{}", city);
+        }
+
+        for shard in Shard::all() {
+            assert!(
+                CITY_SHARDS
+                    .iter()
+                    .any(|(_, _, _, expected)| *expected == shard),
+                "no reference city for {:?}",
+                shard
+            );
+        }
+    }
+
+    #[test]
+    fn test_shard_assignment_wraps_dateline() {
+        // 188.25°E is Apia expressed past the antimeridian
+        let apia = GeoLocation {
+            latitude: -13.8333,
+            longitude: 188.25,
             accuracy_meters: 1000.0,
         };
-        assert_eq!(PopValidator::assign_shard(&london), Shard::Europe);
+        assert_eq!(PopValidator::assign_shard(&apia), Shard::Oceania);
+        assert_eq!(normalize_longitude(180.0), -180.0);
+        assert_eq!(normalize_longitude(-190.0), 170.0);
+    }
 
-        // Test Tokyo
-        let tokyo = GeoLocation {
-            latitude: 35.6762,
-            longitude: 139.6503,
-            accuracy_meters: 1000.0,
+    #[test]
+    fn test_bounding_boxes_agree_with_assignment() {
+        let oceania = Shard::Oceania.bounding_box();
+        assert!(oceania.crosses_antimeridian());
+        assert_eq!(
+            (oceania.min_longitude, oceania.max_longitude),
+            (110.0, -150.0)
+        );
+        let asia = Shard::Asia.bounding_box();
+        assert_eq!((asia.min_latitude, asia.max_latitude), (-10.0, 82.0));
+        assert_eq!((asia.min_longitude, asia.max_longitude), (34.0, 180.0));
+        assert_eq!(Shard::Antarctica.bounding_box().min_latitude, -90.0);
+
+        // Every point on a half-degree grid lies in its assigned shard's
+        // box, unless it matched no region and fell back to North America
+        for lat in -180..=180 {
+            for lon in -360..360 {
+                let location = GeoLocation {
+                    latitude: lat as f64 / 2.0,
+                    longitude: lon as f64 / 2.0,
+                    accuracy_meters: 0.0,
+                };
+                let shard = PopValidator::assign_shard(&location);
+                if region_shard(location.latitude, location.longitude).is_some() {
+                    assert!(shard.bounding_box().contains(&location), "{:?}", location);
+                } else {
+                    assert_eq!(shard, Shard::NorthAmerica);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_implausible_stake_rejected() {
+        let (mut validator, keys) = test_network(4);
+        validator.policy.max_stake_fraction = 0.01; // 100M of the 10B supply
+
+        let mut node = validator.nodes[&keys[0]].clone();
+        node.stake_amount = StakeAmount::from_meshx(100_000_000);
+        assert_eq!(
+            validator.validate_node(&node),
+            Ok(ValidationOutcome::default())
+        );
+
+        node.stake_amount = StakeAmount::from_meshx(100_000_001);
+        assert_eq!(
+            validator.validate_node(&node),
+            Err(ValidationError::ImplausibleStake)
+        );
+
+        node.stake_amount = StakeAmount::MAX;
+        assert_eq!(
+            validator.validate_node(&node),
+            Err(ValidationError::ImplausibleStake)
+        );
+    }
+
+    #[test]
+    fn test_attack_cost() {
+        let just_over =
+            |meshx| StakeAmount::from_meshx(meshx).saturating_add(StakeAmount::from_base_units(1));
+        let (mut validator, keys) = test_network(3);
+        assert_eq!(validator.attack_cost(Shard::Europe), just_over(300_000));
+
+        // More honest stake makes the shard more expensive to attack
+        validator.nodes.get_mut(&keys[0]).unwrap().stake_amount = StakeAmount::from_meshx(500_000);
+        assert_eq!(validator.attack_cost(Shard::Europe), just_over(700_000));
+
+        // A thinly staked shard is bounded below only by its minimum stake
+        let mut antarctic = test_node(9, -77.8, 166.7, 12_000);
+        antarctic.shard = Shard::Antarctica;
+        validator.nodes.insert(antarctic.pubkey, antarctic);
+        assert_eq!(validator.attack_cost(Shard::Antarctica), just_over(12_000));
+        assert_eq!(
+            validator.attack_cost(Shard::Oceania),
+            StakeAmount::from_meshx(50_000)
+        );
+    }
+
+    #[test]
+    fn test_effective_stake_shared_across_features() {
+        let (mut validator, keys) = test_network(4);
+        validator.reputation_stake_influence = 0.5;
+        validator.validator_count = 4;
+        assert_eq!(
+            validator.effective_stake(&keys[0]),
+            StakeAmount::from_meshx(100_000)
+        );
+        let centroid = validator.stake_weighted_centroid(Shard::Europe).unwrap();
+
+        // Half the reputation gone, at half influence, costs a quarter
+        validator.nodes.get_mut(&keys[0]).unwrap().reputation_score = 0.5;
+        let effective = validator.effective_stake(&keys[0]);
+        assert_eq!(effective, StakeAmount::from_meshx(75_000));
+
+        // Selection, validation reports and the stake centroid all see it
+        let transcript = validator.selection_transcript(Shard::Europe, 1).unwrap();
+        let candidate = transcript
+            .candidates
+            .iter()
+            .find(|candidate| candidate.pubkey == keys[0])
+            .unwrap();
+        assert_eq!(candidate.effective_weight, effective);
+        let node = validator.nodes[&keys[0]].clone();
+        assert_eq!(
+            validator.validation_report(&node).stake.effective_stake,
+            effective
+        );
+        let shifted = validator.stake_weighted_centroid(Shard::Europe).unwrap();
+        assert!(haversine_distance(&centroid, &shifted) > 100.0);
+        assert_eq!(
+            validator.effective_stake(&test_pubkey(99)),
+            StakeAmount::ZERO
+        );
+
+        let snapshot = validator.stake_snapshot(1);
+        let entry = snapshot
+            .entries
+            .iter()
+            .find(|entry| entry.pubkey == keys[0])
+            .unwrap();
+        assert_eq!(entry.effective_stake, effective);
+        assert_eq!(
+            validator.attack_cost(Shard::Europe),
+            StakeAmount::from_meshx(375_000).saturating_add(StakeAmount::from_base_units(1))
+        );
+
+        // Rewards split 75:100:100:100
+        validator
+            .reward_policy
+            .shard_emission_per_epoch
+            .insert(Shard::Europe, StakeAmount::from_meshx(1_500));
+        let payouts = validator.distribute_rewards(1).unwrap();
+        assert_eq!(payouts[&keys[0]], StakeAmount::from_meshx(300));
+        assert_eq!(payouts[&keys[1]], StakeAmount::from_meshx(400));
+    }
+
+    #[test]
+    fn test_lower_reputation_lowers_selection_frequency() {
+        let (mut validator, keys) = test_network(10);
+        validator.reputation_stake_influence = 0.9;
+        validator.validator_count = 3;
+        let selections = |validator: &PopValidator| {
+            (0..300)
+                .filter(|epoch| {
+                    validator
+                        .select_validators(*epoch)
+                        .unwrap()
+                        .contains(&keys[0])
+                })
+                .count()
         };
-        assert_eq!(PopValidator::assign_shard(&tokyo), Shard::Asia);
+        let trusted = selections(&validator);
+        let trusted_probability = validator.selection_probability(&keys[0]).unwrap();
+
+        // A tenth of the effective stake, so a small share of the draw
+        validator.nodes.get_mut(&keys[0]).unwrap().reputation_score = 0.0;
+        let distrusted = selections(&validator);
+        assert!(distrusted * 3 < trusted, "{} vs {}", distrusted, trusted);
+        assert!(validator.selection_probability(&keys[0]).unwrap() < trusted_probability / 3.0);
     }
 
     #[test]
-    fn test_haversine_distance() {
-        let loc1 = GeoLocation {
-            latitude: 40.7128,
-            longitude: -74.0060,
-            accuracy_meters: 1000.0,
+    fn test_min_honest_stake_for_liveness() {
+        let (mut validator, keys) = test_network(4);
+        for (i, key) in keys.iter().enumerate() {
+            validator.nodes.get_mut(key).unwrap().stake_amount =
+                StakeAmount::from_meshx(100_000 * (i as u64 + 1));
+        }
+        validator.minimum_nodes = 2;
+        let two_nodes = validator.min_honest_stake_for_liveness(Shard::Europe);
+        assert_eq!(two_nodes, StakeAmount::from_meshx(300_000));
+
+        validator.minimum_nodes = 1;
+        let one_node = validator.min_honest_stake_for_liveness(Shard::Europe);
+        assert!(one_node < two_nodes);
+        assert_eq!(one_node, StakeAmount::from_meshx(100_000));
+
+        // A frozen node can't be relied on, so the next smallest takes its place
+        validator.slash_node(&keys[0], StakeAmount::ZERO).unwrap();
+        assert_eq!(
+            validator.min_honest_stake_for_liveness(Shard::Europe),
+            StakeAmount::from_meshx(200_000)
+        );
+
+        // Missing validators must join at the shard minimum
+        validator.minimum_nodes = 5;
+        assert_eq!(
+            validator.min_honest_stake_for_liveness(Shard::Europe),
+            StakeAmount::from_meshx(1_100_000)
+        );
+    }
+
+    #[test]
+    fn test_epoch_survives_backwards_clock_jump() {
+        let clock = Arc::new(crate::clock::ManualClock::new(10 * 3600 + 60));
+        let mut validator = PopValidator::new(1);
+        validator.set_clock(clock.clone());
+
+        assert_eq!(validator.advance_epoch(), 10);
+        clock.advance(3600);
+        assert_eq!(validator.advance_epoch(), 11);
+
+        // Wall clock steps back two hours; time keeps moving monotonically
+        clock.set_wall(9 * 3600);
+        assert_eq!(validator.advance_epoch(), 11);
+        assert_eq!(validator.now_secs(), 11 * 3600 + 60);
+
+        // Even a fresh anchor on the rewound clock can't move the epoch back
+        validator.set_clock(clock.clone());
+        assert_eq!(validator.advance_epoch(), 11);
+        assert_eq!(validator.current_epoch, 11);
+    }
+
+    #[test]
+    fn test_staggered_shard_epochs() {
+        let mut validator = PopValidator::new(1);
+        validator.genesis_time = 1_000;
+        validator.shard_epoch_offsets_secs.insert(Shard::Asia, 600);
+        // Offsets wrap at the epoch length
+        validator
+            .shard_epoch_offsets_secs
+            .insert(Shard::Africa, 3600 + 1200);
+
+        let boundary = 1_000 + 5 * 3600;
+        assert_eq!(validator.shard_transition_time(Shard::Europe, 5), boundary);
+        assert_eq!(
+            validator.shard_transition_time(Shard::Asia, 5),
+            boundary + 600
+        );
+        assert_eq!(
+            validator.shard_transition_time(Shard::Africa, 5),
+            boundary + 1200
+        );
+
+        // Same global schedule, different wall-clock transitions
+        assert_eq!(validator.shard_epoch_for_time(Shard::Europe, boundary), 5);
+        assert_eq!(validator.shard_epoch_for_time(Shard::Asia, boundary), 4);
+        assert_eq!(
+            validator.shard_epoch_for_time(Shard::Asia, boundary + 599),
+            4
+        );
+        assert_eq!(
+            validator.shard_epoch_for_time(Shard::Asia, boundary + 600),
+            5
+        );
+        assert_eq!(validator.current_epoch_for_time(boundary + 600), 5);
+
+        // Transitions follow the offsets as the clock runs
+        let clock = Arc::new(crate::clock::ManualClock::new(boundary - 60));
+        validator.set_clock(clock.clone());
+        validator.advance_epoch();
+        validator.advance_shard_epochs();
+        assert_eq!(validator.shard_epoch(Shard::Asia), 4);
+        clock.advance(60);
+        assert_eq!(validator.advance_epoch(), 5);
+        let transitioned = validator.advance_shard_epochs();
+        assert!(transitioned.contains(&(Shard::Europe, 5)));
+        assert!(!transitioned.iter().any(|(shard, _)| *shard == Shard::Asia));
+        assert_eq!(validator.shard_epoch(Shard::Asia), 4);
+        clock.advance(600);
+        validator.advance_epoch();
+        assert_eq!(validator.advance_shard_epochs(), vec![(Shard::Asia, 5)]);
+        assert_eq!(validator.shard_epoch(Shard::Africa), 4);
+        clock.advance(600);
+        validator.advance_epoch();
+        assert_eq!(validator.advance_shard_epochs(), vec![(Shard::Africa, 5)]);
+        assert!(validator.advance_shard_epochs().is_empty());
+    }
+
+    #[test]
+    fn test_pending_node_promotion() {
+        let (mut validator, keys) = test_network(4);
+        let newcomer = test_node(9, 50.5, 10.0, 100_000);
+        let newcomer_key = newcomer.pubkey;
+
+        assert_eq!(
+            validator.register_node(newcomer.clone()),
+            Err(ValidationError::InsufficientLatencyData)
+        );
+        validator.register_node_pending(newcomer).unwrap();
+        assert!(validator.pending_nodes.contains_key(&newcomer_key));
+        assert!(!validator
+            .select_validators(0)
+            .unwrap()
+            .contains(&newcomer_key));
+
+        // Not enough anchors yet
+        validator.latency_matrix.insert((keys[0], newcomer_key), 10);
+        assert_eq!(
+            validator.promote_pending(&newcomer_key),
+            Err(ValidationError::InsufficientLatencyData)
+        );
+        assert!(validator.pending_nodes.contains_key(&newcomer_key));
+
+        for anchor in &keys[1..3] {
+            validator.latency_matrix.insert((*anchor, newcomer_key), 10);
+        }
+        validator.promote_pending(&newcomer_key).unwrap();
+        assert!(validator.pending_nodes.is_empty());
+        assert!(validator.nodes.contains_key(&newcomer_key));
+        assert!(validator
+            .select_validators(0)
+            .unwrap()
+            .contains(&newcomer_key));
+    }
+
+    #[test]
+    fn test_pending_path_keeps_registered_state() {
+        let (mut validator, keys) = test_network(4);
+        validator.tee_change_hold_epochs = Some(2);
+        validator.nodes.get_mut(&keys[0]).unwrap().reputation_score = 0.1;
+
+        // Re-registering through the pending path can't reset reputation or
+        // switch TEE type unnoticed
+        let mut claimed = validator.nodes[&keys[0]].clone();
+        claimed.reputation_score = 1.0;
+        claimed.tee_attestation.tee_type = TeeType::AmdSev;
+        validator.register_node_pending(claimed).unwrap();
+        assert!(validator.pending_nodes.is_empty());
+        assert_eq!(validator.nodes[&keys[0]].reputation_score, 0.1);
+        assert!(validator.tee_change_holds.contains_key(&keys[0]));
+
+        // Nor can a pending record promoted over a registration made since
+        let newcomer = test_node(9, 50.5, 10.0, 100_000);
+        let newcomer_key = newcomer.pubkey;
+        validator.register_node_pending(newcomer.clone()).unwrap();
+        for anchor in &keys[..3] {
+            validator.latency_matrix.insert((*anchor, newcomer_key), 10);
+        }
+        validator.register_node(newcomer).unwrap();
+        validator
+            .nodes
+            .get_mut(&newcomer_key)
+            .unwrap()
+            .reputation_score = 0.1;
+        validator.promote_pending(&newcomer_key).unwrap();
+        assert_eq!(validator.nodes[&newcomer_key].reputation_score, 0.1);
+    }
+
+    #[test]
+    fn test_parallel_triangulation_matches_serial() {
+        let (mut validator, keys) = test_network(6);
+        validator.triangulation_threads = 3;
+        for seed in 10..17u8 {
+            let pending = test_node(seed, 50.0 + seed as f64 * 0.05, 10.2, 100_000);
+            let anchors = keys.iter().cycle().skip(seed as usize).take(3);
+            for (i, anchor) in anchors.enumerate() {
+                validator
+                    .latency_matrix
+                    .insert((*anchor, pending.pubkey), 5 + seed as u32 + i as u32);
+            }
+            validator.pending_nodes.insert(pending.pubkey, pending);
+        }
+        let unanchored = test_node(20, 51.0, 10.0, 100_000);
+        validator
+            .pending_nodes
+            .insert(unanchored.pubkey, unanchored);
+
+        let results = validator.triangulate_all_pending();
+        assert_eq!(results.len(), 8);
+        for (pubkey, result) in &results {
+            assert_eq!(*result, validator.triangulate_node(pubkey));
+        }
+        assert_eq!(
+            results[&test_pubkey(20)],
+            Err(ValidationError::InsufficientLatencyData)
+        );
+    }
+
+    #[test]
+    fn test_soft_failures_admit_with_penalty() {
+        let (mut validator, keys) = test_network(4);
+        validator.policy.soft_failures = vec![ValidationError::InsufficientLatencyData];
+        validator.policy.soft_failure_penalty = 0.25;
+
+        // Only two anchors have measured the newcomer
+        let newcomer = test_node(10, 50.0, 10.0, 100_000);
+        for anchor in &keys[..2] {
+            validator
+                .latency_matrix
+                .insert((*anchor, newcomer.pubkey), 10);
+        }
+        assert_eq!(
+            validator.validate_node(&newcomer),
+            Ok(ValidationOutcome {
+                soft_failures: vec![ValidationError::InsufficientLatencyData],
+            })
+        );
+        validator.register_node(newcomer.clone()).unwrap();
+        assert_eq!(validator.nodes[&newcomer.pubkey].reputation_score, 0.75);
+
+        // Unclassified failures still reject
+        let mut understaked = newcomer.clone();
+        understaked.stake_amount = StakeAmount::from_meshx(1_000);
+        assert_eq!(
+            validator.register_node(understaked),
+            Err(ValidationError::InsufficientStake)
+        );
+    }
+
+    #[test]
+    fn test_reregistration_preserves_standing() {
+        let (mut validator, keys) = test_network(4);
+        let key = keys[0];
+        let existing = validator.nodes.get_mut(&key).unwrap();
+        existing.reputation_score = 0.9;
+        existing.tee_attestation.timestamp -= 10;
+        validator
+            .reward_balances
+            .insert(key, StakeAmount::from_meshx(500));
+
+        // Restarted node re-registers with a fresh attestation and new resources
+        let mut restarted = validator.nodes[&key].clone();
+        restarted.reputation_score = 1.0;
+        restarted.tee_attestation.timestamp += 5;
+        restarted.tee_attestation.quote = test_quote(1);
+        restarted.resources.cpu_cores = 16;
+        validator.register_node(restarted.clone()).unwrap();
+
+        let node = &validator.nodes[&key];
+        assert_eq!(node.reputation_score, 0.9);
+        assert_eq!(node.tee_attestation.quote, test_quote(1));
+        assert_eq!(node.resources.cpu_cores, 16);
+        assert_eq!(
+            validator.reward_balances[&key],
+            StakeAmount::from_meshx(500)
+        );
+
+        // A record older than the one on file doesn't roll it back
+        let mut outdated = restarted;
+        outdated.tee_attestation.timestamp -= 2;
+        outdated.resources.cpu_cores = 4;
+        assert_eq!(
+            validator.register_node(outdated),
+            Err(ValidationError::StaleAttestation)
+        );
+        assert_eq!(validator.nodes[&key].resources.cpu_cores, 16);
+    }
+
+    #[test]
+    fn test_attestation_regression_rejected() {
+        let mut validator = PopValidator::new(1);
+        let key = test_pubkey(1);
+        let mut attestation = test_node(1, 50.1, 10.0, 100_000).tee_attestation;
+        validator
+            .record_attestation_timestamp(&key, &attestation)
+            .unwrap();
+
+        attestation.timestamp -= 5;
+        assert_eq!(
+            validator.record_attestation_timestamp(&key, &attestation),
+            Err(ValidationError::AttestationRegression)
+        );
+
+        attestation.timestamp += 10;
+        validator
+            .record_attestation_timestamp(&key, &attestation)
+            .unwrap();
+        assert_eq!(
+            validator.attestation_timestamps[&key],
+            (0, attestation.timestamp)
+        );
+
+        // A new epoch restarts the ordering
+        validator.current_epoch = 1;
+        attestation.timestamp -= 20;
+        validator
+            .record_attestation_timestamp(&key, &attestation)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_expected_wait_until_selection() {
+        let (mut validator, keys) = test_network(4);
+        validator.validator_count = 4;
+        assert_eq!(validator.selection_probability(&keys[0]), Some(1.0));
+        let certain_wait = validator.expected_epochs_until_selection(&keys[0]);
+        assert_eq!(certain_wait, Some(1.0));
+
+        // Fewer seats for the same nodes lowers the chance and lengthens the wait
+        validator.validator_count = 2;
+        assert_eq!(validator.selection_probability(&keys[0]), Some(0.5));
+        let halved_wait = validator.expected_epochs_until_selection(&keys[0]);
+        assert_eq!(halved_wait, Some(2.0));
+        assert!(halved_wait > certain_wait);
+        assert_eq!(
+            validator.expected_time_until_selection(&keys[0]),
+            Some(Duration::from_secs(7200))
+        );
+        // Two epochs of the longest configurable length overflow a Duration
+        let epoch_length_secs = validator.epoch_length_secs;
+        validator.epoch_length_secs = u64::MAX;
+        assert_eq!(validator.expected_time_until_selection(&keys[0]), None);
+        validator.epoch_length_secs = epoch_length_secs;
+
+        validator.slash_node(&keys[1], StakeAmount::ZERO).unwrap();
+        assert_eq!(validator.expected_epochs_until_selection(&keys[1]), None);
+        assert_eq!(
+            validator.expected_epochs_until_selection(&test_pubkey(9)),
+            None
+        );
+        let wait = validator.expected_epochs_until_selection(&keys[0]).unwrap();
+        assert!((wait - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_selection_probability_follows_tee_balance() {
+        let (mut validator, keys) = test_network(4);
+        validator.validator_count = 2;
+        validator
+            .nodes
+            .get_mut(&keys[3])
+            .unwrap()
+            .tee_attestation
+            .tee_type = TeeType::AmdSev;
+        validator.record_selection(0, &[keys[0], keys[1]]);
+        assert_eq!(validator.selection_probability(&keys[0]), Some(0.5));
+        assert_eq!(validator.selection_probability(&keys[3]), Some(0.5));
+
+        // An all-SGX selection halves SGX's draw weight, so the AMD node is
+        // twice as likely to be drawn as any SGX node
+        validator.tee_balance_strength = 1.0;
+        let sgx = validator.selection_probability(&keys[0]).unwrap();
+        let amd = validator.selection_probability(&keys[3]).unwrap();
+        assert!((sgx - 0.4).abs() < 1e-9);
+        assert!((amd - 0.8).abs() < 1e-9);
+
+        // Balance is judged per shard against the recorded selection: a
+        // shard nobody was selected from keeps full weight, and a local
+        // change to the current set doesn't move the draw
+        validator.nodes.get_mut(&keys[2]).unwrap().shard = Shard::Asia;
+        validator.current_validator_set = vec![keys[3]];
+        let sgx = validator.selection_probability(&keys[0]).unwrap();
+        let other_shard = validator.selection_probability(&keys[2]).unwrap();
+        assert!((sgx - 1.0 / 3.0).abs() < 1e-9);
+        assert!((other_shard - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tee_balance_reduces_overrepresented_share() {
+        let sgx_share = |strength: f64| {
+            let (mut validator, keys) = test_network(20);
+            for key in &keys[15..] {
+                validator
+                    .nodes
+                    .get_mut(key)
+                    .unwrap()
+                    .tee_attestation
+                    .tee_type = TeeType::AmdSev;
+            }
+            validator.validator_count = 8;
+            validator.tee_balance_strength = strength;
+            (0..20)
+                .map(|epoch| {
+                    let selected = validator.select_validators(epoch).unwrap();
+                    validator.record_selection(epoch, &selected);
+                    let sgx = selected
+                        .iter()
+                        .filter(|key| {
+                            validator.nodes[*key].tee_attestation.tee_type == TeeType::IntelSgx
+                        })
+                        .count();
+                    sgx as f64 / selected.len() as f64
+                })
+                .collect::<Vec<_>>()
         };
+        let mean = |shares: &[f64]| shares.iter().sum::<f64>() / shares.len() as f64;
 
-        let loc2 = GeoLocation {
-            latitude: 51.5074,
-            longitude: -0.1278,
-            accuracy_meters: 1000.0,
+        let unbalanced = sgx_share(0.0);
+        let balanced = sgx_share(4.0);
+        // The first epoch has no validator set to balance against
+        assert_eq!(balanced[0], unbalanced[0]);
+        assert!(mean(&balanced[10..]) < mean(&unbalanced[10..]));
+        assert!(mean(&balanced[10..]) < balanced[0]);
+    }
+
+    #[test]
+    fn test_tee_diversity_in_selection() {
+        let (mut validator, _) = test_network(5);
+        let ranked = validator.select_validators(0).unwrap();
+        let last = ranked[4];
+        validator
+            .nodes
+            .get_mut(&last)
+            .unwrap()
+            .tee_attestation
+            .tee_type = TeeType::AmdSev;
+
+        // The AMD node ranks outside a three-validator selection
+        validator.validator_count = 3;
+        assert_eq!(validator.select_validators(0).unwrap(), ranked[..3]);
+
+        validator.min_tee_types_per_shard = 2;
+        let selected = validator.select_validators(0).unwrap();
+        assert_eq!(selected, vec![ranked[0], ranked[1], last]);
+    }
+
+    #[test]
+    fn test_reputation_weighted_anchors() {
+        let mut validator = PopValidator::new(1);
+        let truth = test_node(1, 50.0, 10.0, 100_000).geo_location;
+        let honest = [
+            test_node(2, 50.5, 10.0, 100_000),
+            test_node(3, 49.5, 10.0, 100_000),
+            test_node(4, 50.0, 10.5, 100_000),
+        ];
+        // Claims to be close while sitting far to the north-east
+        let mut liar = test_node(5, 55.0, 20.0, 100_000);
+        liar.reputation_score = 0.1;
+        let anchors: Vec<(&MeshXNode, u32)> = honest
+            .iter()
+            .map(|node| (node, 1))
+            .chain([(&liar, 1)])
+            .collect();
+
+        let unweighted = validator.triangulate_anchors(&anchors).unwrap();
+        validator.policy.anchor_reputation_influence = 1.0;
+        let weighted = validator.triangulate_anchors(&anchors).unwrap();
+        assert!(
+            haversine_distance(&weighted, &truth) < haversine_distance(&unweighted, &truth) / 2.0
+        );
+    }
+
+    #[test]
+    fn test_cross_check_bandwidth() {
+        let (mut validator, keys) = test_network(4);
+        validator
+            .nodes
+            .get_mut(&keys[0])
+            .unwrap()
+            .resources
+            .bandwidth_mbps = 1_000;
+        assert_eq!(validator.cross_check_bandwidth(&keys[0]), Ok(()));
+
+        // 500ms from every shard peer caps a flow around 134 Mbps
+        for peer in &keys[1..] {
+            validator.latency_matrix.insert((keys[0], *peer), 500);
+            validator.latency_matrix.insert((*peer, keys[0]), 500);
+        }
+        assert_eq!(
+            validator.cross_check_bandwidth(&keys[0]),
+            Err(ValidationError::ImplausibleBandwidth)
+        );
+        validator
+            .nodes
+            .get_mut(&keys[0])
+            .unwrap()
+            .resources
+            .bandwidth_mbps = 100;
+        assert_eq!(validator.cross_check_bandwidth(&keys[0]), Ok(()));
+    }
+
+    #[test]
+    fn test_resource_fluctuation_tolerance() {
+        let (mut validator, keys) = test_network(4);
+        let mut node = validator.nodes[&keys[0]].clone();
+
+        // 100 -> 95 Mbps is within the 10% tolerance
+        node.resources.bandwidth_mbps = 95;
+        validator.register_node(node.clone()).unwrap();
+        assert_eq!(validator.nodes[&keys[0]].resources.bandwidth_mbps, 95);
+
+        // 500 -> 300 GB is a 40% drop
+        node.resources.storage_gb = 300;
+        assert_eq!(
+            validator.register_node(node),
+            Err(ValidationError::ResourceDowngrade)
+        );
+        assert_eq!(validator.nodes[&keys[0]].resources.storage_gb, 500);
+    }
+
+    #[test]
+    fn test_select_triangulation_anchors() {
+        let truth = GeoLocation {
+            latitude: 50.0,
+            longitude: 10.0,
+            accuracy_meters: 0.0,
+        };
+        let mut validator = PopValidator::new(1);
+        // A tight cluster to the north plus one anchor each east, west and south
+        let mut anchors: Vec<_> = (0..5)
+            .map(|i| test_node(10 + i, 55.0, 9.5 + 0.25 * i as f64, 100_000))
+            .collect();
+        anchors.push(test_node(20, 50.0, 17.0, 100_000));
+        anchors.push(test_node(21, 50.0, 3.0, 100_000));
+        anchors.push(test_node(22, 45.0, 10.0, 100_000));
+        let target = test_pubkey(1);
+        for anchor in &anchors {
+            let km = haversine_distance(&anchor.geo_location, &truth) / 1000.0;
+            let latency = (km / FIBER_KM_PER_MS).round().max(1.0) as u32;
+            validator
+                .latency_matrix
+                .insert((anchor.pubkey, target), latency);
+            validator.nodes.insert(anchor.pubkey, anchor.clone());
+        }
+
+        let chosen = validator.select_triangulation_anchors(&target, 3);
+        assert_eq!(chosen.len(), 3);
+        let located = |keys: &[PublicKey]| -> Vec<&MeshXNode> {
+            keys.iter().map(|key| &validator.nodes[key]).collect()
         };
+        let arbitrary: Vec<_> = anchors[..3].iter().map(|a| a.pubkey).collect();
 
-        let distance = haversine_distance(&loc1, &loc2);
-        assert!((distance - 5_570_000.0).abs() < 10_000.0); // ~5570km ± 10km
+        let gdop = |nodes: &[&MeshXNode]| {
+            let locations: Vec<_> = nodes.iter().map(|n| &n.geo_location).collect();
+            geometric_dilution(&truth, &locations)
+        };
+        assert!(gdop(&located(&chosen)) < gdop(&located(&arbitrary)));
+
+        let error = |nodes: Vec<&MeshXNode>| {
+            let with_latency: Vec<_> = nodes
+                .into_iter()
+                .map(|n| (n, validator.latency_matrix[&(n.pubkey, target)]))
+                .collect();
+            let estimate = validator.triangulate_anchors(&with_latency).unwrap();
+            haversine_distance(&estimate, &truth)
+        };
+        assert!(error(located(&chosen)) <= error(located(&arbitrary)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_state_fingerprint() {
+        let (mut validator, keys) = test_network(4);
+        let fingerprint = validator.state_fingerprint();
+        assert_eq!(validator.state_fingerprint(), fingerprint);
+
+        // Insertion order doesn't matter
+        let mut replica = PopValidator::new(4);
+        for key in keys.iter().rev() {
+            replica.nodes.insert(*key, validator.nodes[key].clone());
+        }
+        for (edge, latency) in &validator.latency_matrix {
+            replica.latency_matrix.insert(*edge, *latency);
+        }
+        assert_eq!(replica.state_fingerprint(), fingerprint);
+
+        validator.nodes.get_mut(&keys[0]).unwrap().stake_amount =
+            StakeAmount::from_meshx(100_000).saturating_add(StakeAmount::from_base_units(1));
+        let changed_node = validator.state_fingerprint();
+        assert_ne!(changed_node, fingerprint);
+
+        validator.latency_matrix.insert((keys[0], keys[1]), 11);
+        let changed_edge = validator.state_fingerprint();
+        assert_ne!(changed_edge, changed_node);
+
+        validator.current_epoch += 1;
+        assert_ne!(validator.state_fingerprint(), changed_edge);
+    }
+
+    #[test]
+    fn test_rtt_measurement_halved() {
+        let mut measurement = LatencyMeasurement {
+            from_node: test_pubkey(1),
+            to_node: test_pubkey(2),
+            latency_ms: 20,
+            timestamp: 0,
+            is_rtt: false,
+        };
+        let one_way_distance = measurement.max_distance_km();
+        measurement.is_rtt = true;
+        assert_eq!(measurement.max_distance_km(), one_way_distance / 2.0);
+
+        let mut validator = PopValidator::new(1);
+        validator.record_latency(&measurement);
+        assert_eq!(
+            validator.latency_matrix[&(test_pubkey(1), test_pubkey(2))],
+            10
+        );
+
+        // Records serialized before the field existed are one-way
+        let mut legacy = serde_json::to_value(&measurement).unwrap();
+        legacy.as_object_mut().unwrap().remove("is_rtt");
+        let legacy: LatencyMeasurement = serde_json::from_value(legacy).unwrap();
+        assert!(!legacy.is_rtt);
+    }
+}
@@ -0,0 +1,64 @@
+// MeshX - The Immutable Global Device Mesh
+// Published enclave releases
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{hex_encode, PopValidator};
+
+// A published build of the MeshX validator enclave
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    pub enclave_hash: [u8; 32],
+    pub version: String,
+    pub build_date: String, // As published, e.g. "2025-12-01"
+}
+
+impl PopValidator {
+    // The release `hash` was published as, looked up in the policy's
+    // `known_releases`. Warns when the hash isn't a recognized release.
+    pub fn verify_enclave_hash_known(&self, hash: &[u8; 32]) -> Option<ReleaseInfo> {
+        let release = self
+            .policy
+            .known_releases
+            .iter()
+            .find(|release| release.enclave_hash == *hash)
+            .cloned();
+        if release.is_none() {
+            tracing::warn!(
+                enclave_hash = %hex_encode(hash),
+                "enclave hash doesn't match any known MeshX release"
+            );
+        }
+        release
+    }
+
+    // Release of the enclave hash attestations are checked against
+    pub fn expected_enclave_release(&self) -> Option<ReleaseInfo> {
+        self.verify_enclave_hash_known(&self.get_expected_enclave_hash())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enclave_hash_lookup() {
+        let release = ReleaseInfo {
+            enclave_hash: [0x42; 32],
+            version: "0.1.0".to_string(),
+            build_date: "2025-12-01".to_string(),
+        };
+        let mut validator = PopValidator::new(1);
+        assert_eq!(validator.expected_enclave_release(), None);
+
+        validator.policy.known_releases.push(release.clone());
+        assert_eq!(validator.expected_enclave_release(), Some(release.clone()));
+        assert_eq!(
+            validator.verify_enclave_hash_known(&[0x42; 32]),
+            Some(release)
+        );
+        assert_eq!(validator.verify_enclave_hash_known(&[0x43; 32]), None);
+    }
+}
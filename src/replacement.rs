@@ -0,0 +1,101 @@
+// MeshX - The Immutable Global Device Mesh
+// Mid-epoch validator replacement
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+
+use crate::proof_of_presence::{PopValidator, Shard};
+
+impl PopValidator {
+    // Refill the seat of a validator that dropped out of the current set
+    // mid-epoch (slashed, banned or frozen) with the best-ranked candidate
+    // from the shard not already in the set. The ranking is the epoch's VRF
+    // ordering, so every validator picks the same replacement. None when
+    // replacement is disabled, `dropped` isn't in the current set, or no
+    // eligible candidate remains; the set is unchanged then. `dropped` may
+    // already be gone from the registry, e.g. after a ban.
+    pub fn replace_validator(
+        &mut self,
+        shard: Shard,
+        epoch: u64,
+        dropped: &PublicKey,
+    ) -> Option<PublicKey> {
+        if !self.mid_epoch_replacement {
+            return None;
+        }
+        let seat = self
+            .current_validator_set
+            .iter()
+            .position(|pubkey| pubkey == dropped)?;
+        let replacement = self
+            .ranked_candidates(epoch)
+            .into_iter()
+            .filter(|node| node.shard == shard && node.pubkey != *dropped)
+            .filter(|node| !self.current_validator_set.contains(&node.pubkey))
+            .filter(|node| !self.is_banned(&node.pubkey, epoch))
+            .find(|node| self.validate_node(node).is_ok())
+            .map(|node| node.pubkey)?;
+        self.current_validator_set[seat] = replacement;
+        Some(replacement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_network;
+
+    #[test]
+    fn test_dropped_validator_replaced_by_next_ranked() {
+        let (mut validator, _) = test_network(6);
+        validator.validator_count = 3;
+        validator.mid_epoch_replacement = true;
+        let selected = validator.select_validators(5).unwrap();
+        validator.record_selection(5, &selected);
+
+        // The fourth-ranked node is the one a larger set would have taken
+        validator.validator_count = 4;
+        let next = *validator
+            .select_validators(5)
+            .unwrap()
+            .iter()
+            .find(|pubkey| !selected.contains(pubkey))
+            .unwrap();
+        validator.validator_count = 3;
+
+        let (mut other, _) = test_network(6);
+        other.validator_count = 3;
+        other.mid_epoch_replacement = true;
+        other.record_selection(5, &selected);
+
+        validator.ban(&selected[1], None);
+        other.ban(&selected[1], None);
+        let replacement = validator.replace_validator(Shard::Europe, 5, &selected[1]);
+        assert_eq!(replacement, Some(next));
+        assert_eq!(
+            other.replace_validator(Shard::Europe, 5, &selected[1]),
+            replacement
+        );
+        assert_eq!(validator.current_validator_set[1], next);
+        assert_eq!(validator.current_validator_set, other.current_validator_set);
+
+        // A node no longer in the set has no seat to refill
+        assert_eq!(
+            validator.replace_validator(Shard::Europe, 5, &selected[1]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_replacement_disabled_by_default() {
+        let (mut validator, _) = test_network(6);
+        validator.validator_count = 3;
+        let selected = validator.select_validators(5).unwrap();
+        validator.record_selection(5, &selected);
+        assert_eq!(
+            validator.replace_validator(Shard::Europe, 5, &selected[0]),
+            None
+        );
+        assert_eq!(validator.current_validator_set, selected);
+    }
+}
@@ -0,0 +1,521 @@
+// MeshX - The Immutable Global Device Mesh
+// Network health reporting
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::proof_of_presence::{
+    haversine_distance, hex_encode, GeoLocation, LatencyMeasurement, MeshXNode, NodeSummary,
+    PopValidator, Shard, ValidationErrorKind,
+};
+use crate::stake::StakeAmount;
+
+// Selection chance below which a node's stake counts as idle
+const IDLE_SELECTION_PROBABILITY: f64 = 1e-6;
+
+// Registry and latency measurements as exported by a running node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkState {
+    pub nodes: Vec<MeshXNode>,
+    pub measurements: Vec<LatencyMeasurement>,
+}
+
+impl NetworkState {
+    // Load the state into a validator without re-validating it
+    pub fn into_validator(self, minimum_nodes: usize) -> PopValidator {
+        let mut validator = PopValidator::new(minimum_nodes);
+        for measurement in &self.measurements {
+            validator.record_latency(measurement);
+        }
+        for node in self.nodes {
+            validator.nodes.insert(node.pubkey, node);
+        }
+        validator
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardStats {
+    pub shard: Shard,
+    pub node_count: usize,
+    pub total_stake: StakeAmount,
+    pub centroid: Option<GeoLocation>,
+    pub stake_weighted_centroid: Option<GeoLocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardReport {
+    #[serde(flatten)]
+    pub stats: ShardStats,
+    pub attack_cost: StakeAmount,
+    pub stake_gini: f32,
+    pub coverage_gap: bool,
+}
+
+// Weighting of the components of `geographic_redundancy`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedundancyWeights {
+    pub across_shards: f32,        // Evenness of node counts over all shards
+    pub within_shards: f32,        // Distance of nodes from their shard centroid
+    pub within_shard_scale_m: f64, // Mean spread that scores half
+}
+
+impl Default for RedundancyWeights {
+    fn default() -> Self {
+        Self {
+            across_shards: 0.5,
+            within_shards: 0.5,
+            within_shard_scale_m: 500_000.0,
+        }
+    }
+}
+
+// Per-shard health plus the connectivity of the latency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkReport {
+    pub shards: Vec<ShardReport>,
+    pub partitions: Vec<Vec<String>>, // Hex pubkeys of each connected group
+    pub partitioned: bool,
+}
+
+impl PopValidator {
+    pub fn shard_stats(&self, shard: Shard) -> ShardStats {
+        let nodes: Vec<_> = self
+            .nodes
+            .values()
+            .filter(|node| node.shard == shard)
+            .collect();
+        ShardStats {
+            shard,
+            node_count: nodes.len(),
+            total_stake: nodes.iter().fold(StakeAmount::ZERO, |total, node| {
+                total.saturating_add(node.stake_amount)
+            }),
+            centroid: self.shard_centroid(shard),
+            stake_weighted_centroid: self.stake_weighted_centroid(shard),
+        }
+    }
+
+    // Summaries of the `count` registered nodes closest to `location`,
+    // nearest first; callers fetch full records as needed
+    pub fn nearest_nodes(&self, location: &GeoLocation, count: usize) -> Vec<NodeSummary> {
+        let mut nodes: Vec<(f64, &MeshXNode)> = self
+            .nodes
+            .values()
+            .map(|node| (haversine_distance(location, &node.geo_location), node))
+            .collect();
+        nodes.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        nodes
+            .into_iter()
+            .take(count)
+            .map(|(_, node)| node.summary())
+            .collect()
+    }
+
+    // How well spread the registry is globally, in 0..=1. Across shards this
+    // is the normalized entropy of node counts, 1 when every shard holds the
+    // same number. Within shards it is each shard's mean distance from its
+    // centroid as spread / (spread + scale), averaged by node count. The two
+    // are blended by `redundancy_weights`.
+    pub fn geographic_redundancy(&self) -> f32 {
+        let weights = &self.redundancy_weights;
+        let stats: Vec<ShardStats> = Shard::all()
+            .into_iter()
+            .map(|shard| self.shard_stats(shard))
+            .collect();
+        let total_nodes: usize = stats.iter().map(|stats| stats.node_count).sum();
+        if total_nodes == 0 {
+            return 0.0;
+        }
+
+        let entropy: f64 = stats
+            .iter()
+            .filter(|stats| stats.node_count > 0)
+            .map(|stats| {
+                let share = stats.node_count as f64 / total_nodes as f64;
+                -share * share.ln()
+            })
+            .sum();
+        let across = entropy / (stats.len() as f64).ln();
+
+        let within = stats
+            .iter()
+            .filter_map(|stats| {
+                let spread = stats.centroid.as_ref()?.accuracy_meters as f64;
+                let scale = weights.within_shard_scale_m.max(f64::MIN_POSITIVE);
+                Some(stats.node_count as f64 * spread / (spread + scale))
+            })
+            .sum::<f64>()
+            / total_nodes as f64;
+
+        let across_weight = weights.across_shards.max(0.0) as f64;
+        let within_weight = weights.within_shards.max(0.0) as f64;
+        if across_weight + within_weight == 0.0 {
+            return 0.0;
+        }
+        ((across_weight * across + within_weight * within) / (across_weight + within_weight)) as f32
+    }
+
+    // Shards with too few nodes to meet the validator minimum on their own
+    pub fn coverage_gaps(&self) -> Vec<Shard> {
+        Shard::all()
+            .into_iter()
+            .filter(|shard| self.shard_stats(*shard).node_count < self.minimum_nodes)
+            .collect()
+    }
+
+    // Staked nodes with effectively no chance of selection next epoch (failing
+    // validation, slashed, provisional, or crowded out), with their idle
+    // stake. Uses `selection_probability`, so TEE balancing counts too.
+    pub fn idle_stake_report(&self) -> HashMap<PublicKey, StakeAmount> {
+        self.nodes
+            .values()
+            .filter(|node| node.stake_amount > StakeAmount::ZERO)
+            .filter(|node| {
+                self.selection_probability(&node.pubkey)
+                    .is_none_or(|probability| probability < IDLE_SELECTION_PROBABILITY)
+            })
+            .map(|node| (node.pubkey, node.stake_amount))
+            .collect()
+    }
+
+    // Gini coefficient of effective stake among the shard's nodes: 0 when
+    // every node carries the same weight, approaching 1 as one node holds it
+    // all. 0 for shards with fewer than two nodes or no stake.
+    pub fn stake_gini(&self, shard: Shard) -> f32 {
+        let mut stakes: Vec<f64> = self
+            .nodes
+            .values()
+            .filter(|node| node.shard == shard)
+            .map(|node| self.effective_stake(&node.pubkey).base_units() as f64)
+            .collect();
+        let total: f64 = stakes.iter().sum();
+        if stakes.len() < 2 || total == 0.0 {
+            return 0.0;
+        }
+        stakes.sort_by(f64::total_cmp);
+        let n = stakes.len() as f64;
+        let ranked: f64 = stakes
+            .iter()
+            .enumerate()
+            .map(|(i, stake)| (i + 1) as f64 * stake)
+            .sum();
+        (2.0 * ranked / (n * total) - (n + 1.0) / n) as f32
+    }
+
+    // Groups of registered nodes connected by latency measurements in
+    // either direction. More than one group means the mesh is partitioned.
+    // Groups are ordered by size, largest first, and sorted internally.
+    pub fn detect_partitions(&self) -> Vec<Vec<PublicKey>> {
+        let mut neighbours: HashMap<PublicKey, Vec<PublicKey>> = HashMap::new();
+        for (from, to) in self.latency_matrix.keys() {
+            if self.nodes.contains_key(from) && self.nodes.contains_key(to) {
+                neighbours.entry(*from).or_default().push(*to);
+                neighbours.entry(*to).or_default().push(*from);
+            }
+        }
+
+        let mut keys: Vec<PublicKey> = self.nodes.keys().copied().collect();
+        keys.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        let mut visited: HashSet<PublicKey> = HashSet::new();
+        let mut partitions = Vec::new();
+        for start in keys {
+            if visited.contains(&start) {
+                continue;
+            }
+            let mut group = vec![start];
+            visited.insert(start);
+            let mut next = 0;
+            while next < group.len() {
+                for peer in neighbours.get(&group[next]).into_iter().flatten() {
+                    if visited.insert(*peer) {
+                        group.push(*peer);
+                    }
+                }
+                next += 1;
+            }
+            group.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+            partitions.push(group);
+        }
+        partitions.sort_by_key(|group| std::cmp::Reverse(group.len()));
+        partitions
+    }
+
+    // Run every validation check over the whole registry and group nodes by
+    // failure. A node failing several checks appears under each of them;
+    // nodes passing everything are omitted. Keys are sorted within a group.
+    pub fn scan_network(&self) -> HashMap<ValidationErrorKind, Vec<PublicKey>> {
+        let mut failures: HashMap<ValidationErrorKind, Vec<PublicKey>> = HashMap::new();
+        for node in self.nodes.values() {
+            for error in self.validation_report(node).errors() {
+                failures.entry(error.clone()).or_default().push(node.pubkey);
+            }
+        }
+        for nodes in failures.values_mut() {
+            nodes.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        }
+        failures
+    }
+
+    // Registry export safe to attach to bug reports: each node's raw TEE
+    // quote is replaced by its SHA3-256 hash
+    pub fn to_redacted_json(&self) -> String {
+        let redact = |node: &MeshXNode| {
+            let mut value = serde_json::to_value(node).expect("node serializes");
+            let attestation = value["tee_attestation"]
+                .as_object_mut()
+                .expect("attestation is an object");
+            attestation.remove("quote");
+            attestation.insert(
+                "quote_sha3".to_string(),
+                hex_encode(&Sha3_256::digest(&node.tee_attestation.quote)).into(),
+            );
+            value
+        };
+        let sorted = |nodes: &HashMap<PublicKey, MeshXNode>| {
+            let mut nodes: Vec<_> = nodes.values().collect();
+            nodes.sort_by(|a, b| a.pubkey.as_bytes().cmp(b.pubkey.as_bytes()));
+            nodes.into_iter().map(redact).collect::<Vec<_>>()
+        };
+
+        let mut latencies: Vec<_> = self
+            .latency_matrix
+            .iter()
+            .map(|((from, to), latency_ms)| {
+                serde_json::json!({
+                    "from_node": hex_encode(from.as_bytes()),
+                    "to_node": hex_encode(to.as_bytes()),
+                    "latency_ms": latency_ms,
+                })
+            })
+            .collect();
+        latencies.sort_by_key(|edge| (edge["from_node"].to_string(), edge["to_node"].to_string()));
+
+        serde_json::json!({
+            "current_epoch": self.current_epoch,
+            "policy": self.policy,
+            "nodes": sorted(&self.nodes),
+            "pending_nodes": sorted(&self.pending_nodes),
+            "latencies": latencies,
+        })
+        .to_string()
+    }
+
+    pub fn network_report(&self) -> NetworkReport {
+        let gaps = self.coverage_gaps();
+        let partitions: Vec<Vec<String>> = self
+            .detect_partitions()
+            .into_iter()
+            .map(|group| group.iter().map(|key| hex_encode(key.as_bytes())).collect())
+            .collect();
+        NetworkReport {
+            shards: Shard::all()
+                .into_iter()
+                .map(|shard| ShardReport {
+                    stats: self.shard_stats(shard),
+                    attack_cost: self.attack_cost(shard),
+                    stake_gini: self.stake_gini(shard),
+                    coverage_gap: gaps.contains(&shard),
+                })
+                .collect(),
+            partitioned: partitions.len() > 1,
+            partitions,
+        }
+    }
+}
+
+impl fmt::Display for NetworkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "📊 MeshX Network Report")?;
+        for shard in &self.shards {
+            let stats = &shard.stats;
+            writeln!(f, "\n{:?}", stats.shard)?;
+            writeln!(f, "   Nodes: {}", stats.node_count)?;
+            writeln!(f, "   Stake: {}", stats.total_stake)?;
+            if let Some(centroid) = &stats.centroid {
+                writeln!(
+                    f,
+                    "   Centroid: {:.4}, {:.4}",
+                    centroid.latitude, centroid.longitude
+                )?;
+            }
+            writeln!(f, "   Attack cost: {}", shard.attack_cost)?;
+            writeln!(f, "   Stake Gini: {:.3}", shard.stake_gini)?;
+            if shard.coverage_gap {
+                writeln!(f, "   ⚠️  Coverage gap")?;
+            }
+        }
+        if self.partitioned {
+            writeln!(
+                f,
+                "\n⚠️  Network partitioned into {} groups",
+                self.partitions.len()
+            )
+        } else {
+            writeln!(f, "\n✅ Network connected")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::{test_network, test_node};
+    use crate::proof_of_presence::ValidationError;
+
+    #[test]
+    fn test_detect_partitions() {
+        let (mut validator, keys) = test_network(4);
+        assert_eq!(validator.detect_partitions().len(), 1);
+
+        // Cut keys[0] off from everyone else
+        validator
+            .latency_matrix
+            .retain(|(from, to), _| *from != keys[0] && *to != keys[0]);
+        let partitions = validator.detect_partitions();
+        assert_eq!(partitions.len(), 2);
+        assert_eq!(partitions[0].len(), 3);
+        assert_eq!(partitions[1], vec![keys[0]]);
+
+        let report = validator.network_report();
+        assert!(report.partitioned);
+        assert_eq!(report.shards.len(), 7);
+    }
+
+    #[test]
+    fn test_nearest_nodes_returns_summaries() {
+        let mut validator = PopValidator::new(1);
+        let berlin = test_node(1, 52.5, 13.4, 100_000);
+        let paris = test_node(2, 48.9, 2.3, 100_000);
+        let tokyo = test_node(3, 35.7, 139.7, 100_000);
+        for node in [&berlin, &paris, &tokyo] {
+            validator.nodes.insert(node.pubkey, node.clone());
+        }
+
+        let near = GeoLocation {
+            latitude: 51.0,
+            longitude: 10.0,
+            accuracy_meters: 100.0,
+        };
+        assert_eq!(
+            validator.nearest_nodes(&near, 2),
+            vec![berlin.summary(), paris.summary()]
+        );
+        assert_eq!(validator.nearest_nodes(&near, 10).len(), 3);
+    }
+
+    #[test]
+    fn test_idle_stake_report() {
+        let (mut validator, keys) = test_network(5);
+        validator.nodes.get_mut(&keys[0]).unwrap().stake_amount = StakeAmount::from_meshx(10);
+        validator.slash_node(&keys[1], StakeAmount::ZERO).unwrap();
+
+        let idle = validator.idle_stake_report();
+        assert_eq!(idle.len(), 2);
+        assert_eq!(idle[&keys[0]], StakeAmount::from_meshx(10));
+        assert!(idle.contains_key(&keys[1]));
+        assert!(validator.selection_probability(&keys[2]).unwrap() > 0.0);
+        assert!(!idle.contains_key(&keys[2]));
+    }
+
+    #[test]
+    fn test_stake_gini() {
+        let (mut validator, keys) = test_network(20);
+        let shard = validator.nodes[&keys[0]].shard;
+        assert!(validator.stake_gini(shard).abs() < 1e-6);
+
+        for key in &keys[1..] {
+            validator.nodes.get_mut(key).unwrap().stake_amount = StakeAmount::from_meshx(1);
+        }
+        validator.nodes.get_mut(&keys[0]).unwrap().stake_amount =
+            StakeAmount::from_meshx(100_000_000);
+        // One holder of everything among n nodes scores (n - 1) / n
+        assert!(validator.stake_gini(shard) > 0.94);
+
+        let (single, _) = test_network(1);
+        assert_eq!(single.stake_gini(shard), 0.0);
+        assert_eq!(validator.stake_gini(Shard::Antarctica), 0.0);
+    }
+
+    #[test]
+    fn test_scan_network_groups_failures() {
+        let (mut validator, keys) = test_network(6);
+        for key in &keys[..3] {
+            validator
+                .nodes
+                .get_mut(key)
+                .unwrap()
+                .tee_attestation
+                .timestamp -= 7200;
+        }
+        let node = validator.nodes.get_mut(&keys[2]).unwrap();
+        node.stake_amount = StakeAmount::from_meshx(1_000);
+        node.resources.ram_gb = 1;
+        validator.nodes.get_mut(&keys[3]).unwrap().resources.ram_gb = 2;
+
+        let scan = validator.scan_network();
+        assert_eq!(scan.len(), 3);
+        assert_eq!(scan[&ValidationError::StaleAttestation].len(), 3);
+        assert_eq!(scan[&ValidationError::InsufficientStake], vec![keys[2]]);
+        assert_eq!(scan[&ValidationError::InsufficientRAM].len(), 2);
+    }
+
+    #[test]
+    fn test_geographic_redundancy() {
+        let (mut concentrated, _) = test_network(7);
+
+        let cities = [
+            (40.7, -74.0),  // New York
+            (51.5, -0.1),   // London
+            (35.7, 139.7),  // Tokyo
+            (-23.5, -46.6), // São Paulo
+            (-1.3, 36.8),   // Nairobi
+            (-33.9, 151.2), // Sydney
+            (-77.8, 166.7), // McMurdo
+        ];
+        let mut spread = PopValidator::new(1);
+        for (seed, (latitude, longitude)) in cities.into_iter().enumerate() {
+            let node = test_node(seed as u8 + 1, latitude, longitude, 100_000);
+            spread.nodes.insert(node.pubkey, node);
+        }
+        let shards: HashSet<Shard> = spread.nodes.values().map(|node| node.shard).collect();
+        assert_eq!(shards.len(), 7);
+
+        let concentrated_score = concentrated.geographic_redundancy();
+        let spread_score = spread.geographic_redundancy();
+        assert!(concentrated_score < spread_score);
+        assert!((spread_score - 0.5).abs() < 1e-6);
+
+        // Weighting only the within-shard spread favours the concentrated
+        // network, whose one shard at least has more than one location
+        concentrated.redundancy_weights.across_shards = 0.0;
+        spread.redundancy_weights.across_shards = 0.0;
+        assert!(spread.geographic_redundancy() < 1e-6);
+        assert!(concentrated.geographic_redundancy() > spread.geographic_redundancy());
+    }
+
+    #[test]
+    fn test_redacted_json_hashes_quotes() {
+        let (mut validator, keys) = test_network(2);
+        let quote = b"SECRET-QUOTE-BYTES".to_vec();
+        validator
+            .nodes
+            .get_mut(&keys[0])
+            .unwrap()
+            .tee_attestation
+            .quote = quote.clone();
+
+        let json = validator.to_redacted_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let quote_hash = hex_encode(&Sha3_256::digest(&quote));
+        assert!(json.contains(&quote_hash));
+        assert!(!json.contains("\"quote\""));
+        let raw_bytes = serde_json::to_string(&quote).unwrap();
+        assert!(!json.contains(&raw_bytes[1..raw_bytes.len() - 1]));
+        assert_eq!(value["nodes"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,213 @@
+// MeshX - The Immutable Global Device Mesh
+// Node reputation
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::proof_of_presence::{MeshXNode, PopValidator, Shard, TeeAttestation, TeeType};
+
+// Starting reputation derived from attestation quality, so nodes on
+// stronger TEE hardware begin with a head start over minimal ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationBootstrap {
+    pub tee_tiers: Vec<(TeeType, f32)>, // Starting reputation per TEE type
+    pub default_score: f32,             // For TEE types not listed
+}
+
+impl Default for ReputationBootstrap {
+    fn default() -> Self {
+        Self {
+            tee_tiers: vec![
+                (TeeType::IntelSgx, 0.7),
+                (TeeType::AmdSev, 0.7),
+                (TeeType::AppleSecureEnclave, 0.6),
+                (TeeType::ArmTrustZone, 0.5),
+            ],
+            default_score: 0.5, // Neutral
+        }
+    }
+}
+
+impl ReputationBootstrap {
+    pub fn initial_reputation(&self, attestation: &TeeAttestation) -> f32 {
+        self.tee_tiers
+            .iter()
+            .find(|(tee_type, _)| *tee_type == attestation.tee_type)
+            .map_or(self.default_score, |(_, score)| *score)
+            .clamp(0.0, 1.0)
+    }
+}
+
+// Per-epoch reputation decay. Shard-aware decay scales the rate by how
+// active the node's shard is, so nodes in sparse shards with few chances to
+// earn reputation back aren't worn down at the pace of busy ones.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationDecay {
+    pub rate: f32, // Fraction of reputation lost per epoch
+    pub shard_aware: bool,
+    pub min_activity_scale: f32, // Floor on the scale for the least active shards
+}
+
+impl Default for ReputationDecay {
+    fn default() -> Self {
+        Self {
+            rate: 0.01,
+            shard_aware: false,
+            min_activity_scale: 0.1,
+        }
+    }
+}
+
+impl PopValidator {
+    // Apply one epoch of reputation decay to every registered node
+    pub fn decay_reputation(&mut self) {
+        let decay = self.reputation_decay.clone();
+        let activity = if decay.shard_aware {
+            self.shard_activity()
+        } else {
+            HashMap::new()
+        };
+        for node in self.nodes.values_mut() {
+            let scale = if decay.shard_aware {
+                activity
+                    .get(&node.shard)
+                    .copied()
+                    .unwrap_or(0.0)
+                    .max(decay.min_activity_scale)
+            } else {
+                1.0
+            };
+            let factor = (decay.rate * scale).clamp(0.0, 1.0);
+            node.reputation_score *= 1.0 - factor;
+        }
+    }
+
+    // Fraction of recorded selection epochs in which the shard had at least
+    // one node selected
+    pub fn shard_activity(&self) -> HashMap<Shard, f32> {
+        let mut epochs: HashSet<u64> = HashSet::new();
+        let mut active: HashMap<Shard, HashSet<u64>> = HashMap::new();
+        for (pubkey, history) in &self.selection_history {
+            let shard = self.nodes.get(pubkey).map(|node| node.shard);
+            for (epoch, selected) in history {
+                epochs.insert(*epoch);
+                if let (Some(shard), true) = (shard, *selected) {
+                    active.entry(shard).or_default().insert(*epoch);
+                }
+            }
+        }
+        if epochs.is_empty() {
+            return HashMap::new();
+        }
+        active
+            .into_iter()
+            .map(|(shard, active_epochs)| (shard, active_epochs.len() as f32 / epochs.len() as f32))
+            .collect()
+    }
+
+    // Replace a newly registering node's self-declared reputation with the
+    // bootstrapped one. Re-registrations keep the reputation already earned.
+    pub(crate) fn bootstrap_reputation(&self, node: &mut MeshXNode) {
+        let Some(bootstrap) = &self.reputation_bootstrap else {
+            return;
+        };
+        node.reputation_score = match self
+            .nodes
+            .get(&node.pubkey)
+            .or_else(|| self.pending_nodes.get(&node.pubkey))
+        {
+            Some(existing) => existing.reputation_score,
+            None => bootstrap.initial_reputation(&node.tee_attestation),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::{test_network, test_node};
+    use ed25519_dalek::PublicKey;
+
+    #[test]
+    fn test_bootstrap_from_attestation_tier() {
+        let (mut validator, keys) = test_network(4);
+        validator.reputation_bootstrap = Some(ReputationBootstrap {
+            tee_tiers: vec![(TeeType::IntelSgx, 0.8), (TeeType::ArmTrustZone, 0.3)],
+            default_score: 0.5,
+        });
+
+        let mut strong = test_node(10, 50.0, 10.0, 100_000);
+        strong.reputation_score = 1.0;
+        let mut minimal = test_node(11, 50.0, 10.0, 100_000);
+        minimal.tee_attestation.tee_type = TeeType::ArmTrustZone;
+        minimal.reputation_score = 1.0;
+        for node in [&strong, &minimal] {
+            for anchor in &keys[..3] {
+                validator.latency_matrix.insert((*anchor, node.pubkey), 10);
+            }
+        }
+
+        validator.register_node(strong.clone()).unwrap();
+        validator.register_node(minimal.clone()).unwrap();
+        let strong_score = validator.nodes[&strong.pubkey].reputation_score;
+        let minimal_score = validator.nodes[&minimal.pubkey].reputation_score;
+        assert_eq!(strong_score, 0.8);
+        assert_eq!(minimal_score, 0.3);
+        assert!(strong_score > minimal_score);
+
+        // Earned reputation survives re-registration
+        validator
+            .nodes
+            .get_mut(&minimal.pubkey)
+            .unwrap()
+            .reputation_score = 0.9;
+        validator.register_node(minimal.clone()).unwrap();
+        assert_eq!(validator.nodes[&minimal.pubkey].reputation_score, 0.9);
+    }
+
+    #[test]
+    fn test_shard_aware_decay_spares_sparse_shards() {
+        let mut validator = PopValidator::new(1);
+        let mut add = |seed: u8, latitude: f64, longitude: f64| -> PublicKey {
+            let node = test_node(seed, latitude, longitude, 100_000);
+            let pubkey = node.pubkey;
+            validator.nodes.insert(pubkey, node);
+            pubkey
+        };
+        let busy_asia = add(1, 35.7, 139.7);
+        let idle_asia = add(2, 34.7, 135.5);
+        let busy_antarctica = add(3, -77.8, 166.7);
+        let idle_antarctica = add(4, -75.0, 0.0);
+        assert_eq!(validator.nodes[&idle_antarctica].shard, Shard::Antarctica);
+
+        // Asia has a validator every epoch, Antarctica in one of ten
+        for epoch in 0..10 {
+            let mut selected = vec![busy_asia];
+            if epoch == 0 {
+                selected.push(busy_antarctica);
+            }
+            validator.record_selection(epoch, &selected);
+        }
+
+        validator.reputation_decay = ReputationDecay {
+            rate: 0.05,
+            shard_aware: true,
+            min_activity_scale: 0.1,
+        };
+        for _ in 0..10 {
+            validator.decay_reputation();
+        }
+        let asia = validator.nodes[&idle_asia].reputation_score;
+        let antarctica = validator.nodes[&idle_antarctica].reputation_score;
+        assert!(antarctica > asia, "{} <= {}", antarctica, asia);
+        assert!((asia - 0.95f32.powi(10)).abs() < 1e-5);
+
+        // Uniform decay treats both alike
+        validator.reputation_decay.shard_aware = false;
+        validator.decay_reputation();
+        let after = |key| validator.nodes[&key].reputation_score;
+        assert!((after(idle_asia) / asia - 0.95).abs() < 1e-5);
+        assert!((after(idle_antarctica) / antarctica - 0.95).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,227 @@
+// MeshX - The Immutable Global Device Mesh
+// Resource threshold proofs
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::{Keypair, PublicKey, Signature, Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::proof_of_presence::{
+    MeshXNode, PopValidator, TeeAttestation, ValidationError, ValidationPolicy,
+};
+use crate::stake::StakeAmount;
+
+// A node's claim that each of its resources is at least the policy's
+// minimum, without stating the actual figures. The claim is bound to the
+// node's TEE attestation, so it stands or falls with the enclave that
+// measured the resources.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceClaim {
+    pub pubkey: PublicKey,
+    pub attestation_hash: [u8; 32],
+    pub stake_amount: StakeAmount, // Selects the stake-scaled CPU and RAM tier
+    pub min_cpu_cores: u32,
+    pub min_ram_gb: u32,
+    pub min_storage_gb: u64,
+    pub min_bandwidth_mbps: u32,
+    pub min_gpu_memory_gb: Option<u32>,
+}
+
+// A resource claim signed by the node. A zero-knowledge proof can replace
+// the signature later without changing what is claimed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceProof {
+    pub claim: ResourceClaim,
+    pub signature: Signature,
+}
+
+impl ResourceProof {
+    pub fn sign(claim: ResourceClaim, keypair: &Keypair) -> Self {
+        let signature = keypair.sign(&claim.signing_payload());
+        Self { claim, signature }
+    }
+
+    // Check the node's signature and that every claimed threshold is at
+    // least what `policy` requires, without access to a validator
+    pub fn verify(&self, policy: &ValidationPolicy) -> Result<(), ValidationError> {
+        let claim = &self.claim;
+        claim
+            .pubkey
+            .verify(&claim.signing_payload(), &self.signature)
+            .map_err(|_| ValidationError::InvalidSignature)?;
+        if claim.min_cpu_cores < policy.min_cpu_cores_for(claim.stake_amount) {
+            return Err(ValidationError::InsufficientCPU);
+        }
+        if claim.min_ram_gb < policy.min_ram_gb_for(claim.stake_amount) {
+            return Err(ValidationError::InsufficientRAM);
+        }
+        if claim.min_storage_gb < policy.min_storage_gb {
+            return Err(ValidationError::InsufficientStorage);
+        }
+        if claim.min_bandwidth_mbps < policy.min_bandwidth_mbps {
+            return Err(ValidationError::InsufficientBandwidth);
+        }
+        if let Some(min_gpu_memory_gb) = policy.min_gpu_memory_gb {
+            if claim.min_gpu_memory_gb.unwrap_or(0) < min_gpu_memory_gb {
+                return Err(ValidationError::InsufficientGPU);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ResourceClaim {
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = b"MESHX_RESOURCE_PROOF".to_vec();
+        payload.extend_from_slice(self.pubkey.as_bytes());
+        payload.extend_from_slice(&self.attestation_hash);
+        payload.extend_from_slice(&self.stake_amount.base_units().to_le_bytes());
+        payload.extend_from_slice(&self.min_cpu_cores.to_le_bytes());
+        payload.extend_from_slice(&self.min_ram_gb.to_le_bytes());
+        payload.extend_from_slice(&self.min_storage_gb.to_le_bytes());
+        payload.extend_from_slice(&self.min_bandwidth_mbps.to_le_bytes());
+        match self.min_gpu_memory_gb {
+            Some(gpu) => {
+                payload.push(1);
+                payload.extend_from_slice(&gpu.to_le_bytes());
+            }
+            None => payload.push(0),
+        }
+        payload
+    }
+}
+
+fn attestation_hash(attestation: &TeeAttestation) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"MESHX_RESOURCE_ATTESTATION");
+    hasher.update(attestation.enclave_hash);
+    hasher.update(attestation.signer_pubkey.as_bytes());
+    hasher.update(attestation.timestamp.to_le_bytes());
+    hasher.update(&attestation.quote);
+    hasher.finalize().into()
+}
+
+impl PopValidator {
+    // Prove the node meets `policy`'s resource minimums, claiming exactly
+    // those minimums. Fails with the first unmet resource, as validation
+    // would.
+    pub fn resource_threshold_proof(
+        &self,
+        node: &MeshXNode,
+        policy: &ValidationPolicy,
+        keypair: &Keypair,
+    ) -> Result<ResourceProof, ValidationError> {
+        if keypair.public != node.pubkey {
+            return Err(ValidationError::InvalidSignature);
+        }
+        policy.check_resources(&node.resources, node.stake_amount)?;
+        let claim = ResourceClaim {
+            pubkey: node.pubkey,
+            attestation_hash: attestation_hash(&node.tee_attestation),
+            stake_amount: node.stake_amount,
+            min_cpu_cores: policy.min_cpu_cores_for(node.stake_amount),
+            min_ram_gb: policy.min_ram_gb_for(node.stake_amount),
+            min_storage_gb: policy.min_storage_gb,
+            min_bandwidth_mbps: policy.min_bandwidth_mbps,
+            min_gpu_memory_gb: policy.min_gpu_memory_gb,
+        };
+        Ok(ResourceProof::sign(claim, keypair))
+    }
+
+    // Verify a proof against this validator's policy, also checking it was
+    // made under the attestation and stake the node is registered with
+    pub fn verify_resource_proof(&self, proof: &ResourceProof) -> Result<(), ValidationError> {
+        proof.verify(&self.policy)?;
+        let claim = &proof.claim;
+        let node = self
+            .nodes
+            .get(&claim.pubkey)
+            .ok_or(ValidationError::UnknownNode)?;
+        if attestation_hash(&node.tee_attestation) != claim.attestation_hash
+            || node.stake_amount != claim.stake_amount
+        {
+            return Err(ValidationError::StaleResourceProof);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dcap::tests::test_quote;
+    use crate::presence::tests::test_keypair;
+    use crate::proof_of_presence::tests::test_node;
+
+    fn keyed_node(seed: u8) -> (MeshXNode, Keypair) {
+        let keypair = test_keypair(seed);
+        let mut node = test_node(seed, 50.0, 10.0, 100_000);
+        node.pubkey = keypair.public;
+        node.tee_attestation.signer_pubkey = keypair.public;
+        (node, keypair)
+    }
+
+    #[test]
+    fn test_resource_proof_verifies_for_qualifying_node() {
+        let (node, keypair) = keyed_node(1);
+        let mut validator = PopValidator::new(1);
+        let policy = validator.policy.clone();
+        let proof = validator
+            .resource_threshold_proof(&node, &policy, &keypair)
+            .unwrap();
+        assert_eq!(proof.verify(&policy), Ok(()));
+        // The actual figures (8 cores, 16 GB) aren't disclosed
+        assert_eq!(proof.claim.min_cpu_cores, policy.min_cpu_cores);
+
+        validator.nodes.insert(node.pubkey, node.clone());
+        assert_eq!(validator.verify_resource_proof(&proof), Ok(()));
+
+        // A fresh attestation needs a fresh proof
+        let mut reattested = node.clone();
+        reattested.tee_attestation.quote = test_quote(1);
+        validator.nodes.insert(node.pubkey, reattested);
+        assert_eq!(
+            validator.verify_resource_proof(&proof),
+            Err(ValidationError::StaleResourceProof)
+        );
+        // As does a change of stake
+        let mut restaked = node.clone();
+        restaked.stake_amount = StakeAmount::from_meshx(200_000);
+        validator.nodes.insert(node.pubkey, restaked);
+        assert_eq!(
+            validator.verify_resource_proof(&proof),
+            Err(ValidationError::StaleResourceProof)
+        );
+
+        let mut tampered = proof.clone();
+        tampered.claim.min_cpu_cores = 64;
+        assert_eq!(
+            tampered.verify(&policy),
+            Err(ValidationError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_signed_claim_below_policy_fails() {
+        let (node, keypair) = keyed_node(2);
+        let validator = PopValidator::new(1);
+        let mut lax = validator.policy.clone();
+        lax.min_cpu_cores = 1;
+        let proof = validator
+            .resource_threshold_proof(&node, &lax, &keypair)
+            .unwrap();
+        assert_eq!(proof.verify(&lax), Ok(()));
+        assert_eq!(
+            proof.verify(&validator.policy),
+            Err(ValidationError::InsufficientCPU)
+        );
+
+        // A node short of the policy can't produce a proof at all
+        let mut strict = validator.policy.clone();
+        strict.min_ram_gb = 32;
+        assert_eq!(
+            validator.resource_threshold_proof(&node, &strict, &keypair),
+            Err(ValidationError::InsufficientRAM)
+        );
+    }
+}
@@ -0,0 +1,339 @@
+// MeshX - The Immutable Global Device Mesh
+// Validator reward emission
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::proof_of_presence::{MeshXNode, PopValidator, Shard, ValidationError};
+use crate::stake::StakeAmount;
+
+// Per-shard reward pools. Each shard's selected validators share only their
+// own shard's emission, so under-provisioned shards can be incentivized by
+// boosting their pool independently of the others. Every pool halves each
+// `halving_interval_epochs` epochs when set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RewardPolicy {
+    pub shard_emission_per_epoch: HashMap<Shard, StakeAmount>, // Before any halving
+    pub halving_interval_epochs: Option<u64>,                  // None keeps emission flat
+    pub max_reward_weight: Option<StakeAmount>, // Effective stake beyond this earns nothing; None uncapped
+}
+
+impl Default for RewardPolicy {
+    fn default() -> Self {
+        // Year-one emission of 1B MESHX over hourly epochs, split evenly
+        let per_shard = StakeAmount::from_meshx(1_000_000_000)
+            .checked_div(365 * 24 * Shard::all().len() as u64)
+            .expect("nonzero divisor");
+        Self {
+            shard_emission_per_epoch: Shard::all()
+                .into_iter()
+                .map(|shard| (shard, per_shard))
+                .collect(),
+            halving_interval_epochs: None,
+            max_reward_weight: None,
+        }
+    }
+}
+
+impl RewardPolicy {
+    pub fn emission_for(&self, shard: &Shard) -> StakeAmount {
+        self.shard_emission_per_epoch
+            .get(shard)
+            .copied()
+            .unwrap_or(StakeAmount::ZERO)
+    }
+
+    // A shard's pool in the given epoch, after halvings
+    pub fn emission_at(&self, shard: &Shard, epoch: u64) -> StakeAmount {
+        let base = self.emission_for(shard).base_units();
+        StakeAmount::from_base_units(base.checked_shr(self.halvings_by(epoch)).unwrap_or(0))
+    }
+
+    // Every shard's pool in the given epoch
+    pub fn total_emission_at(&self, epoch: u64) -> StakeAmount {
+        Shard::all().iter().fold(StakeAmount::ZERO, |total, shard| {
+            total.saturating_add(self.emission_at(shard, epoch))
+        })
+    }
+
+    fn halvings_by(&self, epoch: u64) -> u32 {
+        match self.halving_interval_epochs {
+            Some(interval) if interval > 0 => (epoch / interval).min(u32::MAX as u64) as u32,
+            _ => 0,
+        }
+    }
+}
+
+impl PopValidator {
+    // Pay out each shard's pool to that shard's validators selected for the
+    // epoch, pro rata by reward weight. Payouts are credited to
+    // `reward_balances` and returned, once per epoch; rounding dust is never
+    // emitted.
+    pub fn distribute_rewards(
+        &mut self,
+        epoch: u64,
+    ) -> Result<HashMap<PublicKey, StakeAmount>, ValidationError> {
+        if self.rewarded_epochs.contains(&epoch) {
+            return Err(ValidationError::RewardsAlreadyDistributed);
+        }
+        let selected = self.select_validators(epoch)?;
+
+        let mut by_shard: HashMap<Shard, Vec<(PublicKey, u64)>> = HashMap::new();
+        for pubkey in selected {
+            let node = &self.nodes[&pubkey];
+            by_shard
+                .entry(node.shard)
+                .or_default()
+                .push((pubkey, self.reward_weight_of(node).base_units()));
+        }
+
+        let mut payouts = HashMap::new();
+        for (shard, validators) in by_shard {
+            let pool = self.reward_policy.emission_at(&shard, epoch);
+            let total_weight: u128 = validators.iter().map(|(_, weight)| *weight as u128).sum();
+            if total_weight == 0 {
+                continue;
+            }
+            for (pubkey, weight) in validators {
+                let payout = pool.base_units() as u128 * weight as u128 / total_weight;
+                payouts.insert(pubkey, StakeAmount::from_base_units(payout as u64));
+            }
+        }
+
+        for (pubkey, payout) in &payouts {
+            let balance = self.reward_balances.entry(*pubkey).or_default();
+            *balance = balance.saturating_add(*payout);
+        }
+        self.rewarded_epochs.insert(epoch);
+        Ok(payouts)
+    }
+
+    // A node's share of its shard's pool is in proportion to this: its
+    // effective stake, capped at `max_reward_weight`
+    fn reward_weight_of(&self, node: &MeshXNode) -> StakeAmount {
+        let weight = self.effective_stake_of(node);
+        match self.reward_policy.max_reward_weight {
+            Some(cap) => weight.min(cap),
+            None => weight,
+        }
+    }
+
+    // Estimated change in a node's expected reward per epoch, in base units,
+    // if it staked `additional` more. The estimate is selection probability
+    // times the pool share w / (w + p * W) next epoch, with w the node's
+    // reward weight and W that of the shard's other eligible nodes, each
+    // selected with the same probability p. Returns shrink as w grows, stop
+    // at the reward weight cap, and turn negative if the extra stake would
+    // make the node fail validation.
+    pub fn marginal_reward_of_stake(&self, pubkey: &PublicKey, additional: StakeAmount) -> i64 {
+        let Some(node) = self.nodes.get(pubkey) else {
+            return 0;
+        };
+        let mut staked = node.clone();
+        staked.stake_amount = staked.stake_amount.saturating_add(additional);
+        let gained = self.expected_reward(&staked) - self.expected_reward(node);
+        gained.round() as i64
+    }
+
+    fn expected_reward(&self, node: &MeshXNode) -> f64 {
+        let epoch = self.current_epoch + 1;
+        if self.validate_node(node).is_err() {
+            return 0.0;
+        }
+        let others: Vec<&MeshXNode> = self
+            .selection_candidates(epoch)
+            .into_iter()
+            .filter(|other| other.pubkey != node.pubkey && self.validate_node(other).is_ok())
+            .collect();
+        let probability = (self.get_validator_count() as f64 / (others.len() + 1) as f64).min(1.0);
+        let weight = self.reward_weight_of(node).as_meshx_f64();
+        let others_weight: f64 = others
+            .iter()
+            .filter(|other| other.shard == node.shard)
+            .map(|other| self.reward_weight_of(other).as_meshx_f64())
+            .sum();
+        let competing = weight + probability * others_weight;
+        if competing == 0.0 {
+            return 0.0;
+        }
+        let pool = self
+            .reward_policy
+            .emission_at(&node.shard, epoch)
+            .base_units() as f64;
+        probability * pool * weight / competing
+    }
+
+    // Total emission over epochs `from_epoch..to_epoch`. Emission is
+    // constant between halvings, so this sums whole halving periods at once.
+    pub fn projected_emission(&self, from_epoch: u64, to_epoch: u64) -> StakeAmount {
+        let policy = &self.reward_policy;
+        let mut total = StakeAmount::ZERO;
+        let mut epoch = from_epoch;
+        while epoch < to_epoch {
+            let period_end = match policy.halving_interval_epochs {
+                Some(interval) if interval > 0 => (epoch / interval + 1)
+                    .saturating_mul(interval)
+                    .min(to_epoch),
+                _ => to_epoch,
+            };
+            let per_epoch = policy.total_emission_at(epoch).base_units() as u128;
+            let period = per_epoch * (period_end - epoch) as u128;
+            total = total.saturating_add(StakeAmount::from_base_units(
+                period.min(u64::MAX as u128) as u64,
+            ));
+            if per_epoch == 0 {
+                break; // Every later period is zero as well
+            }
+            epoch = period_end;
+        }
+        total
+    }
+
+    // Annualized issuance at the epoch's emission rate, as a fraction of the
+    // circulating supply; 0 with no circulating supply
+    pub fn inflation_rate(&self, epoch: u64, circulating_supply: StakeAmount) -> f32 {
+        if circulating_supply == StakeAmount::ZERO {
+            return 0.0;
+        }
+        let epochs_per_year = 365.0 * 24.0 * 3600.0 / self.epoch_length_secs.max(1) as f64;
+        let per_epoch = self.reward_policy.total_emission_at(epoch).as_meshx_f64();
+        (per_epoch * epochs_per_year / circulating_supply.as_meshx_f64()) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::{test_network, test_node};
+
+    // Four Europe nodes plus three fully meshed Asia nodes
+    fn two_shard_network() -> PopValidator {
+        let (mut validator, _) = test_network(4);
+        let asia: Vec<_> = (20..23)
+            .map(|seed| test_node(seed, 35.7, 139.7, 100_000))
+            .collect();
+        for node in &asia {
+            for peer in validator.nodes.keys().chain(asia.iter().map(|n| &n.pubkey)) {
+                if *peer != node.pubkey {
+                    validator.latency_matrix.insert((*peer, node.pubkey), 40);
+                }
+            }
+        }
+        for node in asia {
+            validator.nodes.insert(node.pubkey, node);
+        }
+        validator
+    }
+
+    #[test]
+    fn test_projected_emission_across_halving() {
+        let mut validator = PopValidator::new(1);
+        validator.reward_policy.halving_interval_epochs = Some(100);
+        let per_epoch_sum = |policy: &RewardPolicy, from: u64, to: u64| {
+            (from..to).fold(StakeAmount::ZERO, |total, epoch| {
+                total.saturating_add(policy.total_emission_at(epoch))
+            })
+        };
+        assert_eq!(
+            validator.projected_emission(40, 260),
+            per_epoch_sum(&validator.reward_policy, 40, 260)
+        );
+        assert_eq!(validator.projected_emission(5, 5), StakeAmount::ZERO);
+
+        // The window after the halving issues half as much
+        let before = validator.projected_emission(50, 100);
+        let after = validator.projected_emission(100, 150);
+        assert!(after < before);
+        let supply = StakeAmount::from_meshx(10_000_000_000);
+        let rate = validator.inflation_rate(0, supply);
+        assert!((rate - 0.1).abs() < 1e-4, "rate {}", rate);
+        assert!(validator.inflation_rate(100, supply) < rate);
+
+        // Pools run dry after 64 halvings
+        validator.reward_policy.halving_interval_epochs = Some(1);
+        assert_eq!(
+            validator.projected_emission(0, u64::MAX),
+            per_epoch_sum(&validator.reward_policy, 0, 64)
+        );
+    }
+
+    #[test]
+    fn test_marginal_reward_diminishes_to_cap() {
+        let (mut validator, keys) = test_network(4);
+        validator.validator_count = 4;
+        validator
+            .reward_policy
+            .shard_emission_per_epoch
+            .insert(Shard::Europe, StakeAmount::from_meshx(1_000));
+        validator.reward_policy.max_reward_weight = Some(StakeAmount::from_meshx(200_000));
+        let step = StakeAmount::from_meshx(50_000);
+        let set_stake = |validator: &mut PopValidator, meshx| {
+            validator.nodes.get_mut(&keys[0]).unwrap().stake_amount =
+                StakeAmount::from_meshx(meshx);
+        };
+
+        // 100K of 400K earns 250 MESHX; 150K of 450K earns 333.3
+        let first = validator.marginal_reward_of_stake(&keys[0], step);
+        assert_eq!(first, 83_333_333_333);
+        set_stake(&mut validator, 150_000);
+        let second = validator.marginal_reward_of_stake(&keys[0], step);
+        assert!(second > 0 && second < first);
+
+        // Stake past the cap earns nothing more
+        set_stake(&mut validator, 200_000);
+        assert_eq!(validator.marginal_reward_of_stake(&keys[0], step), 0);
+        set_stake(&mut validator, 175_000);
+        let capped = validator.marginal_reward_of_stake(&keys[0], step);
+        assert!(capped < second);
+
+        // Stake beyond the plausible share of supply fails validation and
+        // forfeits the reward
+        let supply_share = StakeAmount::from_meshx(600_000_000);
+        assert!(validator.marginal_reward_of_stake(&keys[0], supply_share) < 0);
+        assert_eq!(
+            validator.marginal_reward_of_stake(&test_node(9, 50.0, 10.0, 1).pubkey, step),
+            0
+        );
+    }
+
+    #[test]
+    fn test_shard_pools_are_independent() {
+        let mut validator = two_shard_network();
+        let baseline = validator.distribute_rewards(0).unwrap();
+
+        validator
+            .reward_policy
+            .shard_emission_per_epoch
+            .insert(Shard::Asia, StakeAmount::from_meshx(90_000));
+        let boosted = validator.distribute_rewards(1).unwrap();
+        assert_eq!(boosted.len(), 7);
+
+        for (pubkey, payout) in &boosted {
+            match validator.nodes[pubkey].shard {
+                Shard::Asia => {
+                    assert_eq!(*payout, StakeAmount::from_meshx(30_000));
+                    assert!(*payout > baseline[pubkey]);
+                }
+                _ => assert_eq!(*payout, baseline[pubkey]),
+            }
+        }
+        let total = |amounts: &mut dyn Iterator<Item = &StakeAmount>| {
+            amounts.fold(StakeAmount::ZERO, |total, amount| {
+                total.saturating_add(*amount)
+            })
+        };
+        let balance = total(&mut validator.reward_balances.values());
+        let paid = total(&mut baseline.values().chain(boosted.values()));
+        assert_eq!(balance, paid);
+
+        // Each epoch pays out once
+        assert_eq!(
+            validator.distribute_rewards(1),
+            Err(ValidationError::RewardsAlreadyDistributed)
+        );
+        let balance_after = total(&mut validator.reward_balances.values());
+        assert_eq!(balance_after, balance);
+    }
+}
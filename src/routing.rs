@@ -0,0 +1,280 @@
+// MeshX - The Immutable Global Device Mesh
+// Quality-aware service routing
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::proof_of_presence::{
+    haversine_distance, GeoLocation, MeshXNode, PopValidator, Shard, FIBER_KM_PER_MS,
+};
+
+// Blend of proximity and bandwidth used to rank nodes for serving a client.
+// Each factor is x / (x + scale) style in 0..=1, so only the weight ratio
+// matters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceRoutingWeights {
+    pub proximity: f32,
+    pub bandwidth: f32,
+    pub distance_scale_km: f64, // Distance at which proximity scores half
+    pub bandwidth_scale_mbps: f64, // Bandwidth that scores half
+}
+
+impl Default for ServiceRoutingWeights {
+    fn default() -> Self {
+        Self {
+            proximity: 0.7,
+            bandwidth: 0.3,
+            distance_scale_km: 1_000.0,
+            bandwidth_scale_mbps: 1_000.0,
+        }
+    }
+}
+
+// One-way latency between two nodes, either as measured or inferred from
+// their claimed positions where no measurement exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LatencyEstimate {
+    Measured(u32),
+    Estimated(u32), // Great-circle distance at fiber speed, a lower bound
+}
+
+impl LatencyEstimate {
+    pub fn ms(self) -> u32 {
+        match self {
+            Self::Measured(ms) | Self::Estimated(ms) => ms,
+        }
+    }
+}
+
+// Spread of measured latencies between two shards, in ms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub min: u32,
+    pub median: u32, // Lower middle value for an even number of samples
+    pub max: u32,
+    pub samples: usize,
+}
+
+impl LatencySummary {
+    fn from_samples(mut samples: Vec<u32>) -> Option<Self> {
+        samples.sort_unstable();
+        Some(Self {
+            min: *samples.first()?,
+            median: samples[(samples.len() - 1) / 2],
+            max: *samples.last()?,
+            samples: samples.len(),
+        })
+    }
+}
+
+impl PopValidator {
+    // Latency from `a` to `b`: the measurement in either direction if there
+    // is one, else an estimate from the nodes' registered or pending
+    // locations. None when neither is available.
+    pub fn estimate_latency(&self, a: &PublicKey, b: &PublicKey) -> Option<LatencyEstimate> {
+        if let Some(latency) = self
+            .latency_matrix
+            .get(&(*a, *b))
+            .or_else(|| self.latency_matrix.get(&(*b, *a)))
+        {
+            return Some(LatencyEstimate::Measured(*latency));
+        }
+        let location = |pubkey: &PublicKey| {
+            self.nodes
+                .get(pubkey)
+                .or_else(|| self.pending_nodes.get(pubkey))
+                .map(|node| &node.geo_location)
+        };
+        let distance_km = haversine_distance(location(a)?, location(b)?) / 1000.0;
+        Some(LatencyEstimate::Estimated(
+            (distance_km / FIBER_KM_PER_MS).ceil() as u32,
+        ))
+    }
+
+    // Latency for every ordered pair of registered nodes, filling gaps in the
+    // latency matrix with estimates. Callers routing repeatedly can keep the
+    // result until the registry or matrix changes.
+    pub fn latency_estimates(&self) -> HashMap<(PublicKey, PublicKey), LatencyEstimate> {
+        let mut estimates = HashMap::new();
+        for a in self.nodes.keys() {
+            for b in self.nodes.keys().filter(|b| *b != a) {
+                if let Some(estimate) = self.estimate_latency(a, b) {
+                    estimates.insert((*a, *b), estimate);
+                }
+            }
+        }
+        estimates
+    }
+
+    // Measured latencies between registered nodes of different shards,
+    // keyed by (measuring node's shard, target's shard). Only real
+    // measurements count; position-based estimates would just restate the
+    // distance between the shards.
+    pub fn inter_shard_latency(&self) -> HashMap<(Shard, Shard), LatencySummary> {
+        let mut samples: HashMap<(Shard, Shard), Vec<u32>> = HashMap::new();
+        for ((from, to), latency) in &self.latency_matrix {
+            let (Some(from), Some(to)) = (self.nodes.get(from), self.nodes.get(to)) else {
+                continue;
+            };
+            if from.shard != to.shard {
+                samples
+                    .entry((from.shard, to.shard))
+                    .or_default()
+                    .push(*latency);
+            }
+        }
+        samples
+            .into_iter()
+            .filter_map(|(pair, samples)| Some((pair, LatencySummary::from_samples(samples)?)))
+            .collect()
+    }
+
+    // The k registered nodes best placed to serve a client at `location`,
+    // with their scores, best first
+    pub fn best_service_nodes(&self, location: &GeoLocation, k: usize) -> Vec<(&MeshXNode, f32)> {
+        let weights = &self.service_routing_weights;
+        let total_weight = weights.proximity.max(0.0) + weights.bandwidth.max(0.0);
+        let score = |node: &MeshXNode| {
+            if total_weight == 0.0 {
+                return 0.0;
+            }
+            let distance_km = haversine_distance(location, &node.geo_location) / 1000.0;
+            let proximity = weights.distance_scale_km / (weights.distance_scale_km + distance_km);
+            let bandwidth_mbps = node.resources.bandwidth_mbps as f64;
+            let bandwidth = bandwidth_mbps / (bandwidth_mbps + weights.bandwidth_scale_mbps);
+            (weights.proximity.max(0.0) * proximity as f32
+                + weights.bandwidth.max(0.0) * bandwidth as f32)
+                / total_weight
+        };
+
+        let mut ranked: Vec<(&MeshXNode, f32)> = self
+            .nodes
+            .values()
+            .map(|node| (node, score(node)))
+            .collect();
+        ranked.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| a.pubkey.as_bytes().cmp(b.pubkey.as_bytes()))
+        });
+        ranked.truncate(k);
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_node;
+
+    #[test]
+    fn test_latency_estimates_fill_missing_edges() {
+        let mut validator = PopValidator::new(1);
+        let frankfurt = test_node(1, 50.1, 8.7, 100_000);
+        let paris = test_node(2, 48.9, 2.4, 100_000);
+        let tokyo = test_node(3, 35.7, 139.7, 100_000);
+        let keys = [frankfurt.pubkey, paris.pubkey, tokyo.pubkey];
+        for node in [frankfurt, paris, tokyo] {
+            validator.nodes.insert(node.pubkey, node);
+        }
+        validator.latency_matrix.insert((keys[0], keys[1]), 9);
+
+        assert_eq!(
+            validator.estimate_latency(&keys[0], &keys[1]),
+            Some(LatencyEstimate::Measured(9))
+        );
+        assert_eq!(
+            validator.estimate_latency(&keys[1], &keys[0]),
+            Some(LatencyEstimate::Measured(9))
+        );
+        // About 9,350 km at 200 km/ms
+        let estimate = validator.estimate_latency(&keys[0], &keys[2]).unwrap();
+        assert!(
+            matches!(estimate, LatencyEstimate::Estimated(46..=48)),
+            "{:?}",
+            estimate
+        );
+        assert_eq!(
+            validator.estimate_latency(&keys[0], &test_node(4, 0.0, 0.0, 0).pubkey),
+            None
+        );
+
+        let all = validator.latency_estimates();
+        assert_eq!(all.len(), 6);
+        assert_eq!(all[&(keys[2], keys[0])], estimate);
+    }
+
+    #[test]
+    fn test_inter_shard_latency_summary() {
+        let mut validator = PopValidator::new(1);
+        let mut europe = Vec::new();
+        let mut asia = Vec::new();
+        for seed in 1..=3 {
+            let mut node = test_node(seed, 50.0, 10.0, 100_000);
+            node.shard = Shard::Europe;
+            europe.push(node.pubkey);
+            validator.nodes.insert(node.pubkey, node);
+        }
+        for seed in 4..=5 {
+            let mut node = test_node(seed, 35.7, 139.7, 100_000);
+            node.shard = Shard::Asia;
+            asia.push(node.pubkey);
+            validator.nodes.insert(node.pubkey, node);
+        }
+        let measured = [120, 140, 135, 150, 128];
+        let pairs = [
+            (europe[0], asia[0]),
+            (europe[0], asia[1]),
+            (europe[1], asia[0]),
+            (europe[1], asia[1]),
+            (europe[2], asia[0]),
+        ];
+        for (pair, latency) in pairs.into_iter().zip(measured) {
+            validator.latency_matrix.insert(pair, latency);
+        }
+        // Same-shard and reverse-direction edges are kept apart
+        validator.latency_matrix.insert((europe[0], europe[1]), 5);
+        validator.latency_matrix.insert((asia[0], europe[0]), 300);
+
+        let summary = validator.inter_shard_latency();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(
+            summary[&(Shard::Europe, Shard::Asia)],
+            LatencySummary {
+                min: 120,
+                median: 135,
+                max: 150,
+                samples: 5,
+            }
+        );
+        assert_eq!(summary[&(Shard::Asia, Shard::Europe)].median, 300);
+    }
+
+    #[test]
+    fn test_bandwidth_weight_promotes_distant_fast_node() {
+        let mut validator = PopValidator::new(1);
+        let mut near = test_node(1, 50.1, 10.0, 100_000);
+        near.resources.bandwidth_mbps = 20;
+        let mut far = test_node(2, 48.9, 2.4, 100_000);
+        far.resources.bandwidth_mbps = 10_000;
+        let (near_key, far_key) = (near.pubkey, far.pubkey);
+        validator.nodes.insert(near.pubkey, near);
+        validator.nodes.insert(far.pubkey, far);
+        let client = GeoLocation {
+            latitude: 50.0,
+            longitude: 10.0,
+            accuracy_meters: 1_000.0,
+        };
+
+        validator.service_routing_weights.bandwidth = 0.0;
+        let ranked = validator.best_service_nodes(&client, 2);
+        assert_eq!(ranked[0].0.pubkey, near_key);
+
+        validator.service_routing_weights.bandwidth = 1.0;
+        let ranked = validator.best_service_nodes(&client, 1);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].0.pubkey, far_key);
+    }
+}
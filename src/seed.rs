@@ -0,0 +1,153 @@
+// MeshX - The Immutable Global Device Mesh
+// Rolling epoch seeds
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use sha3::{Digest, Sha3_256};
+
+use crate::proof_of_presence::{PopValidator, ValidationError};
+
+// Seed for `epoch`, chained from the previous epoch's seed and the aggregate
+// of that epoch's selected VRF outputs. No single node can grind it without
+// controlling every output that went into the aggregate.
+pub fn derive_epoch_seed(previous_seed: &[u8; 32], epoch: u64, aggregate: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"MESHX_EPOCH_SEED");
+    hasher.update(previous_seed);
+    hasher.update(epoch.to_le_bytes());
+    hasher.update(aggregate);
+    hasher.finalize().into()
+}
+
+impl PopValidator {
+    // Order-independent digest of the selected validators' VRF outputs
+    pub fn aggregate_vrf_outputs(&self, epoch: u64, selected: &[PublicKey]) -> [u8; 32] {
+        let mut outputs: Vec<[u8; 32]> = selected
+            .iter()
+            .map(|pubkey| self.vrf_prove(epoch, pubkey).output)
+            .collect();
+        outputs.sort();
+
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"MESHX_VRF_AGGREGATE");
+        hasher.update((outputs.len() as u64).to_le_bytes());
+        for output in &outputs {
+            hasher.update(output);
+        }
+        hasher.finalize().into()
+    }
+
+    // Entropy an epoch contributes to the next seed: the aggregate of its
+    // recorded selection, or of no outputs if no selection was recorded
+    pub fn aggregate_epoch_entropy(&self, epoch: u64) -> [u8; 32] {
+        self.vrf_aggregates
+            .get(&epoch)
+            .copied()
+            .unwrap_or_else(|| self.aggregate_vrf_outputs(epoch, &[]))
+    }
+
+    // Roll `epoch_seed`, if enabled, forward through every epoch after
+    // `from_epoch` up to `to_epoch`
+    pub(crate) fn roll_epoch_seed(&mut self, from_epoch: u64, to_epoch: u64) {
+        let Some(mut seed) = self.epoch_seed else {
+            return;
+        };
+        for epoch in from_epoch + 1..=to_epoch {
+            seed = derive_epoch_seed(&seed, epoch, &self.aggregate_epoch_entropy(epoch - 1));
+        }
+        self.epoch_seed = Some(seed);
+    }
+
+    // Check that `seeds` holds the seed of every epoch from `from_epoch` to
+    // `to_epoch` inclusive, each derived from the one before it and the
+    // entropy of the prior epoch. The first seed is trusted as given. Only
+    // chains within the retained selection history can be checked.
+    pub fn verify_seed_chain(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+        seeds: &[[u8; 32]],
+    ) -> Result<(), ValidationError> {
+        if to_epoch < from_epoch || seeds.len() as u64 != to_epoch - from_epoch + 1 {
+            return Err(ValidationError::InvalidSeedChain);
+        }
+        for (pair, epoch) in seeds.windows(2).zip(from_epoch + 1..) {
+            let aggregate = self.aggregate_epoch_entropy(epoch - 1);
+            if derive_epoch_seed(&pair[0], epoch, &aggregate) != pair[1] {
+                return Err(ValidationError::InvalidSeedChain);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::proof_of_presence::tests::test_network;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_seed_chain_detects_tampering() {
+        let (mut validator, keys) = test_network(5);
+        let mut seeds = vec![[7u8; 32]];
+        for epoch in 10..14 {
+            let selected = &keys[(epoch as usize % 3)..];
+            validator.record_selection(epoch, selected);
+            let aggregate = validator.aggregate_vrf_outputs(epoch, selected);
+            seeds.push(derive_epoch_seed(
+                seeds.last().unwrap(),
+                epoch + 1,
+                &aggregate,
+            ));
+        }
+        assert_eq!(validator.verify_seed_chain(10, 14, &seeds), Ok(()));
+        assert_eq!(validator.verify_seed_chain(12, 14, &seeds[2..]), Ok(()));
+
+        let mut tampered = seeds.clone();
+        tampered[2][0] ^= 1;
+        assert_eq!(
+            validator.verify_seed_chain(10, 14, &tampered),
+            Err(ValidationError::InvalidSeedChain)
+        );
+        assert_eq!(
+            validator.verify_seed_chain(10, 13, &seeds),
+            Err(ValidationError::InvalidSeedChain)
+        );
+        // Epoch 9 has no recorded selection to contribute
+        assert_eq!(
+            validator.verify_seed_chain(9, 10, &seeds[..2]),
+            Err(ValidationError::InvalidSeedChain)
+        );
+    }
+
+    #[test]
+    fn test_epoch_entropy_from_selected_outputs() {
+        let (mut validator, keys) = test_network(5);
+        validator.record_selection(3, &keys[..3]);
+        let entropy = validator.aggregate_epoch_entropy(3);
+        let reordered = [keys[2], keys[0], keys[1]];
+        assert_eq!(validator.aggregate_vrf_outputs(3, &reordered), entropy);
+
+        // Swapping one validator swaps one output
+        validator.record_selection(3, &[keys[0], keys[1], keys[4]]);
+        let swapped = validator.aggregate_epoch_entropy(3);
+        assert_ne!(swapped, entropy);
+        validator.record_selection(3, &[keys[0], keys[1], keys[4]]);
+        assert_eq!(validator.aggregate_epoch_entropy(3), swapped);
+
+        // advance_epoch rolls the seed through each epoch it passes
+        let clock = Arc::new(ManualClock::new(1_700_000_000));
+        validator.set_clock(clock.clone());
+        validator.genesis_time = 1_700_000_000 - 3 * 3600;
+        validator.current_epoch = 2;
+        validator.epoch_seed = Some([7u8; 32]);
+        assert_eq!(validator.advance_epoch(), 3);
+        clock.advance(3600);
+        assert_eq!(validator.advance_epoch(), 4);
+        let seed_3 = derive_epoch_seed(&[7u8; 32], 3, &validator.aggregate_epoch_entropy(2));
+        let expected = derive_epoch_seed(&seed_3, 4, &swapped);
+        assert_eq!(validator.epoch_seed, Some(expected));
+    }
+}
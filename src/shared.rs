@@ -0,0 +1,85 @@
+// MeshX - The Immutable Global Device Mesh
+// Validator shared across concurrent tasks
+// Copyright (c) 2025 MeshX Foundation
+
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::proof_of_presence::PopValidator;
+
+// Cloneable handle giving concurrent readers (RPC, metrics) and the
+// epoch-advancing writer access to one validator.
+//
+// Lock ordering: the whole validator sits behind this single lock, so there
+// is no ordering between internal locks to get wrong. To stay deadlock-free:
+// - never request a write guard while holding a read guard on the same
+//   thread, as the write waits for that read to be released
+// - never hold a guard across an `.await` or a blocking call; prefer
+//   `with_read`/`with_write`, which release the guard when the closure returns
+// - when locking several validators, lock them in a consistent order
+#[derive(Clone)]
+pub struct SharedValidator {
+    inner: Arc<RwLock<PopValidator>>,
+}
+
+impl SharedValidator {
+    pub fn new(validator: PopValidator) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(validator)),
+        }
+    }
+
+    // A panic while holding the lock leaves the validator poisoned; its
+    // state is still usable, so the poison is ignored rather than spread
+    pub fn read(&self) -> RwLockReadGuard<'_, PopValidator> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, PopValidator> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn with_read<T>(&self, f: impl FnOnce(&PopValidator) -> T) -> T {
+        f(&self.read())
+    }
+
+    pub fn with_write<T>(&self, f: impl FnOnce(&mut PopValidator) -> T) -> T {
+        f(&mut self.write())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concurrent_reads_see_consistent_snapshot() {
+        let shared = SharedValidator::new(PopValidator::new(1));
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                let reader = shared.clone();
+                scope.spawn(move || {
+                    for _ in 0..1_000 {
+                        // Both fields are updated under one write guard
+                        reader.with_read(|validator| {
+                            assert_eq!(validator.current_epoch, validator.genesis_time);
+                        });
+                    }
+                });
+            }
+
+            let writer = shared.clone();
+            scope.spawn(move || {
+                for epoch in 1..=1_000 {
+                    writer.with_write(|validator| {
+                        validator.current_epoch = epoch;
+                        std::thread::yield_now();
+                        validator.genesis_time = epoch;
+                    });
+                }
+            });
+        });
+
+        assert_eq!(shared.read().current_epoch, 1_000);
+    }
+}
@@ -0,0 +1,146 @@
+// MeshX - The Immutable Global Device Mesh
+// Signed stake snapshots for audits
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::{Keypair, PublicKey, Signature, Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::proof_of_presence::{PopValidator, ValidationError};
+use crate::stake::StakeAmount;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeEntry {
+    pub pubkey: PublicKey,
+    pub effective_stake: StakeAmount, // Weight the node carries in selection
+}
+
+// The stake set selection was based on at an epoch boundary, committed to by
+// a Merkle root over the entries so a signed root pins down every stake
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeSnapshot {
+    pub epoch: u64,
+    pub entries: Vec<StakeEntry>, // Sorted by pubkey bytes
+    pub merkle_root: [u8; 32],
+}
+
+impl StakeSnapshot {
+    // Root over the leaves in entry order. Odd levels carry their last node
+    // up by pairing it with itself; an empty set hashes to the bare domain.
+    pub fn compute_root(entries: &[StakeEntry]) -> [u8; 32] {
+        let mut level: Vec<[u8; 32]> = entries.iter().map(leaf_hash).collect();
+        if level.is_empty() {
+            return Sha3_256::digest(b"MESHX_STAKE_EMPTY").into();
+        }
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| {
+                    let mut hasher = Sha3_256::new();
+                    hasher.update(b"MESHX_STAKE_NODE");
+                    hasher.update(pair[0]);
+                    hasher.update(pair.get(1).unwrap_or(&pair[0]));
+                    hasher.finalize().into()
+                })
+                .collect();
+        }
+        level[0]
+    }
+
+    pub fn sign(&self, keypair: &Keypair) -> Signature {
+        keypair.sign(&self.signing_payload())
+    }
+
+    // Check that the entries match the root and the root was signed by
+    // `signer` for this epoch
+    pub fn verify(&self, signer: &PublicKey, signature: &Signature) -> Result<(), ValidationError> {
+        if Self::compute_root(&self.entries) != self.merkle_root {
+            return Err(ValidationError::InvalidSignature);
+        }
+        signer
+            .verify(&self.signing_payload(), signature)
+            .map_err(|_| ValidationError::InvalidSignature)
+    }
+
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = b"MESHX_STAKE_SNAPSHOT".to_vec();
+        payload.extend_from_slice(&self.epoch.to_le_bytes());
+        payload.extend_from_slice(&self.merkle_root);
+        payload
+    }
+}
+
+fn leaf_hash(entry: &StakeEntry) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"MESHX_STAKE_LEAF");
+    hasher.update(entry.pubkey.as_bytes());
+    hasher.update(entry.effective_stake.base_units().to_le_bytes());
+    hasher.finalize().into()
+}
+
+impl PopValidator {
+    // Snapshot every registered node's effective stake, labelled with the
+    // epoch whose selection it backs
+    pub fn stake_snapshot(&self, epoch: u64) -> StakeSnapshot {
+        let mut entries: Vec<StakeEntry> = self
+            .nodes
+            .values()
+            .map(|node| StakeEntry {
+                pubkey: node.pubkey,
+                effective_stake: self.effective_stake(&node.pubkey),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.pubkey.as_bytes().cmp(b.pubkey.as_bytes()));
+        StakeSnapshot {
+            epoch,
+            merkle_root: StakeSnapshot::compute_root(&entries),
+            entries,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presence::tests::test_keypair;
+    use crate::proof_of_presence::tests::test_network;
+
+    #[test]
+    fn test_stake_snapshot_root_and_signature() {
+        let (mut validator, keys) = test_network(5);
+        let snapshot = validator.stake_snapshot(7);
+        assert_eq!(snapshot.entries.len(), 5);
+
+        let auditor = test_keypair(42);
+        let signature = snapshot.sign(&auditor);
+        assert_eq!(snapshot.verify(&auditor.public, &signature), Ok(()));
+        assert_eq!(
+            snapshot.verify(&test_keypair(43).public, &signature),
+            Err(ValidationError::InvalidSignature)
+        );
+
+        for key in &keys {
+            let node = validator.nodes.get_mut(key).unwrap();
+            node.stake_amount = node
+                .stake_amount
+                .saturating_add(StakeAmount::from_base_units(1));
+            assert_ne!(
+                validator.stake_snapshot(7).merkle_root,
+                snapshot.merkle_root
+            );
+            let node = validator.nodes.get_mut(key).unwrap();
+            node.stake_amount = node
+                .stake_amount
+                .saturating_sub(StakeAmount::from_base_units(1));
+        }
+        assert_eq!(validator.stake_snapshot(7), snapshot);
+
+        // Entries edited after signing no longer match the signed root
+        let mut tampered = snapshot.clone();
+        tampered.entries[2].effective_stake = StakeAmount::from_meshx(1);
+        assert_eq!(
+            tampered.verify(&auditor.public, &signature),
+            Err(ValidationError::InvalidSignature)
+        );
+    }
+}
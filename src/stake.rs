@@ -0,0 +1,168 @@
+// MeshX - The Immutable Global Device Mesh
+// Token amounts
+// Copyright (c) 2025 MeshX Foundation
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+// MESHX is divisible to 9 decimal places
+pub const MESHX_DECIMALS: u32 = 9;
+const BASE_UNITS_PER_MESHX: u64 = 10u64.pow(MESHX_DECIMALS);
+
+// An amount of MESHX held as an integer count of base units
+// (10^-MESHX_DECIMALS MESHX), so stake and reward math never mixes whole
+// tokens with fractions. Serialized as the bare base-unit integer.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct StakeAmount(u64);
+
+impl StakeAmount {
+    pub const ZERO: StakeAmount = StakeAmount(0);
+    pub const MAX: StakeAmount = StakeAmount(u64::MAX);
+
+    pub const fn from_base_units(base_units: u64) -> Self {
+        Self(base_units)
+    }
+
+    // Whole MESHX, saturating at the largest representable amount
+    pub const fn from_meshx(meshx: u64) -> Self {
+        Self(meshx.saturating_mul(BASE_UNITS_PER_MESHX))
+    }
+
+    pub const fn base_units(self) -> u64 {
+        self.0
+    }
+
+    // Approximate amount in MESHX, for ratios and display-only math
+    pub fn as_meshx_f64(self) -> f64 {
+        self.0 as f64 / BASE_UNITS_PER_MESHX as f64
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    // Integer division, truncating toward zero
+    pub fn checked_div(self, divisor: u64) -> Option<Self> {
+        self.0.checked_div(divisor).map(Self)
+    }
+}
+
+// Human units, e.g. "100000.5 MESHX"
+impl fmt::Display for StakeAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / BASE_UNITS_PER_MESHX;
+        let fraction = self.0 % BASE_UNITS_PER_MESHX;
+        if fraction == 0 {
+            return write!(f, "{} MESHX", whole);
+        }
+        let digits = format!("{:0width$}", fraction, width = MESHX_DECIMALS as usize);
+        write!(f, "{}.{} MESHX", whole, digits.trim_end_matches('0'))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum StakeParseError {
+    #[error("Invalid MESHX amount")]
+    Invalid,
+    #[error("More than {} decimal places", MESHX_DECIMALS)]
+    TooPrecise,
+    #[error("Amount too large")]
+    Overflow,
+}
+
+// Parses human units with an optional "MESHX" suffix, e.g. "100000.5 MESHX"
+impl FromStr for StakeAmount {
+    type Err = StakeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s.trim();
+        let number = number.strip_suffix("MESHX").unwrap_or(number).trim_end();
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err(StakeParseError::Invalid);
+        }
+        if fraction.len() > MESHX_DECIMALS as usize {
+            return Err(StakeParseError::TooPrecise);
+        }
+
+        let whole: u64 = whole.parse().map_err(|_| StakeParseError::Overflow)?;
+        let fraction_units = if fraction.is_empty() {
+            0
+        } else {
+            let padding = 10u64.pow(MESHX_DECIMALS - fraction.len() as u32);
+            fraction
+                .parse::<u64>()
+                .map_err(|_| StakeParseError::Invalid)?
+                * padding
+        };
+        whole
+            .checked_mul(BASE_UNITS_PER_MESHX)
+            .and_then(|units| units.checked_add(fraction_units))
+            .map(Self)
+            .ok_or(StakeParseError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let amount: StakeAmount = "100000.5 MESHX".parse().unwrap();
+        assert_eq!(amount.base_units(), 100_000_500_000_000);
+        assert_eq!(amount.to_string(), "100000.5 MESHX");
+        assert_eq!("42".parse(), Ok(StakeAmount::from_meshx(42)));
+        assert_eq!(
+            "0.000000001 MESHX".parse(),
+            Ok(StakeAmount::from_base_units(1))
+        );
+
+        assert_eq!(
+            "1.0000000001".parse::<StakeAmount>(),
+            Err(StakeParseError::TooPrecise)
+        );
+        assert_eq!(
+            "-5 MESHX".parse::<StakeAmount>(),
+            Err(StakeParseError::Invalid)
+        );
+        assert_eq!(
+            "99999999999 MESHX".parse::<StakeAmount>(),
+            Err(StakeParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_comparison_and_arithmetic() {
+        let half = StakeAmount::from_base_units(BASE_UNITS_PER_MESHX / 2);
+        let minimum = StakeAmount::from_meshx(100_000);
+        let stake = minimum.checked_add(half).unwrap();
+        assert!(stake > minimum);
+        assert_eq!(stake.checked_sub(minimum), Some(half));
+        assert_eq!(minimum.checked_sub(stake), None);
+        assert_eq!(StakeAmount::MAX.checked_add(half), None);
+        assert_eq!(StakeAmount::MAX.saturating_add(half), StakeAmount::MAX);
+        assert_eq!(
+            minimum.checked_div(3),
+            Some(StakeAmount::from_base_units(33_333_333_333_333))
+        );
+        assert_eq!(minimum.checked_div(0), None);
+    }
+}
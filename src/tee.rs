@@ -0,0 +1,712 @@
+// MeshX - The Immutable Global Device Mesh
+// TEE quote verification
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use sha3::{Digest, Sha3_256};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::dcap::SgxQuote;
+use crate::proof_of_presence::{PopValidator, TeeAttestation, TeeType, ValidationError};
+
+// Verifies the quote of one TEE technology against the expected enclave hash
+pub trait TeeVerifier: Send + Sync {
+    fn verify(&self, quote: &[u8], expected_enclave_hash: &[u8; 32])
+        -> Result<(), ValidationError>;
+
+    // Verify several quotes at once, results in input order. Verifiers with
+    // work common to many quotes, such as checking a shared certificate
+    // chain, override this to do that work once per batch.
+    fn verify_batch(
+        &self,
+        quotes: &[&[u8]],
+        expected_enclave_hash: &[u8; 32],
+    ) -> Vec<Result<(), ValidationError>> {
+        quotes
+            .iter()
+            .map(|quote| self.verify(quote, expected_enclave_hash))
+            .collect()
+    }
+}
+
+type CachedVerification = (u64, Result<(), ValidationError>);
+
+// Verifiers keyed by TEE type. Built-in types are registered by default;
+// new technologies are added as `TeeType::Custom` without editing the enum.
+//
+// Attestation results are cached by quote. Every cached result is tagged
+// with the trust-config generation it was computed under, and any change to
+// the verifiers or revoked signers bumps the generation, so a result from
+// before the change is never served after it.
+pub struct TeeVerifierRegistry {
+    verifiers: HashMap<TeeType, Arc<dyn TeeVerifier>>,
+    revoked_signers: HashSet<PublicKey>, // Revoked at runtime; kept across reloads
+    listed_signers: HashSet<PublicKey>,  // From the revocation list, replaced on reload
+    generation: u64,
+    cache: Mutex<HashMap<[u8; 32], CachedVerification>>,
+    pub cache_capacity: usize, // 0 disables caching
+    quote_format_check: bool,  // Built-in types must submit their own quote format
+}
+
+impl Default for TeeVerifierRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            verifiers: HashMap::new(),
+            revoked_signers: HashSet::new(),
+            listed_signers: HashSet::new(),
+            generation: 0,
+            cache: Mutex::new(HashMap::new()),
+            cache_capacity: 10_000,
+            quote_format_check: false,
+        };
+        registry.register(TeeType::IntelSgx, Box::new(SgxQuoteVerifier));
+        registry.register(
+            TeeType::ArmTrustZone,
+            Box::new(FnVerifier(verify_trustzone_quote)),
+        );
+        registry.register(
+            TeeType::AppleSecureEnclave,
+            Box::new(FnVerifier(verify_secure_enclave_quote)),
+        );
+        registry.register(TeeType::AmdSev, Box::new(FnVerifier(verify_sev_quote)));
+        registry
+    }
+}
+
+// Clones share the verifiers and trust config but start with an empty cache
+impl Clone for TeeVerifierRegistry {
+    fn clone(&self) -> Self {
+        Self {
+            verifiers: self.verifiers.clone(),
+            revoked_signers: self.revoked_signers.clone(),
+            listed_signers: self.listed_signers.clone(),
+            generation: self.generation,
+            cache: Mutex::new(HashMap::new()),
+            cache_capacity: self.cache_capacity,
+            quote_format_check: self.quote_format_check,
+        }
+    }
+}
+
+impl TeeVerifierRegistry {
+    // Register a verifier, replacing any existing one for the type
+    pub fn register(&mut self, tee_type: TeeType, verifier: Box<dyn TeeVerifier>) {
+        self.verifiers.insert(tee_type, Arc::from(verifier));
+        self.generation += 1;
+    }
+
+    // Reject every attestation signed by the key from now on
+    pub fn revoke_signer(&mut self, signer: PublicKey) {
+        self.revoked_signers.insert(signer);
+        self.generation += 1;
+    }
+
+    // Replace the revocation list with the one read from `source`: one
+    // hex-encoded public key per line, with blank lines and `#` comments
+    // ignored. The list is swapped in only once all of it has parsed, so a
+    // bad file leaves the current one in force. Signers revoked through
+    // `revoke_signer` stay revoked whatever the list says. Returns the
+    // number of distinct signers loaded.
+    pub fn reload_revoked_signers(
+        &mut self,
+        mut source: impl Read,
+    ) -> Result<usize, ValidationError> {
+        let mut raw = String::new();
+        source
+            .read_to_string(&mut raw)
+            .map_err(|_| ValidationError::MalformedRevocationList)?;
+        let revoked = raw
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_signer(line).ok_or(ValidationError::MalformedRevocationList))
+            .collect::<Result<HashSet<_>, _>>()?;
+        self.listed_signers = revoked;
+        self.generation += 1;
+        Ok(self.listed_signers.len())
+    }
+
+    pub fn is_revoked(&self, signer: &PublicKey) -> bool {
+        self.revoked_signers.contains(signer) || self.listed_signers.contains(signer)
+    }
+
+    // Reject quotes whose header belongs to a different built-in TEE type
+    // than the one declared, so a weakly verified type can't be claimed
+    // with another type's quote
+    pub fn set_quote_format_check(&mut self, enabled: bool) {
+        self.quote_format_check = enabled;
+        self.generation += 1;
+    }
+
+    // Counter of trust-config changes; cached results from older
+    // generations are ignored
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    // Check an attestation's signer and quote, reusing the result of an
+    // identical check made under the current trust config
+    pub fn verify_attestation(
+        &self,
+        attestation: &TeeAttestation,
+        expected_enclave_hash: &[u8; 32],
+    ) -> Result<(), ValidationError> {
+        if self.cache_capacity == 0 {
+            return self.verify_uncached(attestation, expected_enclave_hash);
+        }
+
+        let key = cache_key(attestation, expected_enclave_hash);
+        let cached = self.lock_cache().get(&key).cloned();
+        if let Some((generation, result)) = cached {
+            if generation == self.generation {
+                return result;
+            }
+        }
+
+        let result = self.verify_uncached(attestation, expected_enclave_hash);
+        let mut cache = self.lock_cache();
+        if cache.len() >= self.cache_capacity {
+            cache.clear();
+        }
+        cache.insert(key, (self.generation, result.clone()));
+        result
+    }
+
+    // `verify_attestation` for many attestations. The cache is consulted and
+    // updated under one lock each, identical attestations are verified once,
+    // and the rest go to their type's verifier in batches of up to `threads`
+    // parallel chunks. Results are in input order.
+    pub fn verify_attestation_batch(
+        &self,
+        attestations: &[&TeeAttestation],
+        expected_enclave_hash: &[u8; 32],
+        threads: usize,
+    ) -> Vec<Result<(), ValidationError>> {
+        let keys: Vec<[u8; 32]> = attestations
+            .iter()
+            .map(|attestation| cache_key(attestation, expected_enclave_hash))
+            .collect();
+        let mut results: HashMap<[u8; 32], Result<(), ValidationError>> = HashMap::new();
+        if self.cache_capacity > 0 {
+            let cache = self.lock_cache();
+            for key in &keys {
+                if let Some((generation, result)) = cache.get(key) {
+                    if *generation == self.generation {
+                        results.insert(*key, result.clone());
+                    }
+                }
+            }
+        }
+
+        // Distinct uncached attestations, grouped by verifier
+        let mut pending: HashMap<&TeeType, Vec<([u8; 32], &TeeAttestation)>> = HashMap::new();
+        let mut verified = Vec::new();
+        for (key, attestation) in keys.iter().zip(attestations) {
+            if results.contains_key(key) {
+                continue;
+            }
+            let checked = self.check_signer_and_format(attestation);
+            results.insert(*key, checked.clone().map(|_| ()));
+            if checked.is_ok() {
+                pending
+                    .entry(&attestation.tee_type)
+                    .or_default()
+                    .push((*key, *attestation));
+            }
+            verified.push(*key);
+        }
+        for (tee_type, group) in pending {
+            let verifier = &self.verifiers[tee_type];
+            let chunk_size = group.len().div_ceil(threads.max(1)).max(1);
+            let outcomes: Vec<Result<(), ValidationError>> = std::thread::scope(|scope| {
+                let workers: Vec<_> = group
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            let quotes: Vec<&[u8]> = chunk
+                                .iter()
+                                .map(|(_, attestation)| attestation.quote.as_slice())
+                                .collect();
+                            verifier.verify_batch(&quotes, expected_enclave_hash)
+                        })
+                    })
+                    .collect();
+                workers
+                    .into_iter()
+                    .flat_map(|worker| worker.join().expect("quote verifier panicked"))
+                    .collect()
+            });
+            for ((key, _), outcome) in group.iter().zip(outcomes) {
+                results.insert(*key, outcome);
+            }
+        }
+
+        if self.cache_capacity > 0 && !verified.is_empty() {
+            let mut cache = self.lock_cache();
+            if cache.len() + verified.len() > self.cache_capacity {
+                cache.clear();
+            }
+            for key in verified.into_iter().take(self.cache_capacity) {
+                cache.insert(key, (self.generation, results[&key].clone()));
+            }
+        }
+        keys.iter().map(|key| results[key].clone()).collect()
+    }
+
+    fn verify_uncached(
+        &self,
+        attestation: &TeeAttestation,
+        expected_enclave_hash: &[u8; 32],
+    ) -> Result<(), ValidationError> {
+        self.check_signer_and_format(attestation)?
+            .verify(&attestation.quote, expected_enclave_hash)
+    }
+
+    // The checks made before the quote reaches its verifier, returning that
+    // verifier
+    fn check_signer_and_format(
+        &self,
+        attestation: &TeeAttestation,
+    ) -> Result<&Arc<dyn TeeVerifier>, ValidationError> {
+        if self.is_revoked(&attestation.signer_pubkey) {
+            return Err(ValidationError::RevokedSigner);
+        }
+        self.verifier_for(&attestation.tee_type, &attestation.quote)
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, HashMap<[u8; 32], CachedVerification>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn verify(
+        &self,
+        tee_type: &TeeType,
+        quote: &[u8],
+        expected_enclave_hash: &[u8; 32],
+    ) -> Result<(), ValidationError> {
+        self.verifier_for(tee_type, quote)?
+            .verify(quote, expected_enclave_hash)
+    }
+
+    fn verifier_for(
+        &self,
+        tee_type: &TeeType,
+        quote: &[u8],
+    ) -> Result<&Arc<dyn TeeVerifier>, ValidationError> {
+        let verifier = self
+            .verifiers
+            .get(tee_type)
+            .ok_or(ValidationError::UnsupportedTeeType)?;
+        if self.quote_format_check
+            && !quote.is_empty()
+            && !matches!(tee_type, TeeType::Custom(_))
+            && quote_format(quote).as_ref() != Some(tee_type)
+        {
+            return Err(ValidationError::TeeTypeQuoteMismatch);
+        }
+        Ok(verifier)
+    }
+}
+
+impl PopValidator {
+    // Trust config changes made through the validator are snapshotted for
+    // retrospective audits as they happen
+    pub fn register_tee_verifier(&mut self, tee_type: TeeType, verifier: Box<dyn TeeVerifier>) {
+        self.tee_verifiers.register(tee_type, verifier);
+        self.record_policy_snapshot();
+    }
+
+    pub fn revoke_attestation_signer(&mut self, signer: PublicKey) {
+        self.tee_verifiers.revoke_signer(signer);
+        self.record_policy_snapshot();
+    }
+
+    pub fn set_quote_format_check(&mut self, enabled: bool) {
+        self.tee_verifiers.set_quote_format_check(enabled);
+        self.record_policy_snapshot();
+    }
+
+    pub fn reload_revocation_list(&mut self, source: impl Read) -> Result<usize, ValidationError> {
+        let loaded = self.tee_verifiers.reload_revoked_signers(source)?;
+        self.record_policy_snapshot();
+        Ok(loaded)
+    }
+
+    // Verify SGX quotes as DCAP quotes certified up to `root_ca_pem`, the
+    // Intel SGX Root CA certificate as published by Intel
+    #[cfg(feature = "sgx-dcap")]
+    pub fn enable_sgx_dcap(&mut self, root_ca_pem: &str) -> Result<(), ValidationError> {
+        let verifier = crate::dcap::DcapVerifier::from_root_pem(root_ca_pem)?;
+        self.register_tee_verifier(TeeType::IntelSgx, Box::new(verifier));
+        Ok(())
+    }
+}
+
+fn parse_signer(hex: &str) -> Option<PublicKey> {
+    if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let bytes: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|at| u8::from_str_radix(&hex[at..at + 2], 16))
+        .collect::<Result<_, _>>()
+        .ok()?;
+    PublicKey::from_bytes(&bytes).ok()
+}
+
+// Built-in TEE type whose quote format the header matches, as far as
+// needed to tell the formats apart: SGX DCAP quotes open with version 3 or
+// 4, an ECDSA key type and a zero TEE type; SEV-SNP reports with version 2
+// or 3; TrustZone PSA tokens with the COSE_Sign1 tag; Secure Enclave
+// attestations with a three-entry CBOR map.
+fn quote_format(quote: &[u8]) -> Option<TeeType> {
+    let u16_at = |at: usize| {
+        quote
+            .get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+    };
+    let u32_at = |at: usize| {
+        quote
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    if matches!(u16_at(0), Some(3 | 4)) && matches!(u16_at(2), Some(2 | 3)) && u32_at(4) == Some(0)
+    {
+        return Some(TeeType::IntelSgx);
+    }
+    if matches!(u32_at(0), Some(2 | 3)) {
+        return Some(TeeType::AmdSev);
+    }
+    match quote.first() {
+        Some(0xD2) => Some(TeeType::ArmTrustZone),
+        Some(0xA3) => Some(TeeType::AppleSecureEnclave),
+        _ => None,
+    }
+}
+
+// Everything the cached result depends on besides the trust config
+fn cache_key(attestation: &TeeAttestation, expected_enclave_hash: &[u8; 32]) -> [u8; 32] {
+    let tee_type = serde_json::to_vec(&attestation.tee_type).expect("TEE type serializes");
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"MESHX_QUOTE_CACHE");
+    hasher.update((tee_type.len() as u64).to_le_bytes());
+    hasher.update(tee_type);
+    hasher.update(attestation.signer_pubkey.as_bytes());
+    hasher.update(expected_enclave_hash);
+    hasher.update(&attestation.quote);
+    hasher.finalize().into()
+}
+
+// Adapter for the built-in quote checks
+struct FnVerifier(fn(&[u8]) -> Result<(), ValidationError>);
+
+impl TeeVerifier for FnVerifier {
+    fn verify(&self, quote: &[u8], _expected: &[u8; 32]) -> Result<(), ValidationError> {
+        (self.0)(quote)
+    }
+}
+
+// Built-in SGX check: the quote must be a well-formed DCAP quote from the
+// expected enclave. Its signatures are only checked by the sgx-dcap build
+// with a pinned root, see PopValidator::enable_sgx_dcap.
+struct SgxQuoteVerifier;
+
+impl TeeVerifier for SgxQuoteVerifier {
+    fn verify(&self, quote: &[u8], expected: &[u8; 32]) -> Result<(), ValidationError> {
+        SgxQuote::parse(quote)?.check_mr_enclave(expected)
+    }
+}
+
+// Placeholder TEE verification methods
+
+fn verify_trustzone_quote(quote: &[u8]) -> Result<(), ValidationError> {
+    // TODO: Implement ARM TrustZone attestation verification
+    if quote.is_empty() {
+        return Err(ValidationError::InvalidQuote);
+    }
+    Ok(())
+}
+
+fn verify_secure_enclave_quote(quote: &[u8]) -> Result<(), ValidationError> {
+    // TODO: Implement Apple Secure Enclave verification
+    if quote.is_empty() {
+        return Err(ValidationError::InvalidQuote);
+    }
+    Ok(())
+}
+
+fn verify_sev_quote(quote: &[u8]) -> Result<(), ValidationError> {
+    // TODO: Implement AMD SEV attestation verification
+    if quote.is_empty() {
+        return Err(ValidationError::InvalidQuote);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dcap::tests::test_quote;
+    use crate::proof_of_presence::hex_encode;
+    use crate::proof_of_presence::tests::{test_network, test_node, test_pubkey};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // Accepts quotes that start with the expected enclave hash
+    struct PrefixVerifier {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl TeeVerifier for PrefixVerifier {
+        fn verify(&self, quote: &[u8], expected: &[u8; 32]) -> Result<(), ValidationError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if quote.starts_with(expected) {
+                Ok(())
+            } else {
+                Err(ValidationError::InvalidQuote)
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_tee_verifier() {
+        let mut validator = PopValidator::new(1);
+        let mut attestation = test_node(1, 50.0, 10.0, 100_000).tee_attestation;
+        attestation.tee_type = TeeType::Custom(7);
+        attestation.quote = [0x42; 40].to_vec();
+
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::UnsupportedTeeType)
+        );
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        validator.register_tee_verifier(
+            TeeType::Custom(7),
+            Box::new(PrefixVerifier {
+                calls: calls.clone(),
+            }),
+        );
+        assert_eq!(validator.verify_tee_attestation(&attestation), Ok(()));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        attestation.quote = vec![0; 40];
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::InvalidQuote)
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Other custom ids remain unsupported
+        attestation.tee_type = TeeType::Custom(8);
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::UnsupportedTeeType)
+        );
+    }
+
+    #[test]
+    fn test_cached_pass_not_served_after_revocation() {
+        let mut validator = PopValidator::new(1);
+        let mut attestation = test_node(1, 50.0, 10.0, 100_000).tee_attestation;
+        attestation.tee_type = TeeType::Custom(7);
+        attestation.quote = [0x42; 40].to_vec();
+        let calls = Arc::new(AtomicUsize::new(0));
+        validator.register_tee_verifier(
+            TeeType::Custom(7),
+            Box::new(PrefixVerifier {
+                calls: calls.clone(),
+            }),
+        );
+
+        assert_eq!(validator.verify_tee_attestation(&attestation), Ok(()));
+        assert_eq!(validator.verify_tee_attestation(&attestation), Ok(()));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let generation = validator.tee_verifiers.generation();
+        validator.revoke_attestation_signer(attestation.signer_pubkey);
+        assert!(validator.tee_verifiers.generation() > generation);
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::RevokedSigner)
+        );
+    }
+
+    // Counts batches and the quotes in them
+    struct BatchCountingVerifier {
+        batches: Arc<AtomicUsize>,
+        quotes: Arc<AtomicUsize>,
+    }
+
+    impl TeeVerifier for BatchCountingVerifier {
+        fn verify(&self, _quote: &[u8], _expected: &[u8; 32]) -> Result<(), ValidationError> {
+            panic!("batched quotes should be verified as a batch");
+        }
+
+        fn verify_batch(
+            &self,
+            quotes: &[&[u8]],
+            _expected: &[u8; 32],
+        ) -> Vec<Result<(), ValidationError>> {
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            self.quotes.fetch_add(quotes.len(), Ordering::SeqCst);
+            quotes
+                .iter()
+                .map(|quote| match quote.first() {
+                    Some(0) => Err(ValidationError::InvalidQuote),
+                    _ => Ok(()),
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_attestation_batch_shares_verifier_work() {
+        let mut validator = PopValidator::new(1);
+        validator.attestation_batch_threads = 1;
+        let batches = Arc::new(AtomicUsize::new(0));
+        let quotes = Arc::new(AtomicUsize::new(0));
+        validator.register_tee_verifier(
+            TeeType::Custom(7),
+            Box::new(BatchCountingVerifier {
+                batches: batches.clone(),
+                quotes: quotes.clone(),
+            }),
+        );
+        let attestation = |seed: u8, first_byte: u8| {
+            let mut attestation = test_node(seed, 50.0, 10.0, 100_000).tee_attestation;
+            attestation.tee_type = TeeType::Custom(7);
+            attestation.quote = vec![first_byte; 40];
+            attestation
+        };
+        // Three distinct quotes, one of them repeated
+        let batch = vec![
+            attestation(1, 1),
+            attestation(2, 0),
+            attestation(1, 1),
+            attestation(3, 1),
+        ];
+
+        let results = validator.verify_attestation_batch(&batch);
+        assert_eq!(
+            results,
+            vec![Ok(()), Err(ValidationError::InvalidQuote), Ok(()), Ok(())]
+        );
+        assert_eq!(batches.load(Ordering::SeqCst), 1);
+        assert_eq!(quotes.load(Ordering::SeqCst), 3);
+
+        // The results are cached, so a repeat batch needs no verification
+        assert_eq!(validator.verify_attestation_batch(&batch), results);
+        assert_eq!(batches.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_reloaded_revocation_list_applies_immediately() {
+        let (mut validator, keys) = test_network(4);
+        let node = validator.nodes[&keys[0]].clone();
+        assert!(validator.validate_node(&node).is_ok());
+
+        let signer = hex_encode(node.tee_attestation.signer_pubkey.as_bytes());
+        let list = format!(
+            "# Compromised signers\n\n{}\n{}  # listed twice\n{}\n",
+            signer,
+            signer,
+            hex_encode(test_pubkey(9).as_bytes())
+        );
+        assert_eq!(validator.reload_revocation_list(list.as_bytes()), Ok(2));
+        assert_eq!(
+            validator.validate_node(&node),
+            Err(ValidationError::RevokedSigner)
+        );
+
+        // A malformed list is rejected whole, keeping the revocation
+        let malformed = format!("{}\nnot-a-key\n", hex_encode(test_pubkey(9).as_bytes()));
+        assert_eq!(
+            validator.reload_revocation_list(malformed.as_bytes()),
+            Err(ValidationError::MalformedRevocationList)
+        );
+        assert_eq!(
+            validator.validate_node(&node),
+            Err(ValidationError::RevokedSigner)
+        );
+
+        assert_eq!(validator.reload_revocation_list(&b""[..]), Ok(0));
+        assert!(validator.validate_node(&node).is_ok());
+
+        // An emergency revocation outlives reloads of a list that omits it
+        let other = validator.nodes[&keys[1]].clone();
+        validator.revoke_attestation_signer(other.tee_attestation.signer_pubkey);
+        assert_eq!(validator.reload_revocation_list(list.as_bytes()), Ok(2));
+        assert_eq!(validator.reload_revocation_list(&b""[..]), Ok(0));
+        assert_eq!(
+            validator.validate_node(&other),
+            Err(ValidationError::RevokedSigner)
+        );
+        assert!(validator.validate_node(&node).is_ok());
+    }
+
+    #[test]
+    fn test_quote_format_must_match_declared_type() {
+        let mut validator = PopValidator::new(1);
+        let mut attestation = test_node(1, 50.0, 10.0, 100_000).tee_attestation;
+        let sgx_quote = test_quote(0);
+        let sev_report = [vec![2, 0, 0, 0, 1, 0, 0, 0], vec![0xCD; 48]].concat();
+        attestation.quote = sev_report.clone();
+
+        // Off by default, leaving the SGX verifier to reject the report
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::InvalidQuote)
+        );
+
+        validator.set_quote_format_check(true);
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::TeeTypeQuoteMismatch)
+        );
+        attestation.quote = sgx_quote;
+        assert_eq!(validator.verify_tee_attestation(&attestation), Ok(()));
+
+        attestation.tee_type = TeeType::AmdSev;
+        assert_eq!(
+            validator.verify_tee_attestation(&attestation),
+            Err(ValidationError::TeeTypeQuoteMismatch)
+        );
+        attestation.quote = sev_report;
+        assert_eq!(validator.verify_tee_attestation(&attestation), Ok(()));
+    }
+
+    #[test]
+    fn test_builtin_verifiers_registered() {
+        let registry = TeeVerifierRegistry::default();
+        assert_eq!(
+            registry.verify(&TeeType::IntelSgx, &test_quote(0), &[0x42; 32]),
+            Ok(())
+        );
+        for quote in [&[1][..], &[]] {
+            assert_eq!(
+                registry.verify(&TeeType::IntelSgx, quote, &[0x42; 32]),
+                Err(ValidationError::InvalidQuote)
+            );
+        }
+        // A genuine quote from some other enclave
+        assert_eq!(
+            registry.verify(&TeeType::IntelSgx, &test_quote(0), &[0x43; 32]),
+            Err(ValidationError::InvalidEnclaveCode)
+        );
+
+        for tee_type in [
+            TeeType::ArmTrustZone,
+            TeeType::AppleSecureEnclave,
+            TeeType::AmdSev,
+        ] {
+            assert_eq!(registry.verify(&tee_type, &[1], &[0x42; 32]), Ok(()));
+            assert_eq!(
+                registry.verify(&tee_type, &[], &[0x42; 32]),
+                Err(ValidationError::InvalidQuote)
+            );
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// MeshX - The Immutable Global Device Mesh
+// TEE type changes between registrations
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{MeshXNode, PopValidator, TeeType};
+
+// A node that re-registered with a different TEE type. It sits out
+// selection until it has re-attested on the new hardware and the hold has
+// run, since a switch is as likely to be a stolen identity as an upgrade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeeChangeHold {
+    pub until_epoch: u64,        // First epoch the node may be selected again
+    pub change_attestation: u64, // Timestamp of the attestation that switched type
+    pub reattested: bool,        // A newer attestation of the new type was seen
+}
+
+impl PopValidator {
+    // Record the TEE type a node registered with. With `tee_change_hold_epochs`
+    // set, a type differing from `previous` starts a hold; a later, newer
+    // attestation of the same type counts as the required re-attestation.
+    pub(crate) fn track_tee_type(&mut self, node: &MeshXNode, previous: Option<&TeeType>) {
+        let attestation = &node.tee_attestation;
+        let history = self.tee_type_history.entry(node.pubkey).or_default();
+        if let (true, Some(previous)) = (history.is_empty(), previous) {
+            history.push((self.current_epoch, previous.clone()));
+        }
+        if history.last().map(|(_, tee_type)| tee_type) != Some(&attestation.tee_type) {
+            history.push((self.current_epoch, attestation.tee_type.clone()));
+        }
+
+        match (previous, self.tee_change_hold_epochs) {
+            (Some(previous), Some(hold_epochs)) if *previous != attestation.tee_type => {
+                self.tee_change_holds.insert(
+                    node.pubkey,
+                    TeeChangeHold {
+                        until_epoch: self.current_epoch + hold_epochs,
+                        change_attestation: attestation.timestamp,
+                        reattested: false,
+                    },
+                );
+            }
+            _ => {
+                if let Some(hold) = self.tee_change_holds.get_mut(&node.pubkey) {
+                    hold.reattested |= attestation.timestamp > hold.change_attestation;
+                }
+            }
+        }
+    }
+
+    // Whether a TEE type change still keeps the node out of selection
+    pub fn in_tee_change_hold(&self, pubkey: &PublicKey, epoch: u64) -> bool {
+        self.tee_change_holds
+            .get(pubkey)
+            .is_some_and(|hold| !hold.reattested || epoch < hold.until_epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::ManualClock;
+    use crate::proof_of_presence::tests::test_network;
+    use crate::proof_of_presence::TeeType;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_tee_change_requires_reattestation_and_hold() {
+        let (mut validator, keys) = test_network(4);
+        validator.tee_change_hold_epochs = Some(2);
+        let mut node = validator.nodes[&keys[0]].clone();
+        let registered_at = node.tee_attestation.timestamp;
+        validator.set_clock(Arc::new(ManualClock::new(registered_at + 60)));
+
+        node.tee_attestation.tee_type = TeeType::AmdSev;
+        node.tee_attestation.timestamp += 1;
+        validator.register_node(node.clone()).unwrap();
+        assert_eq!(
+            validator.tee_type_history[&keys[0]]
+                .iter()
+                .map(|(_, tee_type)| tee_type.clone())
+                .collect::<Vec<_>>(),
+            vec![TeeType::IntelSgx, TeeType::AmdSev]
+        );
+        // Not re-attested, so even after the hold the node is held back
+        assert!(validator.in_tee_change_hold(&keys[0], 5));
+        assert!(!validator.select_validators(5).unwrap().contains(&keys[0]));
+
+        node.tee_attestation.timestamp += 1;
+        validator.register_node(node).unwrap();
+        assert!(validator.in_tee_change_hold(&keys[0], 1));
+        assert!(!validator.in_tee_change_hold(&keys[0], 2));
+        assert!(validator.select_validators(2).unwrap().contains(&keys[0]));
+    }
+
+    #[test]
+    fn test_tee_change_across_ban_still_held() {
+        let (mut validator, keys) = test_network(4);
+        validator.tee_change_hold_epochs = Some(2);
+        let mut node = validator.nodes[&keys[0]].clone();
+        validator.register_node(node.clone()).unwrap();
+
+        // Banned, then back on different hardware once the ban expires
+        validator.ban(&keys[0], Some(1));
+        validator.current_epoch = 1;
+        node.tee_attestation.tee_type = TeeType::AmdSev;
+        node.tee_attestation.timestamp += 1;
+        validator.register_node(node).unwrap();
+        assert!(validator.in_tee_change_hold(&keys[0], 3));
+        assert!(!validator.select_validators(3).unwrap().contains(&keys[0]));
+    }
+}
@@ -0,0 +1,87 @@
+// MeshX - The Immutable Global Device Mesh
+// Verified location history
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+
+use crate::proof_of_presence::{haversine_distance, GeoLocation, MeshXNode, PopValidator};
+
+impl PopValidator {
+    // Append a node's location as of its attestation once validation has
+    // verified it. Keeps at most `trajectory_len` points per node, dropping
+    // the oldest; 0 keeps none. A point no newer than the last is ignored.
+    pub(crate) fn record_trajectory_point(&mut self, node: &MeshXNode) {
+        let limit = self.trajectory_len;
+        if limit == 0 {
+            return;
+        }
+        let timestamp = node.tee_attestation.timestamp;
+        let points = self.trajectories.entry(node.pubkey).or_default();
+        if points.back().is_some_and(|(last, _)| *last >= timestamp) {
+            return;
+        }
+        points.push_back((timestamp, node.geo_location.clone()));
+        while points.len() > limit {
+            points.pop_front();
+        }
+    }
+
+    // Verified (timestamp, location) points for a node, oldest first
+    pub fn trajectory(&self, pubkey: &PublicKey) -> Vec<(u64, GeoLocation)> {
+        self.trajectories
+            .get(pubkey)
+            .map(|points| points.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    // Fastest great-circle speed in m/s between consecutive trajectory
+    // points; 0 with fewer than two points
+    pub fn max_observed_speed(&self, pubkey: &PublicKey) -> f64 {
+        let Some(points) = self.trajectories.get(pubkey) else {
+            return 0.0;
+        };
+        points
+            .iter()
+            .zip(points.iter().skip(1))
+            .map(|((t1, from), (t2, to))| haversine_distance(from, to) / (t2 - t1) as f64)
+            .fold(0.0, f64::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::test_node;
+
+    #[test]
+    fn test_trajectory_bounded_and_fastest_segment() {
+        let mut validator = PopValidator::new(1);
+        validator.trajectory_len = 3;
+        let mut node = test_node(1, 50.0, 10.0, 100_000);
+        let start = node.tee_attestation.timestamp;
+        // About 111 km per degree of latitude
+        for (offset_secs, latitude) in [(0, 50.0), (3600, 50.5), (7200, 51.5), (10_800, 51.6)] {
+            node.tee_attestation.timestamp = start + offset_secs;
+            node.geo_location.latitude = latitude;
+            validator.record_trajectory_point(&node);
+        }
+        // Replaying an old attestation adds nothing
+        validator.record_trajectory_point(&node);
+
+        let trajectory = validator.trajectory(&node.pubkey);
+        assert_eq!(trajectory.len(), 3);
+        assert_eq!(trajectory[0].0, start + 3600);
+
+        // The 50.5 -> 51.5 hour is the fastest, about 111 km/h
+        let speed = validator.max_observed_speed(&node.pubkey);
+        assert!((speed - 111_195.0 / 3600.0).abs() < 0.5, "speed {}", speed);
+
+        validator.trajectory_len = 0;
+        let mut other = test_node(2, 50.0, 10.0, 100_000);
+        validator.record_trajectory_point(&other);
+        other.tee_attestation.timestamp += 60;
+        validator.record_trajectory_point(&other);
+        assert!(validator.trajectory(&other.pubkey).is_empty());
+        assert_eq!(validator.max_observed_speed(&other.pubkey), 0.0);
+    }
+}
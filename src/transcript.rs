@@ -0,0 +1,187 @@
+// MeshX - The Immutable Global Device Mesh
+// Replayable selection transcripts
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{PopValidator, Shard, ValidationError, VrfProof};
+use crate::stake::StakeAmount;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptCandidate {
+    pub pubkey: PublicKey,
+    pub vrf: VrfProof,
+    pub effective_weight: StakeAmount,
+}
+
+// Everything that went into one shard's part of an epoch's selection:
+// the candidates in VRF order with their proofs and weights, and the nodes
+// selected from them, in selection order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionTranscript {
+    pub network_id: String,
+    pub shard: Shard,
+    pub epoch: u64,
+    pub candidates: Vec<TranscriptCandidate>,
+    pub selected: Vec<PublicKey>,
+}
+
+impl PopValidator {
+    pub fn selection_transcript(
+        &self,
+        shard: Shard,
+        epoch: u64,
+    ) -> Result<SelectionTranscript, ValidationError> {
+        let mut candidates: Vec<TranscriptCandidate> = self
+            .selection_candidates(epoch)
+            .into_iter()
+            .filter(|node| node.shard == shard)
+            .map(|node| TranscriptCandidate {
+                pubkey: node.pubkey,
+                vrf: self.vrf_prove(epoch, &node.pubkey),
+                effective_weight: self.effective_stake(&node.pubkey),
+            })
+            .collect();
+        candidates.sort_by_key(|candidate| candidate.vrf.output);
+
+        let selected = self
+            .select_validators(epoch)?
+            .into_iter()
+            .filter(|pubkey| candidates.iter().any(|c| c.pubkey == *pubkey))
+            .collect();
+        Ok(SelectionTranscript {
+            network_id: self.network_id.clone(),
+            shard,
+            epoch,
+            candidates,
+            selected,
+        })
+    }
+
+    // Replay a transcript against this validator's registry: every VRF
+    // proof must verify for its epoch on this validator's network, the
+    // candidates must be exactly the shard's, in VRF order and at their
+    // effective weights, and `selected` must be what ranking them and
+    // dropping invalid nodes selects
+    pub fn verify_selection_transcript(
+        &self,
+        transcript: &SelectionTranscript,
+    ) -> Result<(), ValidationError> {
+        if transcript.network_id != self.network_id {
+            return Err(ValidationError::VrfInputMismatch);
+        }
+        for candidate in &transcript.candidates {
+            self.vrf_verify(transcript.epoch, &candidate.pubkey, &candidate.vrf)?;
+        }
+        let in_vrf_order = transcript
+            .candidates
+            .windows(2)
+            .all(|pair| pair[0].vrf.output <= pair[1].vrf.output);
+
+        let mut listed: Vec<(PublicKey, StakeAmount)> = transcript
+            .candidates
+            .iter()
+            .map(|candidate| (candidate.pubkey, candidate.effective_weight))
+            .collect();
+        let mut shard_candidates: Vec<(PublicKey, StakeAmount)> = self
+            .ranked_candidates(transcript.epoch)
+            .into_iter()
+            .filter(|node| node.shard == transcript.shard)
+            .map(|node| (node.pubkey, self.effective_stake(&node.pubkey)))
+            .collect();
+        listed.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+        shard_candidates.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+        let replayed: Vec<PublicKey> = self
+            .select_validators(transcript.epoch)?
+            .into_iter()
+            .filter(|pubkey| listed.iter().any(|(listed, _)| listed == pubkey))
+            .collect();
+        if !in_vrf_order || listed != shard_candidates || transcript.selected != replayed {
+            return Err(ValidationError::TranscriptMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_of_presence::tests::{test_network, test_pubkey};
+
+    #[test]
+    fn test_selection_transcript_round_trip() {
+        let (mut validator, _) = test_network(5);
+        validator.validator_count = 3;
+        let transcript = validator.selection_transcript(Shard::Europe, 42).unwrap();
+        assert_eq!(transcript.candidates.len(), 5);
+        assert_eq!(transcript.selected.len(), 3);
+        assert_eq!(validator.verify_selection_transcript(&transcript), Ok(()));
+
+        let json = serde_json::to_string(&transcript).unwrap();
+        let decoded: SelectionTranscript = serde_json::from_str(&json).unwrap();
+        assert_eq!(validator.verify_selection_transcript(&decoded), Ok(()));
+
+        // Swapping two candidates' VRF outputs breaks their proofs
+        let mut swapped = transcript.clone();
+        let first = swapped.candidates[0].vrf.output;
+        swapped.candidates[0].vrf.output = swapped.candidates[1].vrf.output;
+        swapped.candidates[1].vrf.output = first;
+        assert_eq!(
+            validator.verify_selection_transcript(&swapped),
+            Err(ValidationError::InvalidSignature)
+        );
+
+        let mut reordered = transcript.clone();
+        reordered.candidates.swap(0, 1);
+        assert_eq!(
+            validator.verify_selection_transcript(&reordered),
+            Err(ValidationError::TranscriptMismatch)
+        );
+
+        let mut outsider = transcript.clone();
+        outsider.selected.push(test_pubkey(9));
+        assert_eq!(
+            validator.verify_selection_transcript(&outsider),
+            Err(ValidationError::TranscriptMismatch)
+        );
+
+        // Every selected node is a candidate, but not the ones the ranking
+        // picks
+        let passed_over = transcript
+            .candidates
+            .iter()
+            .find(|candidate| !transcript.selected.contains(&candidate.pubkey))
+            .unwrap()
+            .pubkey;
+        let mut substituted = transcript.clone();
+        substituted.selected[0] = passed_over;
+        assert_eq!(
+            validator.verify_selection_transcript(&substituted),
+            Err(ValidationError::TranscriptMismatch)
+        );
+        let mut resorted = transcript.clone();
+        resorted.selected.swap(0, 1);
+        assert_eq!(
+            validator.verify_selection_transcript(&resorted),
+            Err(ValidationError::TranscriptMismatch)
+        );
+
+        // Leaving out a candidate, or misstating its weight
+        let mut omitted = transcript.clone();
+        omitted
+            .candidates
+            .retain(|candidate| candidate.pubkey != passed_over);
+        assert_eq!(
+            validator.verify_selection_transcript(&omitted),
+            Err(ValidationError::TranscriptMismatch)
+        );
+        let mut inflated = transcript;
+        inflated.candidates[0].effective_weight = StakeAmount::from_meshx(1_000_000);
+        assert_eq!(
+            validator.verify_selection_transcript(&inflated),
+            Err(ValidationError::TranscriptMismatch)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+// MeshX - The Immutable Global Device Mesh
+// Delayed unstaking
+// Copyright (c) 2025 MeshX Foundation
+
+use ed25519_dalek::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::proof_of_presence::{PopValidator, ValidationError};
+use crate::stake::StakeAmount;
+
+// Stake a node has asked to withdraw. It keeps counting toward the node's
+// stake until the unbonding delay passes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingUnstake {
+    pub amount: StakeAmount,
+    pub matures_epoch: u64, // First epoch without this stake
+}
+
+impl PopValidator {
+    // Queue a withdrawal maturing `unstake_delay_epochs` from now. Returns
+    // the epoch it matures in.
+    pub fn request_unstake(
+        &mut self,
+        pubkey: &PublicKey,
+        amount: StakeAmount,
+    ) -> Result<u64, ValidationError> {
+        let stake = self
+            .nodes
+            .get(pubkey)
+            .ok_or(ValidationError::UnknownNode)?
+            .stake_amount;
+        if amount > stake.saturating_sub(self.pending_unstake_total(pubkey)) {
+            return Err(ValidationError::InsufficientStake);
+        }
+        let matures_epoch = self.current_epoch + self.unstake_delay_epochs;
+        self.pending_unstakes
+            .entry(*pubkey)
+            .or_default()
+            .push(PendingUnstake {
+                amount,
+                matures_epoch,
+            });
+        Ok(matures_epoch)
+    }
+
+    pub fn pending_unstake_total(&self, pubkey: &PublicKey) -> StakeAmount {
+        self.pending_unstakes
+            .get(pubkey)
+            .into_iter()
+            .flatten()
+            .fold(StakeAmount::ZERO, |total, unstake| {
+                total.saturating_add(unstake.amount)
+            })
+    }
+
+    // The node's stake once every pending unstake has matured
+    pub fn stake_after_unstakes(&self, pubkey: &PublicKey) -> Option<StakeAmount> {
+        self.nodes.get(pubkey).map(|node| {
+            node.stake_amount
+                .saturating_sub(self.pending_unstake_total(pubkey))
+        })
+    }
+
+    // Remove matured unstakes from their nodes' stake
+    pub fn settle_unstakes(&mut self, epoch: u64) {
+        for (pubkey, unstakes) in self.pending_unstakes.iter_mut() {
+            let node = self.nodes.get_mut(pubkey);
+            let mut matured = StakeAmount::ZERO;
+            unstakes.retain(|unstake| {
+                let pending = unstake.matures_epoch > epoch;
+                if !pending {
+                    matured = matured.saturating_add(unstake.amount);
+                }
+                pending
+            });
+            if let Some(node) = node {
+                node.stake_amount = node.stake_amount.saturating_sub(matured);
+            }
+        }
+        self.pending_unstakes
+            .retain(|pubkey, unstakes| !unstakes.is_empty() && self.nodes.contains_key(pubkey));
+    }
+}